@@ -0,0 +1,103 @@
+#[cfg(not(feature = "colorization"))]
+use alloc::format;
+#[cfg(not(feature = "colorization"))]
+use alloc::string::String;
+#[cfg(not(feature = "colorization"))]
+use alloc::vec;
+#[cfg(not(feature = "colorization"))]
+use alloc::vec::Vec;
+use std::io::Write;
+
+#[cfg(not(feature = "colorization"))]
+use anstyle::{Effects, Style};
+
+use crate::color_choice::ColorChoice;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// Colorizes *input* with *whole_marker*, then colorizes the substrings of *markers* with their
+/// styles and underlines *marked_spans*, where substrings that aren't references taken from
+/// *input* are ignored.
+///
+/// Unlike `string_colorization::colorize`, each marker replaces the style of the parts it covers
+/// instead of being added on top of the whole marker, as anstyle styles describe every attribute,
+/// and later markers take precedence over earlier ones.
+#[cfg(not(feature = "colorization"))]
+pub(crate) fn colorize(input: &str, whole_marker: Option<Style>, markers: Vec<(&str, Style)>, marked_spans: &[&str]) -> String {
+    let range_of = |substring: &str| {
+        let start = (substring.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+        let end = start.checked_add(substring.len()).filter(|end| *end <= input.len())?;
+        Some(start..end)
+    };
+    let mut styles = vec![whole_marker.unwrap_or_default(); input.len()];
+    for (substring, style) in markers {
+        if let Some(range) = range_of(substring) {
+            styles[range].fill(style);
+        }
+    }
+    for range in marked_spans.iter().filter_map(|substring| range_of(substring)) {
+        styles[range].iter_mut().for_each(|style| *style = style.effects(style.get_effects() | Effects::UNDERLINE));
+    }
+    let mut res = String::with_capacity(input.len());
+    let mut current_style = Style::new();
+    for (index, char) in input.char_indices() {
+        if styles[index] != current_style {
+            res.push_str(&format!("{}{}", current_style.render_reset(), styles[index].render()));
+            current_style = styles[index];
+        }
+        res.push(char);
+    }
+    res.push_str(&format!("{}", current_style.render_reset()));
+    res
+}
+
+impl SimpleErrorDisplayInfo {
+    /// This is only implemented when using the colorization-anstyle feature.
+    ///
+    /// Writes this error into *stream* through anstream, which keeps or removes its colors
+    /// depending on whether that specific stream supports them, following the `NO_COLOR`,
+    /// `CLICOLOR` and `CLICOLOR_FORCE` conventions too, so the decision is taken per stream rather
+    /// than process-wide, like when stderr is a terminal while stdout is redirected to a file.
+    pub fn write_to_stream<Stream: anstream::stream::RawStream + anstream::stream::AsLockedWrite>(&self, stream: Stream) -> std::io::Result<()> {
+        let mut stream = anstream::AutoStream::auto(stream);
+        writeln!(stream, "{}", self.as_display_string())
+    }
+}
+
+impl<'input> SimpleError<'input> {
+    /// This is only implemented when using the colorization-anstyle feature.
+    ///
+    /// Renders this error with colors and writes it into *stream* through anstream, which keeps or
+    /// removes said colors depending on whether that specific stream supports them (See
+    /// [SimpleErrorDisplayInfo::write_to_stream]).
+    pub fn write_to_stream<Stream: anstream::stream::RawStream + anstream::stream::AsLockedWrite>(&self, stream: Stream) -> std::io::Result<()> {
+        self.as_display_struct(ColorChoice::Always).write_to_stream(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[cfg(not(feature = "colorization"))]
+    #[test]
+    fn markers_replace_the_style_of_the_parts_they_cover() {
+        let input = "let x = y;";
+        let (red, blue) = (Style::new().fg_color(Some(anstyle::AnsiColor::Red.into())), Style::new().fg_color(Some(anstyle::AnsiColor::Blue.into())));
+        let colorized = colorize(input, Some(blue), vec![(&input[4..5], red), ("x", red)], &[&input[8..9]]);
+        let underlined_blue = blue.effects(Effects::UNDERLINE);
+        assert_eq!(colorized, format!("{blue}let {blue:#}{red}x{red:#}{blue} = {blue:#}{underlined_blue}y{underlined_blue:#}{blue};{blue:#}"));
+    }
+
+    #[test]
+    fn streams_without_color_support_get_plain_text() {
+        let input = "let x = y;";
+        let error = SimpleError::new().at(input).error_detail("Unexpected \u{1b}[31mx\u{1b}[0m".to_string());
+        let mut stream = Vec::new();
+        error.write_to_stream(&mut stream).unwrap();
+        assert_eq!(String::from_utf8(stream).unwrap(), "At: let x = y;\nError: Unexpected x\n");
+    }
+}
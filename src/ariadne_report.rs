@@ -0,0 +1,123 @@
+use std::fmt::Debug;
+use std::format;
+use std::hash::Hash;
+use std::ops::Range;
+use std::string::String;
+
+use ariadne::{Label, Report, ReportBuilder, ReportKind};
+
+use crate::severity::Severity;
+use crate::simple_error::SimpleError;
+use crate::span::Span;
+
+impl<'input> SimpleError<'input> {
+    /// This is only implemented when using the ariadne feature.
+    ///
+    /// Turns this error into an ariadne [Report] on the source *source_id*, which can be printed
+    /// through ariadne's multi-line caret rendering by giving it the source this error points into,
+    /// like `report.print((source_id, Source::from(input)))`, where:
+    /// * The explanation becomes the message, while the code is kept as the report's code, and the
+    ///   severity becomes the report's kind, where hints and infos become advices.
+    /// * The location given on [SimpleError::at] becomes a label without message, this requires the
+    ///   location to be resolved through [SimpleError::with_source] or a [crate::Span].
+    /// * Labels become labels, and parts of the input given colorization markers or emphasized
+    ///   become labels without message.
+    /// * The solution and its steps become notes, as do notes, while helps are kept as helps.
+    /// * Causes located in the source become labels holding their explanation, along with their own
+    ///   causes, and the rest become notes holding their summary.
+    pub fn to_ariadne_report<SourceId>(&self, source_id: SourceId) -> Report<'static, (SourceId, Range<usize>)>
+        where SourceId: Debug + Hash + PartialEq + Eq + ToOwned<Owned=SourceId> + Clone {
+        let explanation = self.current_error_detail().as_ref()
+            .map(|error_detail| error_detail.explain_error().evaluated())
+            .unwrap_or_default();
+        let source = self.current_source();
+        let error_span = self.resolve_position(source).0;
+        let kind = match explanation.severity {
+            Severity::Hint | Severity::Info => ReportKind::Advice,
+            Severity::Warning => ReportKind::Warning,
+            Severity::Error => ReportKind::Error,
+        };
+        let offset = error_span.map(|span| span.start).unwrap_or_default();
+        let mut report = Report::build(kind, source_id.clone(), offset)
+            .with_message(self.as_display_struct(false).reason().unwrap_or("Unexplained error"));
+        if let Some(code) = &explanation.code {
+            report = report.with_code(code);
+        }
+        let label = |span: Span| Label::new((source_id.clone(), Range::from(span)));
+        if let Some(span) = error_span {
+            report = report.with_label(label(span));
+        }
+        if let Some(source) = source {
+            for (location, message) in &explanation.labels {
+                if let Some(span) = location.span(source) {
+                    report = report.with_label(label(span).with_message(message));
+                }
+            }
+            #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+            for span in explanation.colorization_markers.iter().filter_map(|(marked, _)| Span::of(source, marked))
+                .chain(explanation.span_colorization_markers.iter().map(|(span, _)| *span)) {
+                report = report.with_label(label(span));
+            }
+            for span in explanation.marked_spans.iter().filter_map(|marked| Span::of(source, marked)) {
+                report = report.with_label(label(span));
+            }
+        }
+        if let Some(solution) = &explanation.solution {
+            report = report.with_note(format!("Solution: {solution}"));
+        }
+        for (step_no, step) in explanation.solution_steps.iter().enumerate() {
+            report = report.with_note(format!("{}. {step}", step_no + 1));
+        }
+        for note in &explanation.notes {
+            report = report.with_note(note);
+        }
+        for help in &explanation.helps {
+            report = report.with_help(help);
+        }
+        for cause in self.current_causes() {
+            report = cause.add_to_ariadne_report(report, &source_id, source);
+        }
+        report.finish()
+    }
+
+    fn add_to_ariadne_report<SourceId>(&self, report: ReportBuilder<'static, (SourceId, Range<usize>)>, source_id: &SourceId, inherited_source: Option<&'input str>) -> ReportBuilder<'static, (SourceId, Range<usize>)>
+        where SourceId: Debug + Hash + PartialEq + Eq + ToOwned<Owned=SourceId> + Clone {
+        let source = self.current_source().or(inherited_source);
+        let display = self.as_display_struct(false);
+        match self.resolve_position(source) {
+            (Some(span), _, _) => {
+                let message = display.reason().map(String::from).unwrap_or_default();
+                let report = report.with_label(Label::new((source_id.clone(), Range::from(span))).with_message(message));
+                self.current_causes().iter().fold(report, |report, cause| cause.add_to_ariadne_report(report, source_id, source))
+            }
+            _ if display.is_explained() => report.with_note(display.summary()),
+            _ => report,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::*;
+    use ariadne::Source;
+    use crate::SimpleErrorExplanation;
+    use crate::formatting::strip_ansi;
+
+    #[test]
+    fn errors_become_ariadne_reports_with_labels_and_notes() {
+        let input = "let x = y;";
+        let explanation = SimpleErrorExplanation::new().explanation("Variable y doesn't exist").solution("Declare y").code("E1")
+            .label(&input[4..5], "Assigned here");
+        let error = SimpleError::new().with_source(input).at(&input[8..9]).error_detail(explanation)
+            .with_cause(SimpleError::new().at(&input[0..3]).error_detail(String::from("Declaration started here")))
+            .with_cause(String::from("Couldn't resolve imports"));
+        let mut written = Vec::new();
+        error.to_ariadne_report("main.script").write(("main.script", Source::from(input)), &mut written).unwrap();
+        let written = strip_ansi(&String::from_utf8(written).unwrap());
+        for expected in ["[E1]", "Variable y doesn't exist", "Assigned here", "Declaration started here", "Declare y", "Couldn't resolve imports"] {
+            assert!(written.contains(expected), "{expected} not found in {written}");
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::format;
+use alloc::string::String;
+
+use crate::report::ReportDisplayInfo;
+use crate::severity::Severity;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// Gives this error as a Checkstyle XML document, holding a single `error` element inside the
+    /// `file` element named *file_name*, like
+    /// `<error line="3" column="7" severity="error" message="..." source="E001"/>`, since many CI
+    /// quality plugins only consume this format.
+    ///
+    /// The causes of this error are flattened into its message as done by
+    /// [SimpleErrorDisplayInfo::summary], where causes deeper than *cause_depth* levels are only
+    /// counted, so a depth of 0 only says how many causes it has. Hints are given the `info`
+    /// severity, the line and column are left out when the error has no position, and colors are
+    /// removed.
+    pub fn as_checkstyle_string(&self, file_name: &str, cause_depth: usize) -> String {
+        checkstyle_document(file_name, core::slice::from_ref(self), cause_depth)
+    }
+}
+
+impl ReportDisplayInfo {
+    /// Gives every error of the report as a Checkstyle XML document, see
+    /// [SimpleErrorDisplayInfo::as_checkstyle_string].
+    pub fn as_checkstyle_string(&self, file_name: &str, cause_depth: usize) -> String {
+        checkstyle_document(file_name, &self.errors, cause_depth)
+    }
+}
+
+fn checkstyle_document(file_name: &str, errors: &[SimpleErrorDisplayInfo], cause_depth: usize) -> String {
+    let mut res = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+    res.push_str(&format!("  <file name=\"{}\">\n", escape_xml(file_name)));
+    errors.iter().for_each(|error| res.push_str(&format!("    {}\n", checkstyle_error(error, cause_depth))));
+    res.push_str("  </file>\n</checkstyle>");
+    res
+}
+
+/// Gives the `error` element for *display*.
+fn checkstyle_error(display: &SimpleErrorDisplayInfo, cause_depth: usize) -> String {
+    let position = display.on_line_and_column
+        .map(|(line, column)| format!("line=\"{line}\" column=\"{column}\" "))
+        .unwrap_or_default();
+    let severity = match display.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "info",
+    };
+    let message = escape_xml(&display.summary_up_to_depth(cause_depth));
+    let source = display.code.as_ref().map(|code| format!(" source=\"{}\"", escape_xml(code))).unwrap_or_default();
+    format!("<error {position}severity=\"{severity}\" message=\"{message}\"{source}/>")
+}
+
+/// Escapes *text* so it can be placed inside an XML attribute, where characters XML doesn't allow
+/// are removed.
+fn escape_xml(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&apos;"),
+            '\n' => res.push_str("&#10;"),
+            '\r' => res.push_str("&#13;"),
+            '\t' => res.push_str("&#9;"),
+            char if char.is_control() || matches!(char, '\u{fffe}' | '\u{ffff}') => {}
+            char => res.push(char),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SimpleError, SimpleErrorExplanation};
+
+    #[test]
+    fn errors_become_escaped_checkstyle_elements() {
+        let input = "let x = 1;";
+        let explained = |explanation| SimpleError::from(SimpleErrorExplanation::new().explanation(explanation).code("E<1>"));
+        let error = explained("Invalid \"x\"").at(&input[4..5]).with_source(input)
+            .with_cause(explained("Reserved name").with_cause(explained("Shadows a keyword")));
+        assert_eq!(error.as_display_struct(false).as_checkstyle_string("scripts&tests.txt", 1), concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n  <file name=\"scripts&amp;tests.txt\">\n",
+            "    <error line=\"1\" column=\"5\" severity=\"error\" message=\"Invalid &quot;x&quot; (1 cause: Reserved name (1 cause))\" source=\"E&lt;1&gt;\"/>\n",
+            "  </file>\n</checkstyle>"));
+    }
+}
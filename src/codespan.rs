@@ -0,0 +1,198 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use codespan_reporting::diagnostic::{Diagnostic, Label, LabelStyle, Severity as CodespanSeverity};
+
+use crate::severity::Severity;
+use crate::simple_error::SimpleError;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+use crate::span::SourceId;
+
+/// Prefix given to the notes holding the solution of an error when exporting it to codespan.
+const SOLUTION_NOTE_PREFIX: &str = "solution: ";
+/// Prefix given to the notes holding each solution step of an error when exporting it to codespan.
+const SOLUTION_STEP_NOTE_PREFIX: &str = "solution step: ";
+/// Prefix given to the notes holding helps of an error when exporting it to codespan.
+const HELP_NOTE_PREFIX: &str = "help: ";
+/// Prefix given to the note holding the documentation link of an error when exporting it to
+/// codespan.
+const DOC_URL_NOTE_PREFIX: &str = "more info: ";
+
+impl<'input> SimpleError<'input> {
+    /// This is only implemented when using the codespan feature.
+    ///
+    /// Turns this error into a codespan-reporting [Diagnostic] on the file *file_id*, where:
+    /// * The explanation becomes the message, while the code and severity are kept as the
+    ///   diagnostic's code and severity.
+    /// * The location given on [SimpleError::at] becomes the primary label, this requires the
+    ///   location to be resolved through [SimpleError::with_source] or a [crate::Span].
+    /// * Labels become secondary labels.
+    /// * Notes are kept as notes, while helps, the solution, its steps and the documentation link
+    ///   become notes prefixed by 'help: ', 'solution: ', 'solution step: ' and 'more info: '.
+    /// * Causes located in the source become secondary labels holding their explanation, and the
+    ///   rest become notes holding their display.
+    pub fn to_codespan_diagnostic<FileId: Copy>(&self, file_id: FileId) -> Diagnostic<FileId> {
+        self.to_codespan_diagnostic_with_files(file_id, |_| None)
+    }
+
+    /// This is only implemented when using the codespan feature.
+    ///
+    /// Turns this error into a codespan-reporting [Diagnostic] as done by
+    /// [SimpleError::to_codespan_diagnostic], but for projects keeping a codespan `Files` database,
+    /// where errors given a [crate::SourceId] through [SimpleError::in_source] have their labels
+    /// placed on the file given by *file_of* for said id, and the rest of errors use the file of
+    /// the error they caused, or *file_id* for this error.
+    ///
+    /// This way, causes happening on other files, like an included file, are labeled on the file
+    /// they happen at.
+    pub fn to_codespan_diagnostic_with_files<FileId: Copy, FileOf: Fn(SourceId) -> Option<FileId>>(&self, file_id: FileId, file_of: FileOf) -> Diagnostic<FileId> {
+        let file_id = self.current_source_id().and_then(&file_of).unwrap_or(file_id);
+        let explanation = self.current_explanation();
+        let severity = match explanation.severity {
+            Severity::Hint => CodespanSeverity::Help,
+            Severity::Info => CodespanSeverity::Note,
+            Severity::Warning => CodespanSeverity::Warning,
+            Severity::Error => CodespanSeverity::Error,
+        };
+        let mut diagnostic = Diagnostic::new(severity)
+            .with_message(explanation.explanation.clone().unwrap_or_default());
+        diagnostic.code = explanation.code.clone();
+        let source = self.current_source();
+        if let (Some(span), _, _) = self.resolve_position(source) {
+            diagnostic.labels.push(Label::primary(file_id, span));
+        }
+        diagnostic.labels.extend(explanation.labels.iter()
+            .filter_map(|(location, message)| source.and_then(|source| location.span(source))
+                .map(|span| Label::secondary(file_id, span).with_message(message.clone()))));
+        diagnostic.notes.extend(explanation.notes.iter().cloned());
+        diagnostic.notes.extend(explanation.helps.iter().map(|help| format!("{HELP_NOTE_PREFIX}{help}")));
+        diagnostic.notes.extend(explanation.solution.iter().map(|solution| format!("{SOLUTION_NOTE_PREFIX}{solution}")));
+        diagnostic.notes.extend(explanation.solution_steps.iter().map(|step| format!("{SOLUTION_STEP_NOTE_PREFIX}{step}")));
+        diagnostic.notes.extend(explanation.doc_url.iter().map(|doc_url| format!("{DOC_URL_NOTE_PREFIX}{doc_url}")));
+        self.current_causes().iter().for_each(|cause| cause.add_to_codespan_diagnostic(&mut diagnostic, file_id, &file_of, source));
+        diagnostic
+    }
+
+    fn add_to_codespan_diagnostic<FileId: Copy, FileOf: Fn(SourceId) -> Option<FileId>>(&self, diagnostic: &mut Diagnostic<FileId>, inherited_file_id: FileId, file_of: &FileOf, inherited_source: Option<&'input str>) {
+        let source = self.current_source().or(inherited_source);
+        let file_id = self.current_source_id().and_then(file_of).unwrap_or(inherited_file_id);
+        match self.resolve_position(source) {
+            (Some(span), _, _) => {
+                let message = self.current_explanation().explanation.unwrap_or_default();
+                diagnostic.labels.push(Label::secondary(file_id, span).with_message(message));
+                self.current_causes().iter().for_each(|cause| cause.add_to_codespan_diagnostic(diagnostic, file_id, file_of, source));
+            }
+            _ => {
+                let display = self.as_display_struct(false);
+                if display.is_explained() {
+                    diagnostic.notes.push(display.as_display_string());
+                }
+            }
+        }
+    }
+
+    fn current_explanation(&self) -> SimpleErrorExplanation<'_> {
+        self.current_error_detail().as_ref()
+            .map(|error_detail| error_detail.explain_error().evaluated())
+            .unwrap_or_default()
+    }
+
+    /// This is only implemented when using the codespan feature.
+    ///
+    /// Creates an error from a codespan-reporting [Diagnostic] whose labels point into *source*,
+    /// where:
+    /// * The message becomes the explanation, while the code and severity are kept as the error's
+    ///   code and severity, where bugs are considered errors.
+    /// * The first primary label becomes the location of the error, and the rest of primary labels
+    ///   become causes located at them.
+    /// * Secondary labels become labels.
+    /// * Notes prefixed by 'help: ' and 'solution: ' become helps and the solution, as written by
+    ///   [SimpleError::to_codespan_diagnostic], and the rest are kept as notes.
+    ///
+    /// Labels are expected to point into *source*, their file ids are not taken into account.
+    pub fn from_codespan_diagnostic<FileId>(diagnostic: &Diagnostic<FileId>, source: &'input str) -> Self {
+        let severity = match diagnostic.severity {
+            CodespanSeverity::Help => Severity::Hint,
+            CodespanSeverity::Note => Severity::Info,
+            CodespanSeverity::Warning => Severity::Warning,
+            CodespanSeverity::Error | CodespanSeverity::Bug => Severity::Error,
+        };
+        let mut explanation = SimpleErrorExplanation::new().explanation(diagnostic.message.clone()).severity(severity);
+        if let Some(code) = &diagnostic.code {
+            explanation = explanation.code(code.clone());
+        }
+        for note in &diagnostic.notes {
+            explanation = if let Some(help) = note.strip_prefix(HELP_NOTE_PREFIX) {
+                explanation.help(help)
+            } else if let Some(doc_url) = note.strip_prefix(DOC_URL_NOTE_PREFIX) {
+                explanation.doc_url(doc_url)
+            } else if let Some(step) = note.strip_prefix(SOLUTION_STEP_NOTE_PREFIX) {
+                explanation.solution_step(step)
+            } else if let Some(solution) = note.strip_prefix(SOLUTION_NOTE_PREFIX) {
+                explanation.solution(solution)
+            } else {
+                explanation.note(note.clone())
+            };
+        }
+        let (primary_labels, secondary_labels): (Vec<_>, Vec<_>) = diagnostic.labels.iter()
+            .partition(|label| label.style == LabelStyle::Primary);
+        for label in secondary_labels {
+            explanation = explanation.label(label.range.clone(), label.message.clone());
+        }
+        let mut primary_labels = primary_labels.into_iter();
+        let mut res = SimpleError::new().with_source(source);
+        if let Some(label) = primary_labels.next() {
+            explanation = explanation.label(label.range.clone(), label.message.clone());
+            res = res.at_span(label.range.clone());
+        }
+        for label in primary_labels {
+            res.add_cause(SimpleError::new()
+                .error_detail(SimpleErrorExplanation::new().explanation(label.message.to_string()))
+                .at_span(label.range.clone()));
+        }
+        res.error_detail(explanation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn diagnostics_round_trip_through_codespan() {
+        let source = "let x = 1;\nlet x = 2;";
+        let explanation = SimpleErrorExplanation::new().explanation("x is defined twice").code("E1").solution("Rename x")
+            .help("Shadowing requires a new let").note("Variables are immutable").label(&source[4..5], "First defined here");
+        let error = SimpleError::new().error_detail(explanation).at(&source[15..16]).with_source(source)
+            .with_cause(SimpleError::from(String::from("Declared here")).at(&source[0..3]))
+            .with_cause(String::from("Unlocated cause"));
+        let diagnostic = error.to_codespan_diagnostic(());
+        assert_eq!(diagnostic.message, "x is defined twice");
+        assert_eq!(diagnostic.code.as_deref(), Some("E1"));
+        assert_eq!(diagnostic.labels.iter().map(|label| (label.style, label.range.clone())).collect::<Vec<_>>(),
+                   [(LabelStyle::Primary, 15..16), (LabelStyle::Secondary, 4..5), (LabelStyle::Secondary, 0..3)]);
+        assert_eq!(diagnostic.labels[2].message, "Declared here");
+        assert_eq!(diagnostic.notes, ["Variables are immutable", "help: Shadowing requires a new let", "solution: Rename x", "Error: Unlocated cause"]);
+
+        let imported = SimpleError::from_codespan_diagnostic(&diagnostic, source).as_display_struct(false).as_display_string();
+        assert!(imported.starts_with("Position: On line 2 and column 5 up to line 2 and column 5\nAt: x\nLabel: 'x' on line 1 and column 5: First defined here"), "{imported}");
+        let warning = Diagnostic::<()>::warning().with_message("Unused variable")
+            .with_labels(vec![Label::primary((), 0..3), Label::primary((), 4..5).with_message("Also unused")]);
+        assert_eq!(SimpleError::from_codespan_diagnostic(&warning, source).current_causes().len(), 1);
+    }
+
+    #[test]
+    fn labels_are_placed_on_the_file_of_their_source_id() {
+        let main = "include utils;";
+        let utils = "fn ?";
+        let error = SimpleError::new().with_source(main).in_source(SourceId(1)).at(&main[8..13]).error_detail(String::from("Couldn't include utils"))
+            .with_cause(SimpleError::new().with_source(utils).in_source(SourceId(2)).at(&utils[3..4]).error_detail(String::from("Unexpected ?")));
+        let diagnostic = error.to_codespan_diagnostic_with_files(0usize, |source_id| Some(source_id.0 as usize * 10));
+        assert_eq!(diagnostic.labels.iter().map(|label| (label.file_id, label.range.clone())).collect::<Vec<_>>(), [(10, 8..13), (20, 3..4)]);
+        assert!(error.to_codespan_diagnostic(7usize).labels.iter().all(|label| label.file_id == 7));
+    }
+}
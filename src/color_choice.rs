@@ -0,0 +1,167 @@
+/// Whether errors are rendered with colors, given to [crate::SimpleError::as_display_struct].
+///
+/// Booleans turn into color choices too, where `true` means [ColorChoice::Auto] and `false` means
+/// [ColorChoice::Never], so code passing a `colorize: bool` keeps working.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Colors are used unless the environment asks otherwise, following the `NO_COLOR`,
+    /// `CLICOLOR` and `CLICOLOR_FORCE` conventions, or when they are forced through
+    /// `colored::control::set_override(true)` (See [ColorChoice::should_colorize]).
+    #[default]
+    Auto,
+    /// Colors are always used, regardless of the environment.
+    Always,
+    /// Colors are never used, removing any escape code from the rendered texts.
+    Never,
+    /// Colors are used as in [ColorChoice::Auto], but only as long as the standard output is a
+    /// terminal when rendering or colors are forced through `colored::control::set_override(true)`,
+    /// so output piped to a file or to other program gets plain text.
+    ///
+    /// Without the std feature, this is the same as [ColorChoice::Auto].
+    Stdout,
+    /// Colors are used as in [ColorChoice::Auto], but only as long as the standard error is a
+    /// terminal when rendering or colors are forced through `colored::control::set_override(true)`,
+    /// so output redirected to a file gets plain text, this is what errors displayed through
+    /// [core::fmt::Display] use, as they are usually written to stderr.
+    ///
+    /// Without the std feature, this is the same as [ColorChoice::Auto].
+    Stderr,
+}
+
+impl ColorChoice {
+    /// Tells if this choice results in colors being used, where [ColorChoice::Auto] checks, in
+    /// order:
+    /// * `colored::control::set_override(true)`: When using the colorization feature and colors are
+    ///   forced through it, colors are used.
+    /// * `NO_COLOR`: When set to anything but an empty string, colors aren't used.
+    /// * `CLICOLOR_FORCE`: When set to anything but `0`, colors are used.
+    /// * `CLICOLOR`: When set to `0`, colors aren't used.
+    ///
+    /// The environment variables are only checked when using the std feature. Colors are used if
+    /// none of them decide it, while [ColorChoice::Stdout] and [ColorChoice::Stderr] check whether
+    /// their stream is a terminal instead.
+    ///
+    /// Note that this decides whether the escape codes produced while rendering are kept, whether
+    /// `colored` itself emits them still depends on its own terminal detection.
+    ///
+    /// ```rust
+    /// use simple_detailed_error::ColorChoice;
+    ///
+    /// colored::control::set_override(true);
+    /// assert!(ColorChoice::Stderr.should_colorize());
+    /// ```
+    ///
+    /// The environment variables are checked in the order above, and they decide even for
+    /// [ColorChoice::Stdout] and [ColorChoice::Stderr] before checking their streams:
+    ///
+    /// ```rust
+    /// use simple_detailed_error::ColorChoice;
+    ///
+    /// std::env::set_var("NO_COLOR", "1");
+    /// assert!(!ColorChoice::Auto.should_colorize());
+    /// assert!(!ColorChoice::Stderr.should_colorize());
+    /// assert!(ColorChoice::Always.should_colorize());
+    ///
+    /// std::env::set_var("NO_COLOR", "");
+    /// std::env::set_var("CLICOLOR", "0");
+    /// assert!(!ColorChoice::Auto.should_colorize());
+    ///
+    /// std::env::set_var("CLICOLOR_FORCE", "1");
+    /// assert!(ColorChoice::Auto.should_colorize());
+    /// assert!(ColorChoice::Stdout.should_colorize());
+    /// assert!(ColorChoice::Stderr.should_colorize());
+    /// ```
+    pub fn should_colorize(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_forced_by_colored() || env_color_choice().unwrap_or(true),
+            ColorChoice::Stdout => is_forced_by_colored() || env_color_choice().unwrap_or_else(|| is_terminal(Stream::Stdout)),
+            ColorChoice::Stderr => is_forced_by_colored() || env_color_choice().unwrap_or_else(|| is_terminal(Stream::Stderr)),
+        }
+    }
+}
+
+impl From<bool> for ColorChoice {
+    fn from(colorize: bool) -> Self {
+        if colorize { ColorChoice::Auto } else { ColorChoice::Never }
+    }
+}
+
+/// Standard stream checked by [ColorChoice::Stdout] and [ColorChoice::Stderr].
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Tells if colors were forced through `colored::control::set_override(true)`, which `colored`
+/// doesn't expose, so it is told apart by `colored` using colors where it wouldn't from the
+/// environment alone.
+#[cfg(feature = "colorization")]
+fn is_forced_by_colored() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize() && !colored::control::ShouldColorize::from_env().should_colorize()
+}
+
+#[cfg(not(feature = "colorization"))]
+fn is_forced_by_colored() -> bool {
+    false
+}
+
+/// Returns whether the environment variables ask for colors or not, see
+/// [ColorChoice::should_colorize], or None if they don't decide it.
+#[cfg(feature = "std")]
+fn env_color_choice() -> Option<bool> {
+    let var = |name: &str| std::env::var_os(name);
+    if var("NO_COLOR").is_some_and(|no_color| !no_color.is_empty()) {
+        return Some(false);
+    }
+    if var("CLICOLOR_FORCE").is_some_and(|clicolor_force| clicolor_force != "0") {
+        return Some(true);
+    }
+    var("CLICOLOR").is_some_and(|clicolor| clicolor == "0").then_some(false)
+}
+
+#[cfg(not(feature = "std"))]
+fn env_color_choice() -> Option<bool> {
+    None
+}
+
+#[cfg(feature = "std")]
+fn is_terminal(stream: Stream) -> bool {
+    use std::io::IsTerminal;
+    match stream {
+        Stream::Stdout => std::io::stdout().is_terminal(),
+        Stream::Stderr => std::io::stderr().is_terminal(),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn is_terminal(_stream: Stream) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::SimpleError;
+
+    #[test]
+    fn booleans_and_explicit_choices_ignore_the_environment() {
+        assert_eq!(ColorChoice::from(true), ColorChoice::Auto);
+        assert_eq!(ColorChoice::from(false), ColorChoice::Never);
+        assert!(ColorChoice::Always.should_colorize());
+        assert!(!ColorChoice::Never.should_colorize());
+        let error = SimpleError::new().error_detail("Unexpected \u{1b}[31m;\u{1b}[0m".to_string());
+        assert_eq!(error.as_display_struct(ColorChoice::Never), error.as_display_struct(false));
+        assert!(error.as_display_struct(ColorChoice::Always).as_display_string().contains("\u{1b}[31m"));
+    }
+
+    #[test]
+    fn displayed_errors_are_colored_as_for_stderr() {
+        let error = SimpleError::new().error_detail("Unexpected \u{1b}[31m;\u{1b}[0m".to_string());
+        assert_eq!(error.to_string(), error.as_display_struct(ColorChoice::Stderr).as_display_string());
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(feature = "std")]
+use core::cell::Cell;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static COLOR_OVERRIDE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Runs *scope* while forcing every error rendered on the current thread to be colored or not,
+/// regardless of the *colorize* value given to [crate::SimpleError::as_display_struct], this also
+/// affects errors displayed through [core::fmt::Display].
+///
+/// Unlike `colored::control::set_override`, this override only lives on the current thread and
+/// only during *scope*, so a thread rendering plain errors for a log file doesn't remove the colors
+/// of another thread rendering errors for the terminal. The previous override is restored once
+/// *scope* finishes, even if it panics.
+///
+/// Note that forcing colors only keeps the escape codes produced while rendering, whether `colored`
+/// itself emits them still depends on its own terminal detection.
+#[cfg(feature = "std")]
+pub fn with_color_override<Res, Scope: FnOnce() -> Res>(colorize: bool, scope: Scope) -> Res {
+    struct RestoreOnDrop(Option<bool>);
+    impl Drop for RestoreOnDrop {
+        fn drop(&mut self) {
+            COLOR_OVERRIDE.with(|color_override| color_override.set(self.0));
+        }
+    }
+    let _restore = RestoreOnDrop(COLOR_OVERRIDE.with(|color_override| color_override.replace(Some(colorize))));
+    scope()
+}
+
+/// Returns the override set through [with_color_override] for the current thread, if any.
+pub(crate) fn current_color_override() -> Option<bool> {
+    #[cfg(feature = "std")]
+        let color_override = COLOR_OVERRIDE.with(|color_override| color_override.get());
+    #[cfg(not(feature = "std"))]
+        let color_override = None;
+    color_override
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::SimpleError;
+
+    #[test]
+    fn overrides_decide_colors_only_during_their_scope() {
+        let error = SimpleError::new().error_detail("Unexpected \u{1b}[31m;\u{1b}[0m".to_string());
+        assert_eq!(with_color_override(false, || error.to_string()), "Error: Unexpected ;");
+        assert!(with_color_override(true, || error.as_display_struct(false).as_display_string()).contains("\u{1b}[31m"));
+        assert_eq!(with_color_override(true, || with_color_override(false, current_color_override)), Some(false));
+        assert_eq!(current_color_override(), None);
+    }
+}
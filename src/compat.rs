@@ -0,0 +1,57 @@
+use alloc::string::String;
+
+use crate::display_options::DisplayOptions;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// Aliases for [SimpleError] methods under the names downstream code commonly uses for them,
+/// kept as thin wrappers over the options-based methods and deprecated, so large codebases can
+/// import this trait, keep compiling and migrate call by call following the deprecation notes.
+pub trait SimpleErrorCompat<'input> {
+    /// Alias of [SimpleError::as_display_struct], named as [crate::SimpleErrorDetail::to_display_struct].
+    #[deprecated(note = "use SimpleError::as_display_struct instead")]
+    fn to_display_struct(&self, colorize: bool) -> SimpleErrorDisplayInfo;
+
+    /// Alias of [SimpleError::as_display_string_with_options] using the default options.
+    #[deprecated(note = "use SimpleError::as_display_string_with_options with DisplayOptions::default() instead")]
+    fn to_display_string(&self, colorize: bool) -> String;
+}
+
+impl<'input> SimpleErrorCompat<'input> for SimpleError<'input> {
+    fn to_display_struct(&self, colorize: bool) -> SimpleErrorDisplayInfo {
+        self.as_display_struct(colorize)
+    }
+
+    fn to_display_string(&self, colorize: bool) -> String {
+        self.as_display_string_with_options(colorize, &DisplayOptions::default())
+    }
+}
+
+/// Aliases for [SimpleErrorDisplayInfo] methods under the names downstream code commonly uses for
+/// them, kept as thin wrappers over the options-based methods and deprecated, see
+/// [SimpleErrorCompat].
+pub trait SimpleErrorDisplayInfoCompat {
+    /// Alias of [SimpleErrorDisplayInfo::as_display_string_with_options] using the default options.
+    #[deprecated(note = "use SimpleErrorDisplayInfo::as_display_string_with_options with DisplayOptions::default() instead")]
+    fn to_display_string(&self) -> String;
+}
+
+impl SimpleErrorDisplayInfoCompat for SimpleErrorDisplayInfo {
+    fn to_display_string(&self) -> String {
+        self.as_display_string_with_options(&DisplayOptions::default())
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_give_the_same_as_the_methods_they_wrap() {
+        let error = SimpleError::new();
+        assert_eq!(error.to_display_struct(false), error.as_display_struct(false));
+        assert_eq!(error.to_display_string(false), error.as_display_struct(false).as_display_string());
+        assert_eq!(error.as_display_struct(false).to_display_string(), "Error: Unexplained error");
+    }
+}
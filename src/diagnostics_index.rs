@@ -0,0 +1,114 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::report::Report;
+use crate::simple_error::SimpleError;
+use crate::span::Span;
+
+/// Index over the errors of a [Report] and all of their causes keyed by where they happen, allowing
+/// to look up which errors are under a line or a range of the input in logarithmic time, this is
+/// specially useful for editor integrations showing the errors under the cursor.
+///
+/// Errors are located by their [SimpleError::start_point_of_error] and
+/// [SimpleError::end_point_of_error], or when these aren't given, by the position of
+/// [SimpleError::at] inside the source given on [SimpleError::with_source], errors without a
+/// location aren't indexed.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsIndex<'report, 'input> {
+    errors: Vec<&'report SimpleError<'input>>,
+    by_line: BTreeMap<usize, Vec<usize>>,
+    by_span_start: BTreeMap<usize, Vec<(Span, usize)>>,
+    longest_span: usize,
+}
+
+impl<'report, 'input> DiagnosticsIndex<'report, 'input> {
+    /// Indexes every error of *report* along with all of their causes.
+    pub fn new(report: &'report Report<'input>) -> Self {
+        let mut res = Self { errors: Vec::new(), by_line: BTreeMap::new(), by_span_start: BTreeMap::new(), longest_span: 0 };
+        report.errors().iter().for_each(|error| res.index(error, None));
+        res
+    }
+
+    fn index(&mut self, error: &'report SimpleError<'input>, inherited_source: Option<&'input str>) {
+        let source = error.current_source().or(inherited_source);
+        let (span, start_point, end_point) = error.resolve_position(source);
+        if span.is_some() || start_point.is_some() {
+            let error_index = self.errors.len();
+            self.errors.push(error);
+            if let Some((start_line, _)) = start_point {
+                let end_line = end_point.map(|(end_line, _)| end_line).unwrap_or(start_line).max(start_line);
+                (start_line..=end_line).for_each(|line| self.by_line.entry(line).or_default().push(error_index));
+            }
+            if let Some(span) = span {
+                self.longest_span = self.longest_span.max(span.len());
+                self.by_span_start.entry(span.start).or_default().push((span, error_index));
+            }
+        }
+        error.current_causes().iter().for_each(|cause| self.index(cause, source));
+    }
+
+    /// Returns the errors covering the given line (Starting from 1), in the order they were found
+    /// on the report, where causes come right after the error they caused.
+    pub fn errors_at(&self, line: usize) -> Vec<&'report SimpleError<'input>> {
+        self.by_line.get(&line)
+            .map(|error_indexes| error_indexes.iter().map(|error_index| self.errors[*error_index]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the errors whose span overlaps the given range of bytes, in the order they were
+    /// found on the report, where causes come right after the error they caused.
+    ///
+    /// Empty ranges and spans are considered to cover the byte they start at, so a range like
+    /// `5..5` returns the errors under a cursor placed at byte 5.
+    pub fn errors_in_range<SpanLike: Into<Span>>(&self, range: SpanLike) -> Vec<&'report SimpleError<'input>> {
+        let range = range.into();
+        let range_end = range.end.max(range.start + 1);
+        let first_possible_start = range.start.saturating_sub(self.longest_span.max(1));
+        let mut error_indexes = self.by_span_start.range(first_possible_start..range_end)
+            .flat_map(|(_, spans)| spans)
+            .filter(|(span, _)| span.end.max(span.start + 1) > range.start)
+            .map(|(_, error_index)| *error_index)
+            .collect::<Vec<_>>();
+        error_indexes.sort_unstable();
+        error_indexes.into_iter().map(|error_index| self.errors[error_index]).collect()
+    }
+
+    /// Returns how many errors were indexed.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Tells if no error was indexed.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::*;
+
+    #[test]
+    fn errors_are_looked_up_by_line_and_range() {
+        let source = "aaaa\nbbbb\ncccc\ndddd";
+        let error = |explanation: &str| SimpleError::from(explanation.to_string());
+        let report = Report::new()
+            .with_error(error("Two lines").at(&source[5..14]).with_source(source))
+            .with_error(error("Fourth line").at_span(15..16).with_source(source).with_cause(error("Cause").at(&source[16..17])))
+            .with_error(error("First line").start_point_of_error(1, 1))
+            .with_error(error("Nowhere"));
+        let index = report.diagnostics_index();
+        let explanations = |errors: Vec<&SimpleError>| errors.iter()
+            .map(|error| error.as_display_struct(false).reason.clone().unwrap_or_default())
+            .collect::<Vec<String>>();
+        assert_eq!(index.len(), 4);
+        assert_eq!(explanations(index.errors_at(1)), ["First line"]);
+        assert_eq!(explanations(index.errors_at(3)), ["Two lines"]);
+        assert_eq!(explanations(index.errors_at(4)), ["Fourth line", "Cause"]);
+        assert_eq!(explanations(index.errors_in_range(16..16)), ["Cause"]);
+        assert_eq!(explanations(index.errors_in_range(6..16)), ["Two lines", "Fourth line"]);
+        assert!(index.errors_in_range(14..15).is_empty());
+    }
+}
@@ -0,0 +1,574 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::formatting::pluralize;
+use crate::severity::Severity;
+
+/// Customizes how errors are turned into strings through
+/// [crate::SimpleErrorDisplayInfo::as_display_string_with_options], letting you override the labels
+/// prefixing every line, the indentation of causes and how causes are separated, for example, to
+/// translate them or to match the style of your application.
+///
+/// Its default value gives the same format as [crate::SimpleErrorDisplayInfo::as_display_string].
+///
+/// Options are compared leaving out their [DisplayOptions::number_formatter], as functions can't be
+/// reliably compared.
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    /// Label for the line saying from which line and column the error happens, 'Position' by
+    /// default.
+    pub position_label: String,
+    /// Label for the line saying where the error happened, 'At' by default.
+    pub at_label: String,
+    /// Label for the lines of the source shown around the error, 'Context' by default.
+    pub context_label: String,
+    /// Label for the lines holding messages attached to related places, 'Label' by default.
+    pub label_label: String,
+    /// Label for the line holding the code of the error, 'Code' by default.
+    pub code_label: String,
+    /// Label for the line explaining the error, 'Error' by default.
+    pub error_label: String,
+    /// Label for the line explaining the error when it is a [crate::Severity::Warning], 'Warning'
+    /// by default.
+    pub warning_label: String,
+    /// Label for the line explaining the error when it is a [crate::Severity::Info], 'Info' by
+    /// default.
+    pub info_label: String,
+    /// Label for the line explaining the error when it is a [crate::Severity::Hint], 'Hint' by
+    /// default.
+    pub hint_label: String,
+    /// Label for the line saying how to solve the error, 'Solution' by default.
+    pub solution_label: String,
+    /// Label for the lines holding suggested replacements, 'Suggestion' by default.
+    pub suggestion_label: String,
+    /// Label for the lines holding suggested replacements that might not fix the error, like those
+    /// whose applicability is [crate::suggestion::Applicability::MaybeIncorrect], 'Possible fix' by
+    /// default.
+    pub possible_fix_label: String,
+    /// Label for the lines holding notes, 'Note' by default.
+    pub note_label: String,
+    /// Label for the lines holding helps, 'Help' by default.
+    pub help_label: String,
+    /// Label for the line holding the link to the documentation of the error, 'More info' by
+    /// default.
+    pub more_info_label: String,
+    /// Label for the lines holding the metadata of the error, 'Details' by default.
+    pub details_label: String,
+    /// Label for the lines holding the backtrace of the error, 'Backtrace' by default.
+    pub backtrace_label: String,
+    /// Label for the line counting the causes, 'Has' by default.
+    pub has_label: String,
+    /// Label shown before the only cause of an error, 'Cause' by default.
+    pub cause_label: String,
+    /// Label shown before the causes of an error, 'Causes' by default.
+    pub causes_label: String,
+    /// Explanation shown for errors without one, 'Unexplained error' by default.
+    pub unexplained_error: String,
+    /// Text introducing every cause when there are many of them, followed by its number, like in
+    /// '- Cause nº 1 -', 'Cause nº' by default.
+    pub cause_header: String,
+    /// Text introducing every error of a numbered [crate::Report], followed by its number and by the
+    /// total of errors, like in '- Error nº 1 of 3 -', 'Error nº' by default.
+    pub error_header: String,
+    /// Text introducing the total of errors after the number of an error of a numbered
+    /// [crate::Report], like in '- Error nº 1 of 3 -', 'of' by default.
+    pub error_total_label: String,
+    /// Gives the texts counting the causes of an error and numbering them after
+    /// [DisplayOptions::cause_header] and [DisplayOptions::error_header], following English rules
+    /// by default.
+    pub number_formatter: NumberFormatter,
+    /// Word joining the counts of explained and unexplained causes, like in '2 explained causes and
+    /// 1 unexplained cause', 'and' by default.
+    pub count_conjunction: String,
+    /// Text placed between causes when there are many of them, an empty line by default.
+    pub cause_separator: String,
+    /// Order in which causes are shown, the same order they were added in by default.
+    pub cause_ordering: CauseOrdering,
+    /// How many spaces causes get indented by, 2 by default.
+    pub indentation: usize,
+    /// How many levels of causes are shown, where causes deeper than this are replaced by
+    /// [DisplayOptions::omitted_causes_text], so long chains of wrapped errors remain readable, by
+    /// default every cause is shown.
+    pub max_depth: Option<usize>,
+    /// Text shown instead of the causes deeper than [DisplayOptions::max_depth], '… and deeper
+    /// causes omitted (use verbose mode)' by default.
+    pub omitted_causes_text: String,
+    /// How many causes are shown for every error, where the rest are replaced by a line counting
+    /// them after [DisplayOptions::more_causes_prefix], like '… and 137 more causes', while the
+    /// 'Has' line still counts all of them, by default every cause is shown.
+    pub max_causes_per_level: Option<usize>,
+    /// Text introducing the count of causes left out by [DisplayOptions::max_causes_per_level],
+    /// '… and' by default.
+    pub more_causes_prefix: String,
+    /// Width lines are soft-wrapped at, keeping wrapped lines aligned under their label, by default
+    /// lines aren't wrapped.
+    pub wrap_width: Option<usize>,
+    /// How tabs are shown on the 'At', 'Label' and 'Suggestion' lines, where they would otherwise
+    /// be aligned unpredictably by the terminal, by default they are kept as they are.
+    pub tab_rendering: TabRendering,
+    /// How many lines of the source are shown before and after the lines every error happens on,
+    /// like rustc does, as long as said lines were kept when turning the error into a
+    /// [crate::SimpleErrorDisplayInfo], see [crate::SimpleError::as_display_struct_with_context],
+    /// by default no line is shown.
+    pub context_lines: usize,
+    /// URL the 'Position' lines link to through OSC 8 hyperlinks, followed by `#L` and the line
+    /// the error happens on, like `file:///home/user/main.script` giving
+    /// `file:///home/user/main.script#L3`, by default positions aren't linked.
+    ///
+    /// Terminals not supporting hyperlinks might show their escape codes, see
+    /// [DisplayOptions::link_positions_to] for setting it only on terminals supporting them.
+    pub position_link: Option<String>,
+    /// Indicates whether the links to the documentation of errors are shown as OSC 8 hyperlinks,
+    /// false by default.
+    pub link_doc_urls: bool,
+    /// Indicates whether the backtraces recorded through [crate::SimpleError::capture_backtrace]
+    /// are shown, this is meant for verbose output, false by default.
+    pub show_backtraces: bool,
+    /// Indicates whether the metadata attached through [crate::SimpleError::with_metadata] is
+    /// shown on a 'Details' block, false by default.
+    pub show_metadata: bool,
+    /// Minimum severity causes must have to be shown, causes less serious than it are left out
+    /// along with their own causes, like hiding hints and notes unless `-W` is given, by default
+    /// every cause is shown, see [crate::SimpleError::filter_min_severity].
+    pub min_severity: Option<Severity>,
+    /// Indicates whether errors and causes with the same fingerprint are shown once, noting how
+    /// many times they occurred, see [crate::SimpleErrorDisplayInfo::dedup_causes], false by
+    /// default.
+    pub group_identical: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            position_label: "Position".to_string(),
+            at_label: "At".to_string(),
+            context_label: "Context".to_string(),
+            label_label: "Label".to_string(),
+            code_label: "Code".to_string(),
+            error_label: "Error".to_string(),
+            warning_label: "Warning".to_string(),
+            info_label: "Info".to_string(),
+            hint_label: "Hint".to_string(),
+            solution_label: "Solution".to_string(),
+            suggestion_label: "Suggestion".to_string(),
+            possible_fix_label: "Possible fix".to_string(),
+            note_label: "Note".to_string(),
+            help_label: "Help".to_string(),
+            more_info_label: "More info".to_string(),
+            details_label: "Details".to_string(),
+            backtrace_label: "Backtrace".to_string(),
+            has_label: "Has".to_string(),
+            cause_label: "Cause".to_string(),
+            causes_label: "Causes".to_string(),
+            unexplained_error: "Unexplained error".to_string(),
+            cause_header: "Cause nº".to_string(),
+            error_header: "Error nº".to_string(),
+            error_total_label: "of".to_string(),
+            number_formatter: NumberFormatter::default(),
+            count_conjunction: "and".to_string(),
+            cause_separator: "\n\n".to_string(),
+            cause_ordering: CauseOrdering::default(),
+            indentation: 2,
+            max_depth: None,
+            omitted_causes_text: "… and deeper causes omitted (use verbose mode)".to_string(),
+            max_causes_per_level: None,
+            more_causes_prefix: "… and".to_string(),
+            wrap_width: None,
+            tab_rendering: TabRendering::default(),
+            context_lines: 0,
+            position_link: None,
+            link_doc_urls: false,
+            show_backtraces: false,
+            show_metadata: false,
+            min_severity: None,
+            group_identical: false,
+        }
+    }
+}
+
+impl PartialEq for DisplayOptions {
+    fn eq(&self, other: &Self) -> bool {
+        // Destructured so new options can't be left out of the comparison by mistake.
+        let Self {
+            position_label, at_label, context_label, label_label, code_label, error_label, warning_label, info_label,
+            hint_label, solution_label, suggestion_label, possible_fix_label, note_label, help_label, more_info_label,
+            details_label, backtrace_label, has_label, cause_label, causes_label, unexplained_error, cause_header,
+            error_header, error_total_label, count_conjunction, cause_separator, cause_ordering, indentation, max_depth,
+            omitted_causes_text, max_causes_per_level, more_causes_prefix, wrap_width, tab_rendering, context_lines,
+            position_link, link_doc_urls, show_backtraces, show_metadata, min_severity, group_identical,
+            number_formatter: _
+        } = self;
+        *position_label == other.position_label && *at_label == other.at_label
+            && *context_label == other.context_label && *label_label == other.label_label
+            && *code_label == other.code_label && *error_label == other.error_label
+            && *warning_label == other.warning_label && *info_label == other.info_label
+            && *hint_label == other.hint_label && *solution_label == other.solution_label
+            && *suggestion_label == other.suggestion_label && *possible_fix_label == other.possible_fix_label
+            && *note_label == other.note_label && *help_label == other.help_label
+            && *more_info_label == other.more_info_label && *details_label == other.details_label
+            && *backtrace_label == other.backtrace_label && *has_label == other.has_label
+            && *cause_label == other.cause_label && *causes_label == other.causes_label
+            && *unexplained_error == other.unexplained_error && *cause_header == other.cause_header
+            && *error_header == other.error_header && *error_total_label == other.error_total_label
+            && *count_conjunction == other.count_conjunction && *cause_separator == other.cause_separator
+            && *cause_ordering == other.cause_ordering && *indentation == other.indentation
+            && *max_depth == other.max_depth && *omitted_causes_text == other.omitted_causes_text
+            && *max_causes_per_level == other.max_causes_per_level
+            && *more_causes_prefix == other.more_causes_prefix && *wrap_width == other.wrap_width
+            && *tab_rendering == other.tab_rendering && *context_lines == other.context_lines
+            && *position_link == other.position_link && *link_doc_urls == other.link_doc_urls
+            && *show_backtraces == other.show_backtraces && *show_metadata == other.show_metadata
+            && *min_severity == other.min_severity && *group_identical == other.group_identical
+    }
+}
+
+impl Eq for DisplayOptions {}
+
+impl DisplayOptions {
+    /// Creates the default options, giving the same format as
+    /// [crate::SimpleErrorDisplayInfo::as_display_string].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Sets [DisplayOptions::wrap_width] to the width of the terminal as given by the `COLUMNS`
+    /// environment variable, leaving it untouched if said variable isn't set.
+    #[cfg(feature = "std")]
+    pub fn wrap_to_terminal_width(mut self) -> Self {
+        if let Some(columns) = std::env::var("COLUMNS").ok().and_then(|columns| columns.trim().parse().ok()) {
+            self.wrap_width = Some(columns);
+        }
+        self
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Sets [DisplayOptions::position_link] to *url* when the terminal seems to support OSC 8
+    /// hyperlinks (See [terminal_supports_hyperlinks]), leaving positions as plain text otherwise.
+    #[cfg(feature = "std")]
+    pub fn link_positions_to<Url: Into<String>>(mut self, url: Url) -> Self {
+        if terminal_supports_hyperlinks() {
+            self.position_link = Some(url.into());
+        }
+        self
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Sets [DisplayOptions::link_doc_urls] when the terminal seems to support OSC 8 hyperlinks
+    /// (See [terminal_supports_hyperlinks]), leaving links as plain text otherwise.
+    #[cfg(feature = "std")]
+    pub fn link_doc_urls_if_supported(mut self) -> Self {
+        self.link_doc_urls = terminal_supports_hyperlinks();
+        self
+    }
+
+    /// Returns the label for the line explaining an error of the given severity.
+    pub(crate) fn severity_label(&self, severity: Severity) -> &String {
+        match severity {
+            Severity::Hint => &self.hint_label,
+            Severity::Info => &self.info_label,
+            Severity::Warning => &self.warning_label,
+            Severity::Error => &self.error_label,
+        }
+    }
+}
+
+/// How errors are rendered by [crate::SimpleErrorDisplayInfo::as_display_string_in_mode].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisplayMode {
+    /// Errors are rendered as in [crate::SimpleErrorDisplayInfo::as_display_string].
+    #[default]
+    Regular,
+    /// Errors are rendered giving the same bytes across runs and platforms, meant for snapshot and
+    /// golden-file tests, like those written with `insta`: colors and hyperlinks are removed,
+    /// causes are shown in the same order they were added in, the default English labels are
+    /// used, line endings become `\n` and trailing whitespace is removed from every line.
+    Snapshot,
+}
+
+/// Order in which the causes of an error are shown, see [DisplayOptions::cause_ordering] and
+/// [crate::SimpleErrorDisplayInfo::sort_causes].
+///
+/// Every ordering is stable, so causes considered equal keep the order they were added in.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CauseOrdering {
+    /// Causes are shown in the same order they were added in.
+    #[default]
+    Insertion,
+    /// Causes are shown by the line and column they happen at, where causes without a position are
+    /// shown last.
+    SourcePosition,
+    /// Causes with fewer causes of their own are shown first, so short explanations aren't buried
+    /// under deep error trees.
+    Complexity,
+    /// The most serious causes are shown first, see [Severity].
+    Severity,
+}
+
+/// How tabs found on the input errors happen at are shown, see [DisplayOptions::tab_rendering].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TabRendering {
+    /// Tabs are kept as they are, leaving their width up to the terminal.
+    #[default]
+    Keep,
+    /// Every tab is replaced by the given number of spaces.
+    Spaces(usize),
+    /// Every tab is replaced by a visible '→'.
+    Arrow,
+}
+
+impl TabRendering {
+    /// Returns *text* with its tabs shown as this rendering says.
+    pub fn render(&self, text: &str) -> String {
+        match self {
+            TabRendering::Keep => text.to_string(),
+            TabRendering::Spaces(width) => text.replace('\t', &" ".repeat(*width)),
+            TabRendering::Arrow => text.replace('\t', "→"),
+        }
+    }
+}
+
+/// Gives the texts counting and numbering the causes of an error, so languages with different
+/// plural rules, like Polish or Arabic, get grammatically correct counts instead of English ones.
+#[derive(Debug, Clone, Copy)]
+pub struct NumberFormatter {
+    /// Gives the text counting a noun, like '2 explained causes', [english_count] by default.
+    pub count: fn(usize, CountedNoun) -> String,
+    /// Gives the number following [DisplayOptions::cause_header] and [DisplayOptions::error_header],
+    /// where causes and errors are numbered from 1, [english_ordinal] by default.
+    pub ordinal: fn(usize) -> String,
+    /// Gives the total of errors following [DisplayOptions::error_total_label], [english_number] by
+    /// default.
+    pub number: fn(usize) -> String,
+}
+
+impl Default for NumberFormatter {
+    fn default() -> Self {
+        Self { count: english_count, ordinal: english_ordinal, number: english_number }
+    }
+}
+
+/// What is being counted by [NumberFormatter::count].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CountedNoun {
+    /// Causes of an error that were explained, see [crate::SimpleErrorDisplayInfo::is_explained].
+    ExplainedCause,
+    /// Causes of an error that weren't explained.
+    UnexplainedCause,
+    /// Causes of an error left out of the display, see [DisplayOptions::max_causes_per_level].
+    MoreCause,
+}
+
+/// Default [NumberFormatter::count], counting *count* nouns following English plural
+/// rules, like '1 explained cause', '2 unexplained causes' or '137 more causes'.
+pub fn english_count(count: usize, noun: CountedNoun) -> String {
+    let noun = match noun {
+        CountedNoun::ExplainedCause => "explained cause",
+        CountedNoun::UnexplainedCause => "unexplained cause",
+        CountedNoun::MoreCause => "more cause",
+    };
+    pluralize(count, noun, "")
+}
+
+/// Default [NumberFormatter::ordinal], giving *number* as it is, like '1' for the first
+/// cause.
+pub fn english_ordinal(number: usize) -> String {
+    format!("{number}")
+}
+
+/// Default [NumberFormatter::number], giving *number* as it is, like '3' for a total of three
+/// errors.
+pub fn english_number(number: usize) -> String {
+    format!("{number}")
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Tells whether the terminal seems to support OSC 8 hyperlinks by looking at the environment
+/// variables set by terminals known to support them, the `FORCE_HYPERLINK` variable overrides the
+/// detection when set to `1` or `0`.
+#[cfg(feature = "std")]
+pub fn terminal_supports_hyperlinks() -> bool {
+    supports_hyperlinks_with(|name| std::env::var(name).ok())
+}
+
+/// Tells whether a terminal supports OSC 8 hyperlinks as [terminal_supports_hyperlinks] does,
+/// reading environment variables through *var*.
+#[cfg(feature = "std")]
+fn supports_hyperlinks_with(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force_hyperlink) = var("FORCE_HYPERLINK") {
+        return force_hyperlink.trim() != "0";
+    }
+    let is_supporting_program = var("TERM_PROGRAM")
+        .is_some_and(|program| matches!(program.as_str(), "iTerm.app" | "WezTerm" | "vscode" | "ghostty"));
+    let is_supporting_vte = var("VTE_VERSION")
+        .and_then(|version| version.parse::<u32>().ok())
+        .is_some_and(|version| version >= 5000);
+    is_supporting_program || is_supporting_vte || ["WT_SESSION", "KONSOLE_VERSION", "DOMTERM"].iter().any(|name| var(name).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleError;
+    use crate::SimpleErrorExplanation;
+
+    #[test]
+    fn labels_indentation_and_separators_can_be_customized() {
+        let error = SimpleError::from(String::from("Couldn't compile")).with_cause(String::from("Missing ;"))
+            .with_cause(SimpleError::from(String::from("Invalid type")).with_cause(String::from("Unknown type Strng")));
+        assert_eq!(error.as_display_string_with_options(false, &DisplayOptions::default()), error.as_display_struct(false).as_display_string());
+        let options = DisplayOptions {
+            error_label: "Fehler".to_string(),
+            causes_label: "Ursachen".to_string(),
+            cause_label: "Ursache".to_string(),
+            has_label: "Hat".to_string(),
+            cause_header: "Ursache Nr.".to_string(),
+            cause_separator: "\n---\n".to_string(),
+            indentation: 4,
+            ..DisplayOptions::new()
+        };
+        assert_eq!(error.as_display_string_with_options(false, &options),
+                   concat!("Fehler: Couldn't compile\nHat: 2 explained causes.\nUrsachen: \n    - Ursache Nr. 1 -\n    - Fehler: Missing ;\n    ---\n",
+                           "    - Ursache Nr. 2 -\n    - Fehler: Invalid type\n    - Ursache: \n          - Fehler: Unknown type Strng"));
+    }
+
+    #[test]
+    fn wrapped_lines_keep_their_label_indentation() {
+        let error = SimpleError::from(SimpleErrorExplanation::new().explanation("one two three four five six seven").solution("alpha beta gamma delta"))
+            .with_cause(SimpleError::from(SimpleErrorExplanation::new().explanation("aaa bbb ccc ddd eee fff").solution("x")));
+        let options = DisplayOptions { wrap_width: Some(20), ..DisplayOptions::default() };
+        let wrapped = error.as_display_struct(false).as_display_string_with_options(&options);
+        assert!(wrapped.lines().all(|line| line.chars().count() <= 20), "{wrapped}");
+        assert!(wrapped.starts_with("Error: one two three\n       four five six\n       seven\nSolution: alpha beta\n          gamma\n          delta\n"), "{wrapped}");
+    }
+
+    #[test]
+    fn non_ascii_labels_indent_by_their_width_in_columns() {
+        let error = SimpleError::from(SimpleErrorExplanation::new().explanation("Missing value").solution("Add a value\nafter the ="));
+        let options = DisplayOptions { solution_label: "Solución".to_string(), ..DisplayOptions::default() };
+        assert_eq!(error.as_display_string_with_options(false, &options), "Error: Missing value\nSolución: Add a value\n          after the =");
+        let options = DisplayOptions { wrap_width: Some(20), ..options };
+        assert_eq!(error.as_display_string_with_options(false, &options), "Error: Missing value\nSolución: Add a\n          value\n          after the\n          =");
+    }
+
+    #[test]
+    fn positions_become_hyperlinks_to_their_line() {
+        let error = SimpleError::new().with_source("let a\nlet b").at_span(6..9);
+        let options = DisplayOptions { position_link: Some("file:///main.script".to_string()), ..DisplayOptions::default() };
+        let linked = error.as_display_struct(false).as_display_string_with_options(&options);
+        assert!(linked.starts_with("Position: \u{1b}]8;;file:///main.script#L2\u{1b}\\On line 2 and column 1"), "{linked:?}");
+        assert!(linked.contains("\u{1b}]8;;\u{1b}\\\n"), "{linked:?}");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hyperlinks_are_only_used_when_supported() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+        };
+        assert!(!supports_hyperlinks_with(env(&[])));
+        assert!(!supports_hyperlinks_with(env(&[("TERM_PROGRAM", "Apple_Terminal"), ("VTE_VERSION", "4800")])));
+        assert!(supports_hyperlinks_with(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supports_hyperlinks_with(env(&[("VTE_VERSION", "6003")])));
+        assert!(supports_hyperlinks_with(env(&[("WT_SESSION", "")])));
+        assert!(!supports_hyperlinks_with(env(&[("FORCE_HYPERLINK", "0"), ("WT_SESSION", "")])));
+        assert!(supports_hyperlinks_with(env(&[("FORCE_HYPERLINK", "1")])));
+    }
+
+    #[test]
+    fn counts_and_ordinals_go_through_the_number_formatter() {
+        fn polish_count(count: usize, noun: CountedNoun) -> String {
+            let word = match noun {
+                CountedNoun::ExplainedCause => "przyczyn",
+                CountedNoun::UnexplainedCause => "niewyjaśnion",
+                CountedNoun::MoreCause => "więcej",
+            };
+            let suffix = if count == 1 { "a" } else if (2..=4).contains(&(count % 10)) && !(12..=14).contains(&(count % 100)) { "y" } else { "" };
+            format!("{count} {word}{suffix}")
+        }
+
+        let error = SimpleError::from(String::from("Couldn't compile")).with_cause(String::from("Missing ;"))
+            .with_cause(String::from("Missing type")).with_cause(SimpleError::new());
+        let options = DisplayOptions {
+            number_formatter: NumberFormatter { count: polish_count, ordinal: |number| format!("{number}."), ..NumberFormatter::default() },
+            count_conjunction: "i".to_string(),
+            ..DisplayOptions::default()
+        };
+        let rendered = error.as_display_string_with_options(false, &options);
+        assert!(rendered.contains("2 przyczyny i 1 niewyjaśniona") && rendered.contains("- Cause nº 2. -"), "{rendered}");
+        let rendered = error.as_display_string_with_options(false, &DisplayOptions::default());
+        assert!(rendered.contains("2 explained causes and 1 unexplained cause.") && rendered.contains("- Cause nº 2 -"), "{rendered}");
+        assert_eq!(options, options.clone());
+        assert_ne!(options, DisplayOptions::default());
+        assert_eq!(DisplayOptions { count_conjunction: "and".to_string(), ..options }, DisplayOptions::default());
+    }
+
+    #[test]
+    fn causes_are_ordered_by_the_cause_ordering_policy() {
+        let tree = SimpleError::from(String::from("Couldn't compile"))
+            .with_cause(SimpleError::from(String::from("Invalid function")).with_cause(String::from("Missing name")))
+            .with_cause(SimpleErrorExplanation::new().explanation("Unused variable").severity(Severity::Warning))
+            .with_cause(SimpleError::from(String::from("Missing ;")).start_point_of_error(1, 1));
+        let ordered_causes = |cause_ordering| {
+            let mut display = tree.as_display_struct(false);
+            display.sort_causes(cause_ordering);
+            display.summary()
+        };
+        assert_eq!(ordered_causes(CauseOrdering::Insertion), "Couldn't compile (3 causes: Invalid function (1 cause: Missing name), Unused variable, Missing ;)");
+        assert_eq!(ordered_causes(CauseOrdering::Complexity), "Couldn't compile (3 causes: Unused variable, Missing ;, Invalid function (1 cause: Missing name))");
+        assert_eq!(ordered_causes(CauseOrdering::SourcePosition), "Couldn't compile (3 causes: Missing ;, Invalid function (1 cause: Missing name), Unused variable)");
+        assert_eq!(ordered_causes(CauseOrdering::Severity), "Couldn't compile (3 causes: Invalid function (1 cause: Missing name), Missing ;, Unused variable)");
+        let options = DisplayOptions { cause_ordering: CauseOrdering::Complexity, ..DisplayOptions::default() };
+        let rendered = tree.as_display_string_with_options(false, &options);
+        assert!(rendered.find("Warning: Unused variable") < rendered.find("Error: Invalid function"), "{rendered}");
+    }
+
+    #[test]
+    fn causes_deeper_than_max_depth_are_omitted() {
+        let tree = SimpleError::from(String::from("Couldn't compile"))
+            .with_cause(SimpleError::from(String::from("Invalid function")).with_cause(String::from("Missing name")));
+        let options = DisplayOptions { max_depth: Some(1), ..DisplayOptions::default() };
+        assert_eq!(tree.as_display_string_with_options(false, &options),
+                   "Error: Couldn't compile\nCause: \n  - Error: Invalid function\n  - Cause: … and deeper causes omitted (use verbose mode)");
+        let options = DisplayOptions { max_depth: Some(0), ..DisplayOptions::default() };
+        assert_eq!(tree.as_display_string_with_options(false, &options), "Error: Couldn't compile\nCause: … and deeper causes omitted (use verbose mode)");
+    }
+
+    #[test]
+    fn causes_past_max_causes_per_level_are_counted() {
+        let tree = ["Missing name", "Missing age", "Missing email", "Missing phone"].into_iter()
+            .map(|explanation| SimpleError::from(explanation.to_string()))
+            .collect::<SimpleError>()
+            .error_detail(String::from("Invalid user"));
+        let options = DisplayOptions { max_causes_per_level: Some(2), ..DisplayOptions::default() };
+        let rendered = tree.as_display_string_with_options(false, &options);
+        assert!(rendered.starts_with("Error: Invalid user\nHas: 4 explained causes."), "{rendered}");
+        assert!(rendered.ends_with("  - Error: Missing age\n  \n  … and 2 more causes"), "{rendered}");
+        assert!(!rendered.contains("Missing email"), "{rendered}");
+    }
+
+    #[test]
+    fn snapshots_trim_trailing_spaces_and_normalize_line_endings() {
+        let error = SimpleError::from(SimpleErrorExplanation::new().explanation("Unexpected ;  \r\nat the end ").solution("Remove it"))
+            .with_cause(String::from("Missing value"));
+        assert_eq!(error.as_display_string_in_mode(DisplayMode::Snapshot),
+                   "Error: Unexpected ;\n       at the end\nSolution: Remove it\nCause:\n  - Error: Missing value");
+    }
+
+    #[test]
+    fn tabs_of_the_input_are_rendered_as_asked() {
+        assert_eq!(TabRendering::Keep.render("\tx"), "\tx");
+        let display = SimpleError::new().error_detail(String::from("Missing value")).at("\tlet x\t= ;").as_display_struct(false);
+        let with_spaces = DisplayOptions { tab_rendering: TabRendering::Spaces(4), ..DisplayOptions::default() };
+        assert!(display.as_display_string_with_options(&with_spaces).contains("At: let x    = ;"));
+        let with_arrows = DisplayOptions { tab_rendering: TabRendering::Arrow, ..DisplayOptions::default() };
+        assert!(display.as_display_string_with_options(&with_arrows).contains("At: let x→= ;"));
+    }
+}
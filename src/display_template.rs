@@ -0,0 +1,266 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::formatting::{indent_lines_except_first, join_strings};
+use crate::simple_error_detail::SimpleErrorDetail;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+
+/// Declarative layout for displaying errors through
+/// [SimpleErrorDisplayInfo::as_display_string_with_template], where placeholders between braces
+/// get replaced by the fields of the error, like in `{at}\n{reason}\n→ {solution}`.
+///
+/// The available placeholders are:
+/// * `{at}`, `{reason}`, `{solution}`, `{code}` and `{severity}`.
+/// * `{doc_url}`, giving the link to the documentation of the error.
+/// * `{solution_steps}`, giving the steps to solve the error as a numbered list, one per line.
+/// * `{line}` and `{column}` where the error starts, and `{end_line}` and `{end_column}` where it
+///   ends.
+/// * `{labels}`, `{suggestions}`, `{notes}` and `{helps}`, giving one per line.
+/// * `{causes}`, giving every explained cause displayed through the cause template, which by
+///   default is this same template, and `{unexplained_causes}`, giving how many causes weren't
+///   explained.
+///
+/// Braces are written by doubling them, like `{{` and `}}`, and lines whose placeholders are all
+/// empty are left out, so a line like `→ {solution}` is only shown when there is a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayTemplate {
+    segments: Vec<Segment>,
+    cause_template: Option<Box<DisplayTemplate>>,
+    cause_indentation: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    At,
+    Reason,
+    Solution,
+    SolutionSteps,
+    Code,
+    Severity,
+    Line,
+    Column,
+    EndLine,
+    EndColumn,
+    Labels,
+    Suggestions,
+    Notes,
+    Helps,
+    DocUrl,
+    Causes,
+    UnexplainedCauses,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Self> {
+        let placeholder = match name {
+            "at" => Placeholder::At,
+            "reason" => Placeholder::Reason,
+            "solution" => Placeholder::Solution,
+            "solution_steps" => Placeholder::SolutionSteps,
+            "code" => Placeholder::Code,
+            "severity" => Placeholder::Severity,
+            "line" => Placeholder::Line,
+            "column" => Placeholder::Column,
+            "end_line" => Placeholder::EndLine,
+            "end_column" => Placeholder::EndColumn,
+            "labels" => Placeholder::Labels,
+            "suggestions" => Placeholder::Suggestions,
+            "notes" => Placeholder::Notes,
+            "helps" => Placeholder::Helps,
+            "doc_url" => Placeholder::DocUrl,
+            "causes" => Placeholder::Causes,
+            "unexplained_causes" => Placeholder::UnexplainedCauses,
+            _ => return None,
+        };
+        Some(placeholder)
+    }
+}
+
+impl DisplayTemplate {
+    /// Reads a template, see [DisplayTemplate] for the available placeholders.
+    pub fn parse(template: &str) -> Result<Self, TemplateParseError> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.char_indices().peekable();
+        while let Some((offset, char)) = chars.next() {
+            match char {
+                '{' if chars.next_if(|(_, next)| *next == '{').is_some() => text.push('{'),
+                '}' if chars.next_if(|(_, next)| *next == '}').is_some() => text.push('}'),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, char)) => name.push(char),
+                            None => return Err(TemplateParseError { offset, reason: TemplateParseErrorReason::UnclosedPlaceholder }),
+                        }
+                    }
+                    let placeholder = Placeholder::from_name(name.trim())
+                        .ok_or(TemplateParseError { offset, reason: TemplateParseErrorReason::UnknownPlaceholder(name) })?;
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(core::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                }
+                '}' => return Err(TemplateParseError { offset, reason: TemplateParseErrorReason::UnmatchedClosingBrace }),
+                char => text.push(char),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self { segments, cause_template: None, cause_indentation: 2 })
+    }
+
+    /// Sets the template used to display the causes given by the `{causes}` placeholder, by
+    /// default, causes are displayed with this same template.
+    pub fn cause_template(mut self, cause_template: DisplayTemplate) -> Self {
+        self.cause_template = Some(Box::new(cause_template));
+        self
+    }
+
+    /// Sets how many spaces every line of the causes given by the `{causes}` placeholder is
+    /// indented by, except for the first one, 2 by default.
+    pub fn cause_indentation(mut self, cause_indentation: usize) -> Self {
+        self.cause_indentation = cause_indentation;
+        self
+    }
+
+    /// Displays *display* following this template.
+    pub(crate) fn render(&self, display: &SimpleErrorDisplayInfo) -> String {
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        let mut line_has_placeholders = false;
+        let mut line_has_values = false;
+        let mut finish_line = |line: &mut String, line_has_placeholders: &mut bool, line_has_values: &mut bool| {
+            if !*line_has_placeholders || *line_has_values {
+                lines.push(core::mem::take(line));
+            }
+            line.clear();
+            *line_has_placeholders = false;
+            *line_has_values = false;
+        };
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => {
+                    let mut text_lines = text.split('\n');
+                    line.push_str(text_lines.next().unwrap_or_default());
+                    for text_line in text_lines {
+                        finish_line(&mut line, &mut line_has_placeholders, &mut line_has_values);
+                        line.push_str(text_line);
+                    }
+                }
+                Segment::Placeholder(placeholder) => {
+                    let value = self.placeholder_value(*placeholder, display);
+                    line_has_placeholders = true;
+                    line_has_values |= !value.is_empty();
+                    line.push_str(&value);
+                }
+            }
+        }
+        finish_line(&mut line, &mut line_has_placeholders, &mut line_has_values);
+        join_strings("\n", lines.iter())
+    }
+
+    fn placeholder_value(&self, placeholder: Placeholder, display: &SimpleErrorDisplayInfo) -> String {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let number = |value: Option<usize>| value.map(|value| value.to_string()).unwrap_or_default();
+        match placeholder {
+            Placeholder::At => optional(&display.at),
+            Placeholder::Reason => optional(&display.reason),
+            Placeholder::Solution => optional(&display.solution),
+            Placeholder::SolutionSteps => join_strings("\n", display.solution_steps.iter().enumerate()
+                .map(|(step_no, step)| format!("{}. {step}", step_no + 1))),
+            Placeholder::Code => optional(&display.code),
+            Placeholder::Severity => display.severity.name().to_string(),
+            Placeholder::Line => number(display.on_line_and_column.map(|(line, _)| line)),
+            Placeholder::Column => number(display.on_line_and_column.map(|(_, column)| column)),
+            Placeholder::EndLine => number(display.up_to_line_an_column.map(|(line, _)| line)),
+            Placeholder::EndColumn => number(display.up_to_line_an_column.map(|(_, column)| column)),
+            Placeholder::Labels => join_strings("\n", display.labels.iter().map(|label| label.as_display_string())),
+            Placeholder::Suggestions => join_strings("\n", display.suggestions.iter().map(|suggestion| suggestion.as_display_string())),
+            Placeholder::Notes => join_strings("\n", display.notes.iter()),
+            Placeholder::Helps => join_strings("\n", display.helps.iter()),
+            Placeholder::DocUrl => optional(&display.doc_url),
+            Placeholder::Causes => {
+                let cause_template = self.cause_template.as_deref().unwrap_or(self);
+                let causes = join_strings("\n", display.explained_causes.iter().map(|cause| cause_template.render(cause)));
+                indent_lines_except_first(causes, self.cause_indentation)
+            }
+            Placeholder::UnexplainedCauses => {
+                if display.unexplained_causes == 0 { String::new() } else { display.unexplained_causes.to_string() }
+            }
+        }
+    }
+}
+
+/// Error given by [DisplayTemplate::parse] when the template can't be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateParseError {
+    /// Byte offset of the template where the error was found.
+    pub offset: usize,
+    /// What was wrong on said offset.
+    pub reason: TemplateParseErrorReason,
+}
+
+/// What was wrong on a template read through [DisplayTemplate::parse].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateParseErrorReason {
+    /// A placeholder isn't any of the available ones.
+    UnknownPlaceholder(String),
+    /// A placeholder was opened with `{` but never closed with `}`.
+    UnclosedPlaceholder,
+    /// A `}` was found without being part of a placeholder nor being doubled.
+    UnmatchedClosingBrace,
+}
+
+impl SimpleErrorDetail for TemplateParseError {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        let offset = self.offset;
+        match &self.reason {
+            TemplateParseErrorReason::UnknownPlaceholder(name) => SimpleErrorExplanation::new()
+                .explanation(format!("Placeholder {{{name}}} at byte {offset} doesn't exist."))
+                .solution("Use one of the placeholders listed on DisplayTemplate, or write {{ to show a brace."),
+            TemplateParseErrorReason::UnclosedPlaceholder => SimpleErrorExplanation::new()
+                .explanation(format!("Placeholder at byte {offset} is never closed."))
+                .solution("Close the placeholder with }, or write {{ to show a brace."),
+            TemplateParseErrorReason::UnmatchedClosingBrace => SimpleErrorExplanation::new()
+                .explanation(format!("Brace at byte {offset} doesn't close any placeholder."))
+                .solution("Write }} to show a brace."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleError;
+
+    #[test]
+    fn templates_render_placeholders_and_skip_empty_lines() {
+        let error = SimpleError::from(SimpleErrorExplanation::new().explanation("Couldn't compile").solution("Fix the causes").code("E1"))
+            .with_cause(SimpleError::from(String::from("Missing ;")).start_point_of_error(3, 4))
+            .with_cause(SimpleError::new());
+        let template = DisplayTemplate::parse("[{code}] {reason}\n→ {solution}\n{{{line}:{column}}}\n{causes}").unwrap()
+            .cause_template(DisplayTemplate::parse("* {reason} at {line}:{column}").unwrap());
+        assert_eq!(error.as_display_struct(false).as_display_string_with_template(&template), "[E1] Couldn't compile\n→ Fix the causes\n* Missing ; at 3:4");
+        let unsolved = SimpleError::from(String::from("Couldn't compile")).with_cause(String::from("Missing ;")).as_display_struct(false);
+        assert_eq!(unsolved.as_display_string_with_template(&DisplayTemplate::parse("{reason}\n→ {solution}\n{causes}").unwrap()), "Couldn't compile\nMissing ;");
+    }
+
+    #[test]
+    fn invalid_templates_tell_where_they_fail() {
+        assert_eq!(DisplayTemplate::parse("ok {nope}"), Err(TemplateParseError { offset: 3, reason: TemplateParseErrorReason::UnknownPlaceholder("nope".to_string()) }));
+        assert_eq!(DisplayTemplate::parse("ab}").unwrap_err().reason, TemplateParseErrorReason::UnmatchedClosingBrace);
+        assert_eq!(DisplayTemplate::parse("a{at").unwrap_err().reason, TemplateParseErrorReason::UnclosedPlaceholder);
+    }
+}
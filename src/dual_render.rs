@@ -0,0 +1,162 @@
+use core::fmt::Write;
+
+use crate::color_choice::ColorChoice;
+use crate::color_override::current_color_override;
+use crate::display_options::DisplayOptions;
+use crate::formatting::join_strings;
+use crate::report::Report;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// Options for [SimpleError::render_dual] and [Report::render_dual], saying how errors are written
+/// on the terminal and on the file report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DualRenderOptions {
+    /// Whether the terminal output is colored, [ColorChoice::Auto] by default.
+    pub color: ColorChoice,
+    /// Options used for the terminal output, see [DisplayOptions].
+    pub terminal_options: DisplayOptions,
+    /// Indicates whether the terminal only shows a one-line summary of every error (See
+    /// [SimpleErrorDisplayInfo::summary]) instead of the whole error, true by default.
+    pub terse_terminal: bool,
+    /// Format of the file report, [FileReportFormat::Plain] by default.
+    pub file_format: FileReportFormat,
+}
+
+impl Default for DualRenderOptions {
+    fn default() -> Self {
+        Self { color: ColorChoice::Auto, terminal_options: DisplayOptions::default(), terse_terminal: true, file_format: FileReportFormat::Plain }
+    }
+}
+
+impl DualRenderOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Format of the file report written by [SimpleError::render_dual] and [Report::render_dual].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FileReportFormat {
+    /// The whole error displayed without colors, as in [SimpleErrorDisplayInfo::as_display_string].
+    #[default]
+    Plain,
+    /// This is only available when using the serde feature.
+    ///
+    /// The whole error without colors serialized as JSON.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+impl<'input> SimpleError<'input> {
+    /// Writes this error on two sinks at once, a terse and possibly colored rendering on
+    /// *term_sink*, meant for the terminal, and a complete rendering without colors on
+    /// *file_sink*, meant for a file report like a CI artifact, see [DualRenderOptions].
+    ///
+    /// The error tree is explained and traversed once, giving both outputs at the same time as
+    /// [SimpleError::as_display_structs_both] does, so the file report is the same as displaying
+    /// the error without colors, including the `>>>` and `<<<` delimiting spans marked through
+    /// [crate::SimpleErrorExplanation::mark_span].
+    pub fn render_dual<TermSink: Write, FileSink: Write>(&self, term_sink: &mut TermSink, file_sink: &mut FileSink, options: &DualRenderOptions) -> core::fmt::Result {
+        let (display, plain_display) = terminal_and_file_displays(self, options.color);
+        if options.terse_terminal {
+            term_sink.write_str(&display.summary())?;
+        } else {
+            term_sink.write_str(&display.as_display_string_with_options(&options.terminal_options))?;
+        }
+        match options.file_format {
+            FileReportFormat::Plain => file_sink.write_str(&plain_display.as_display_string()),
+            #[cfg(feature = "serde")]
+            FileReportFormat::Json => write_json(file_sink, &plain_display),
+        }
+    }
+}
+
+impl<'input> Report<'input> {
+    /// Writes this report on two sinks at once, as [SimpleError::render_dual] does, where the
+    /// terminal gets every error separated by an empty line, or a line each when it is terse.
+    pub fn render_dual<TermSink: Write, FileSink: Write>(&self, term_sink: &mut TermSink, file_sink: &mut FileSink, options: &DualRenderOptions) -> core::fmt::Result {
+        let (errors, plain_errors) = self.errors().iter().map(|error| terminal_and_file_displays(error, options.color)).unzip();
+        let (display, plain_display) = (self.as_display_struct_of(errors), self.as_display_struct_of(plain_errors));
+        if options.terse_terminal {
+            let summaries = display.errors.iter().map(|error| error.summary());
+            term_sink.write_str(&join_strings("\n", summaries))?;
+        } else {
+            term_sink.write_str(&display.as_display_string_with_options(&options.terminal_options))?;
+        }
+        match options.file_format {
+            FileReportFormat::Plain => file_sink.write_str(&plain_display.as_display_string()),
+            #[cfg(feature = "serde")]
+            FileReportFormat::Json => write_json(file_sink, &plain_display),
+        }
+    }
+}
+
+/// Explains *error* once, giving its display for the terminal, colored as *color* says, along with
+/// its display without colors for the file report.
+fn terminal_and_file_displays(error: &SimpleError, color: ColorChoice) -> (SimpleErrorDisplayInfo, SimpleErrorDisplayInfo) {
+    if current_color_override().unwrap_or_else(|| color.should_colorize()) {
+        error.as_display_structs_both()
+    } else {
+        let plain_display = error.as_display_struct(ColorChoice::Never);
+        (plain_display.clone(), plain_display)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn write_json<Sink: Write, Value: serde::Serialize>(sink: &mut Sink, value: &Value) -> core::fmt::Result {
+    let json = serde_json::to_string(value).map_err(|_| core::fmt::Error)?;
+    sink.write_str(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::*;
+    use crate::SimpleErrorExplanation;
+
+    #[test]
+    fn terminals_get_summaries_while_files_get_full_reports() {
+        let error = SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Couldn't compile").solution("Fix the causes"))
+            .with_cause("Missing ;".to_string());
+        let options = DualRenderOptions { color: ColorChoice::Never, ..DualRenderOptions::new() };
+        let (mut terminal, mut file) = (String::new(), String::new());
+        error.render_dual(&mut terminal, &mut file, &options).unwrap();
+        assert_eq!(terminal, "Couldn't compile (1 cause: Missing ;)");
+        assert_eq!(file, error.as_display_struct(false).as_display_string());
+
+        let report = Report::new().with_error(error).with_error(SimpleError::new());
+        let (mut terminal, mut file) = (String::new(), String::new());
+        report.render_dual(&mut terminal, &mut file, &DualRenderOptions { terse_terminal: false, ..options.clone() }).unwrap();
+        assert_eq!(terminal, report.as_display_struct(false).as_display_string());
+        assert_eq!(file, terminal);
+        #[cfg(feature = "serde")]
+        {
+            let (mut terminal, mut file) = (String::new(), String::new());
+            report.render_dual(&mut terminal, &mut file, &DualRenderOptions { file_format: FileReportFormat::Json, ..options }).unwrap();
+            assert_eq!(terminal, "Couldn't compile (1 cause: Missing ;)\nUnexplained error");
+            assert!(file.starts_with("{\"errors\":["), "{file}");
+        }
+    }
+
+    #[test]
+    fn colored_terminals_and_plain_files_come_from_one_explanation() {
+        let input = "let x = missing;";
+        let explanation = SimpleErrorExplanation::new().explanation("Unknown \u{1b}[31mvariable\u{1b}[0m").mark_span(&input[8..15]);
+        let error = SimpleError::new().at(input).error_detail(explanation);
+        let options = DualRenderOptions { color: ColorChoice::Always, terse_terminal: false, ..DualRenderOptions::new() };
+        let (mut terminal, mut file) = (String::new(), String::new());
+        error.render_dual(&mut terminal, &mut file, &options).unwrap();
+        assert_eq!(terminal, error.as_display_struct(ColorChoice::Always).as_display_string());
+        assert!(terminal.contains("\u{1b}[31m"), "{terminal}");
+        assert_eq!(file, "At: let x = >>>missing<<<;\nError: Unknown variable");
+
+        let report = Report::new().with_error(error);
+        let (mut terminal, mut file) = (String::new(), String::new());
+        report.render_dual(&mut terminal, &mut file, &options).unwrap();
+        assert!(terminal.contains("\u{1b}[31m"), "{terminal}");
+        assert_eq!(file, "At: let x = >>>missing<<<;\nError: Unknown variable");
+    }
+}
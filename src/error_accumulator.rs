@@ -0,0 +1,264 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::formatting::{join_strings, pluralize};
+use crate::severity::Severity;
+use crate::simple_error::SimpleError;
+use crate::simple_error_detail::SimpleErrorDetail;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+
+/// Collects errors so parsers can keep going after finding the first one, once finished,
+/// [ErrorAccumulator::into_result] gives either the parsed value or a root [SimpleError] holding
+/// every accumulated error as its causes, in the same order they were pushed.
+///
+/// Errors less serious than [Severity::Error], like warnings, don't make the accumulator fail, they
+/// are given through [ErrorAccumulator::warnings] so they can be shown after a successful run.
+///
+/// Accumulators can be given a budget through [ErrorAccumulator::with_budget], once exceeded, new
+/// errors are no longer stored and are only counted by their [crate::SimpleErrorExplanation::code].
+#[derive(Debug, Default, Clone)]
+pub struct ErrorAccumulator<'input> {
+    root: SimpleError<'input>,
+    errors: Vec<SimpleError<'input>>,
+    budget: Option<usize>,
+    stored_nodes: usize,
+    dropped_by_code: BTreeMap<Option<String>, usize>,
+    dropped_hard_errors: usize,
+}
+
+impl<'input> ErrorAccumulator<'input> {
+    /// Creates an empty accumulator whose root error gives no information by itself.
+    pub fn new() -> Self {
+        Self { root: SimpleError::new(), errors: Vec::new(), budget: None, stored_nodes: 0, dropped_by_code: BTreeMap::new(), dropped_hard_errors: 0 }
+    }
+
+    /// Creates an empty accumulator whose accumulated errors will be stacked as causes of *root*,
+    /// for example, a 'Couldn't compile code' error.
+    pub fn with_root<PError: Into<SimpleError<'input>>>(root: PError) -> Self {
+        Self { root: root.into(), errors: Vec::new(), budget: None, stored_nodes: 0, dropped_by_code: BTreeMap::new(), dropped_hard_errors: 0 }
+    }
+
+    /// Limits how many errors this accumulator stores, counting every pushed error plus all of its
+    /// causes, once an error would exceed this budget it is no longer stored and is only counted by
+    /// its code, preventing pathological inputs from exhausting memory.
+    pub fn with_budget(mut self, max_stored_errors: usize) -> Self {
+        self.budget = Some(max_stored_errors);
+        self
+    }
+
+    /// Adds an error to the accumulator, if it would exceed the budget set through
+    /// [ErrorAccumulator::with_budget], it only gets counted by its code.
+    pub fn push<PError: Into<SimpleError<'input>>>(&mut self, error: PError) {
+        let error = error.into();
+        let nodes = error.node_count();
+        let exceeds_budget = self.budget
+            .is_some_and(|budget| self.stored_nodes.saturating_add(nodes) > budget);
+        if exceeds_budget || !self.dropped_by_code.is_empty() {
+            *self.dropped_by_code.entry(error.current_code()).or_default() += 1;
+            if error.current_severity() == Severity::Error {
+                self.dropped_hard_errors += 1;
+            }
+            return;
+        }
+        self.stored_nodes += nodes;
+        self.errors.push(error);
+    }
+
+    /// Adds an error to the accumulator indicating where it happened, as in [SimpleError::at].
+    pub fn push_at<ErrorDetail: SimpleErrorDetail + 'input>(&mut self, error_detail: ErrorDetail, location_str: &'input str) {
+        self.push(SimpleError::new().error_detail(error_detail).at(location_str));
+    }
+
+    /// Tells if the budget set through [ErrorAccumulator::with_budget] was exceeded, meaning some
+    /// errors were only counted rather than stored.
+    pub fn budget_exceeded(&self) -> bool {
+        !self.dropped_by_code.is_empty()
+    }
+
+    /// Returns how many errors were only counted after exceeding the budget, keyed by their code,
+    /// errors without a code are counted under [None].
+    pub fn dropped_by_code(&self) -> &BTreeMap<Option<String>, usize> {
+        &self.dropped_by_code
+    }
+
+    /// Returns how many errors were only counted after exceeding the budget.
+    pub fn dropped_count(&self) -> usize {
+        self.dropped_by_code.values().sum()
+    }
+
+    /// Tells if any accumulated error is a hard one, this is, one whose
+    /// [SimpleError::current_severity] is [Severity::Error], whether it was stored or only counted
+    /// after exceeding the budget, in which case [ErrorAccumulator::into_result] fails.
+    pub fn has_errors(&self) -> bool {
+        self.dropped_hard_errors > 0 || self.errors.iter().any(|error| error.current_severity() == Severity::Error)
+    }
+
+    /// Returns how many of the stored errors are less serious than [Severity::Error], like
+    /// warnings.
+    pub fn warning_count(&self) -> usize {
+        self.errors.iter().filter(|error| error.current_severity() < Severity::Error).count()
+    }
+
+    /// Gives the stored errors less serious than [Severity::Error], like warnings, as causes of an
+    /// error saying how many there are, in the same order they were pushed, or [None] if there
+    /// aren't any, so they can be shown even when [ErrorAccumulator::into_result] succeeds, like:
+    ///
+    /// ```rust
+    /// # use simple_detailed_error::{ErrorAccumulator, SimpleError};
+    /// # fn compile() -> Result<(), SimpleError<'static>> {
+    /// let accumulator = ErrorAccumulator::new();
+    /// if let Some(warnings) = accumulator.warnings() {
+    ///     eprintln!("{warnings}");
+    /// }
+    /// accumulator.into_result(())
+    /// # }
+    /// ```
+    pub fn warnings(&self) -> Option<SimpleError<'input>> {
+        let warnings = self.errors.iter().filter(|error| error.current_severity() < Severity::Error).cloned().collect::<Vec<_>>();
+        if warnings.is_empty() {
+            return None;
+        }
+        let mut root = SimpleError::new().error_detail(WarningsSummary(warnings.len()));
+        warnings.into_iter().for_each(|warning| root.add_cause(warning));
+        Some(root)
+    }
+
+    /// Tells if no error has been accumulated, including those only counted after exceeding the
+    /// budget.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty() && self.dropped_by_code.is_empty()
+    }
+
+    /// Returns how many errors have been stored, errors only counted after exceeding the budget
+    /// are given by [ErrorAccumulator::dropped_count].
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns *ok_value* if no hard error was accumulated (See [ErrorAccumulator::has_errors]),
+    /// otherwise, it returns the root error holding all the accumulated errors as causes, warnings
+    /// included, preserving the order they were pushed in.
+    ///
+    /// If the budget was exceeded, a last cause tells how many errors were only counted and their
+    /// codes.
+    #[allow(clippy::result_large_err)]
+    pub fn into_result<T>(self, ok_value: T) -> Result<T, SimpleError<'input>> {
+        if !self.has_errors() {
+            return Ok(ok_value);
+        }
+        let budget_summary = self.budget_summary();
+        let mut root = self.root;
+        self.errors.into_iter().for_each(|error| root.add_cause(error));
+        if let Some(budget_summary) = budget_summary {
+            root.add_cause(budget_summary);
+        }
+        Err(root)
+    }
+
+    /// Explains how many errors were only counted after exceeding the budget, if any.
+    fn budget_summary(&self) -> Option<String> {
+        if self.dropped_by_code.is_empty() {
+            return None;
+        }
+        let counts = self.dropped_by_code.iter().map(|(code, count)| match code {
+            Some(code) => format!("{count} with code {code}"),
+            None => format!("{count} without a code"),
+        });
+        Some(format!("Stopped storing errors after exceeding the budget of {}, another {} were only counted: {}",
+                     pluralize(self.budget.unwrap_or_default(), "error", "0 errors"),
+                     pluralize(self.dropped_count(), "error", ""),
+                     join_strings(", ", counts)))
+    }
+}
+
+/// Explanation of the error holding the warnings given by [ErrorAccumulator::warnings], like 'Found
+/// 2 warnings'.
+#[derive(Debug)]
+struct WarningsSummary(usize);
+
+impl SimpleErrorDetail for WarningsSummary {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        SimpleErrorExplanation::new()
+            .explanation(format!("Found {}", pluralize(self.0, "warning", "")))
+            .severity(Severity::Warning)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::SimpleErrorExplanation;
+
+    #[test]
+    fn accumulated_errors_become_causes_of_the_root() {
+        let input = "let a = ;";
+        let mut accumulator = ErrorAccumulator::with_root("Couldn't compile code".to_string());
+        assert!(accumulator.is_empty());
+        accumulator.push("Missing type".to_string());
+        accumulator.push_at("Missing value".to_string(), &input[8..9]);
+        assert_eq!(accumulator.len(), 2);
+        let error = accumulator.into_result(()).unwrap_err();
+        assert_eq!(error.as_display_struct(false).as_display_string(),
+                   concat!("Error: Couldn't compile code\nHas: 2 explained causes.\nCauses: \n",
+                       "  - Cause nº 1 -\n  - Error: Missing type\n  \n  - Cause nº 2 -\n  - At: ;\n  - Error: Missing value"));
+        assert_eq!(ErrorAccumulator::new().into_result(5).unwrap(), 5);
+    }
+
+    #[test]
+    fn errors_exceeding_the_budget_are_only_counted_by_code() {
+        let coded = |code: &str| SimpleError::from(SimpleErrorExplanation::new().explanation("Invalid token").code(code));
+        let mut accumulator = ErrorAccumulator::new().with_budget(3);
+        accumulator.push(coded("E1").with_cause(coded("E1")));
+        accumulator.push(coded("E1"));
+        accumulator.push(coded("E1").with_cause(coded("E2")));
+        accumulator.push(coded("E2"));
+        accumulator.push(SimpleError::from("Unexpected end".to_string()));
+        assert!(accumulator.budget_exceeded());
+        assert_eq!(accumulator.len(), 2);
+        assert_eq!(accumulator.dropped_count(), 3);
+        assert_eq!(accumulator.dropped_by_code().get(&Some("E2".to_string())), Some(&1));
+        let rendered = accumulator.into_result(()).unwrap_err().as_display_struct(false).as_display_string();
+        assert!(rendered.contains("Code: E1"), "{rendered}");
+        assert!(rendered.contains("Stopped storing errors after exceeding the budget of 3 errors, another 3 errors were only counted: 1 without a code, 1 with code E1, 1 with code E2"), "{rendered}");
+    }
+
+    #[test]
+    fn warnings_are_kept_apart_and_only_errors_fail() {
+        let explained = |severity: Severity, explanation: &'static str| SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation(explanation).severity(severity));
+        let mut accumulator = ErrorAccumulator::new();
+        assert!(accumulator.warnings().is_none());
+        accumulator.push(explained(Severity::Warning, "Unused variable x"));
+        accumulator.push(explained(Severity::Hint, "Consider renaming y"));
+        assert!(!accumulator.has_errors());
+        assert_eq!(accumulator.warning_count(), 2);
+        assert_eq!(accumulator.warnings().unwrap().as_display_struct(false).as_display_string(), concat!(
+            "Warning: Found 2 warnings\nHas: 2 explained causes.\nCauses: \n",
+            "  - Cause nº 1 -\n  - Warning: Unused variable x\n  \n",
+            "  - Cause nº 2 -\n  - Hint: Consider renaming y"));
+        let mut failing = accumulator.clone();
+        assert_eq!(accumulator.into_result(1).unwrap(), 1);
+        failing.push(explained(Severity::Error, "Missing value"));
+        assert!(failing.has_errors());
+        assert_eq!(failing.into_result(1).unwrap_err().current_causes().len(), 3);
+    }
+
+    #[test]
+    fn warnings_exceeding_the_budget_dont_make_the_accumulator_fail() {
+        let warning = || SimpleError::from(SimpleErrorExplanation::new().explanation("Unused variable").severity(Severity::Warning));
+        let mut accumulator = ErrorAccumulator::new().with_budget(1);
+        accumulator.push(warning());
+        accumulator.push(warning());
+        accumulator.push(warning());
+        assert!(accumulator.budget_exceeded());
+        assert_eq!(accumulator.dropped_count(), 2);
+        assert!(!accumulator.has_errors());
+        assert_eq!(accumulator.clone().into_result(5).unwrap(), 5);
+        accumulator.push("Missing ;".to_string());
+        assert!(accumulator.has_errors());
+        assert!(accumulator.into_result(5).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ptr;
+
+use crate::formatting::strip_ansi;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// One error of an error tree flattened through [SimpleErrorDisplayInfo::to_error_records], laid
+/// out as a C struct, so applications written in other languages can read it.
+///
+/// Texts are given as a pointer to their UTF-8 bytes along with their length, without a trailing
+/// NUL, where missing texts are given as a null pointer with a length of 0. Lines and columns start
+/// from 1, so 0 means the error has no position.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ErrorRecord {
+    /// How deep the error is on the tree, 0 for the root error, 1 for its causes, and so on.
+    pub depth: usize,
+    /// Pointer to the explanation of the error, see [SimpleErrorDisplayInfo::reason].
+    pub reason_ptr: *const u8,
+    /// Length in bytes of the explanation of the error.
+    pub reason_len: usize,
+    /// Pointer to the solution of the error followed by its steps, one per line.
+    pub solution_ptr: *const u8,
+    /// Length in bytes of the solution of the error.
+    pub solution_len: usize,
+    /// Line the error happens on, 0 if unknown.
+    pub line: usize,
+    /// Column the error happens on, 0 if unknown.
+    pub column: usize,
+}
+
+/// Array of [ErrorRecord]s given by [SimpleErrorDisplayInfo::to_error_records], holding an error
+/// followed by its causes in depth-first order, where every error comes right before its own
+/// causes.
+///
+/// The records and their texts are owned by this array, so they must be released by giving it back
+/// to [sde_error_records_free] once, after which they must not be read.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ErrorRecords {
+    /// Pointer to the first record.
+    pub records: *mut ErrorRecord,
+    /// How many records there are.
+    pub len: usize,
+}
+
+impl SimpleErrorDisplayInfo {
+    /// This is only implemented when using the ffi feature.
+    ///
+    /// Flattens this error and its explained causes into an array of C structs, see
+    /// [ErrorRecords], which must be released through [sde_error_records_free]. Colors are removed
+    /// from the texts.
+    pub fn to_error_records(&self) -> ErrorRecords {
+        let mut records = Vec::new();
+        self.push_error_records(0, &mut records);
+        let len = records.len();
+        ErrorRecords { records: Box::into_raw(records.into_boxed_slice()) as *mut ErrorRecord, len }
+    }
+
+    fn push_error_records(&self, depth: usize, records: &mut Vec<ErrorRecord>) {
+        let (reason_ptr, reason_len) = into_raw_text(self.reason.as_deref().map(strip_ansi));
+        let (solution_ptr, solution_len) = into_raw_text(self.solution_with_steps().as_deref().map(strip_ansi));
+        let (line, column) = self.on_line_and_column.unwrap_or_default();
+        records.push(ErrorRecord { depth, reason_ptr, reason_len, solution_ptr, solution_len, line, column });
+        self.explained_causes.iter().for_each(|cause| cause.push_error_records(depth + 1, records));
+    }
+}
+
+impl SimpleError<'_> {
+    /// This is only implemented when using the ffi feature.
+    ///
+    /// Flattens this error and its causes into an array of C structs, this is the same as calling
+    /// [SimpleError::as_display_struct] without colors followed by
+    /// [SimpleErrorDisplayInfo::to_error_records].
+    pub fn to_error_records(&self) -> ErrorRecords {
+        self.as_display_struct(false).to_error_records()
+    }
+}
+
+/// This is only implemented when using the ffi feature.
+///
+/// Releases the records given by [SimpleErrorDisplayInfo::to_error_records] along with their texts,
+/// doing nothing if the pointer to the records is null.
+///
+/// # Safety
+///
+/// *records* must have been given by [SimpleErrorDisplayInfo::to_error_records] without being
+/// modified, and it must not be released more than once.
+#[no_mangle]
+pub unsafe extern "C" fn sde_error_records_free(records: ErrorRecords) {
+    if records.records.is_null() {
+        return;
+    }
+    let records = unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(records.records, records.len)) };
+    for record in records.iter() {
+        unsafe {
+            free_raw_text(record.reason_ptr, record.reason_len);
+            free_raw_text(record.solution_ptr, record.solution_len);
+        }
+    }
+}
+
+/// Leaks the bytes of *text*, giving the pointer to them and their length, or a null pointer when
+/// there isn't a text.
+fn into_raw_text(text: Option<String>) -> (*const u8, usize) {
+    match text {
+        Some(text) => {
+            let len = text.len();
+            (Box::into_raw(text.into_bytes().into_boxed_slice()) as *const u8, len)
+        }
+        None => (ptr::null(), 0),
+    }
+}
+
+/// Releases the bytes leaked by [into_raw_text].
+unsafe fn free_raw_text(text: *const u8, len: usize) {
+    if !text.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(text as *mut u8, len)) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::slice;
+    use core::str;
+
+    use super::*;
+    use crate::SimpleErrorExplanation;
+
+    fn text_of(text_ptr: *const u8, text_len: usize) -> Option<String> {
+        if text_ptr.is_null() {
+            return None;
+        }
+        Some(str::from_utf8(unsafe { slice::from_raw_parts(text_ptr, text_len) }).unwrap().to_string())
+    }
+
+    #[test]
+    fn error_trees_are_flattened_into_records_depth_first() {
+        let input = "a\nbc";
+        let mut cause = SimpleError::new().error_detail("Invalid type".to_string());
+        cause.add_cause(SimpleError::new().error_detail("Unknown type Strng".to_string()));
+        let error = SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Couldn't compile").solution("Fix the type"))
+            .at(&input[3..4]).with_source(input).with_cause(cause);
+        let records = error.to_error_records();
+        assert_eq!(records.len, 3);
+        let entries = unsafe { slice::from_raw_parts(records.records, records.len) };
+        assert_eq!(text_of(entries[0].reason_ptr, entries[0].reason_len).as_deref(), Some("Couldn't compile"));
+        assert_eq!(text_of(entries[0].solution_ptr, entries[0].solution_len).as_deref(), Some("Fix the type"));
+        assert_eq!((entries[0].depth, entries[0].line, entries[0].column), (0, 2, 2));
+        assert_eq!(text_of(entries[1].reason_ptr, entries[1].reason_len).as_deref(), Some("Invalid type"));
+        assert_eq!(text_of(entries[1].solution_ptr, entries[1].solution_len), None);
+        assert_eq!((entries[2].depth, entries[2].line), (2, 0));
+        unsafe { sde_error_records_free(records) };
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::process::{ExitCode, Termination};
+
+use crate::color_choice::ColorChoice;
+use crate::display_options::DisplayOptions;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Outcome of a command line application, which can be returned from `main` so the error is
+/// printed to stderr with colors when it is a terminal (See [ColorChoice::Stderr]) and the process
+/// exits with the code set through [FinalReport::with_exit_code] or
+/// [FinalReport::with_exit_code_from], 1 by default, like
+/// `fn main() -> FinalReport { FinalReport::from_result(run()) }`, this is the way to get
+/// configurable exit codes.
+///
+/// Errors turn into final reports too, so `fn main() -> Result<(), FinalReport>` works along with
+/// the `?` operator, in which case std prints its own `Error: ` prefix followed by the error tree
+/// without its leading severity label (See the [Debug] implementation), but the process always
+/// exits with code 1, as std decides it for [Result]s regardless of the exit code of the report.
+pub struct FinalReport {
+    error: Option<SimpleErrorDisplayInfo>,
+    exit_code: u8,
+    options: DisplayOptions,
+}
+
+impl FinalReport {
+    /// Creates a report of a successful execution, exiting with code 0 and printing nothing.
+    pub fn success() -> Self {
+        Self { error: None, exit_code: 0, options: DisplayOptions::default() }
+    }
+
+    /// Creates a report of a failed execution because of *error*, rendering it right away, so it
+    /// doesn't borrow the input *error* might point into.
+    pub fn failure<'input, PError: Into<SimpleError<'input>>>(error: PError) -> Self {
+        let error = error.into().as_display_struct(ColorChoice::Stderr);
+        Self { error: Some(error), exit_code: 1, options: DisplayOptions::default() }
+    }
+
+    /// Creates a successful report if *result* is [Ok], or a failed one holding its error
+    /// otherwise.
+    pub fn from_result<'input, T, PError: Into<SimpleError<'input>>>(result: Result<T, PError>) -> Self {
+        match result {
+            Ok(_) => Self::success(),
+            Err(error) => Self::failure(error),
+        }
+    }
+
+    /// Sets the code the process exits with when this report holds an error.
+    pub fn with_exit_code(mut self, exit_code: u8) -> Self {
+        if self.error.is_some() {
+            self.exit_code = exit_code;
+        }
+        self
+    }
+
+    /// Sets the code the process exits with when this report holds an error as the one given by
+    /// *exit_code_of* for said error, such as giving a code for every [crate::Severity] or for
+    /// every [SimpleErrorDisplayInfo::code].
+    pub fn with_exit_code_from<ExitCodeOf: FnOnce(&SimpleErrorDisplayInfo) -> u8>(mut self, exit_code_of: ExitCodeOf) -> Self {
+        if let Some(error) = &self.error {
+            self.exit_code = exit_code_of(error);
+        }
+        self
+    }
+
+    /// Sets the options used for printing the error, see [DisplayOptions].
+    pub fn with_options(mut self, options: DisplayOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the error held by this report, if the execution failed.
+    pub fn error(&self) -> Option<&SimpleErrorDisplayInfo> {
+        self.error.as_ref()
+    }
+
+    /// Returns the code the process exits with when this report is returned from `main`.
+    pub fn exit_code(&self) -> u8 {
+        self.exit_code
+    }
+}
+
+impl<'input, PError: Into<SimpleError<'input>>> From<PError> for FinalReport {
+    fn from(error: PError) -> Self {
+        Self::failure(error)
+    }
+}
+
+/// Prints the error to stderr, if any, and exits with the code of this report.
+impl Termination for FinalReport {
+    fn report(self) -> ExitCode {
+        if self.error.is_some() {
+            eprintln!("{self}");
+        }
+        ExitCode::from(self.exit_code)
+    }
+}
+
+/// Displays the error held by this report, or nothing on successful reports.
+impl Display for FinalReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.error {
+            Some(error) => f.write_str(&error.as_display_string_with_options(&self.options)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Displays the error held by this report as [Display] does, but without the severity label the
+/// error starts with, like `Error: `, so returning `Result<(), FinalReport>` from `main` prints
+/// the error tree rather than its fields, after std's own `Error: ` prefix without repeating it.
+impl Debug for FinalReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(error) = &self.error else {
+            return Ok(());
+        };
+        let display = error.as_display_string_with_options(&self.options);
+        let severity_prefix = format!("{}: ", self.options.severity_label(error.severity()));
+        f.write_str(display.strip_prefix(&severity_prefix).unwrap_or(&display))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Severity, SimpleErrorExplanation};
+
+    #[test]
+    fn failures_are_printed_and_exit_with_their_code() {
+        #[allow(clippy::result_large_err)]
+        fn run() -> Result<(), FinalReport> {
+            Err(String::from("Config file not found"))?;
+            Ok(())
+        }
+
+        assert_eq!(format!("{:?}", run().unwrap_err()), "Config file not found");
+        let solved = FinalReport::failure(SimpleErrorExplanation::new().explanation("Config file not found").solution("Create config.toml"));
+        assert_eq!(format!("Error: {solved:?}"), solved.to_string());
+        let located = FinalReport::failure(SimpleError::from(String::from("Unknown key")).at("key = 1"));
+        assert_eq!(format!("{located:?}"), located.to_string());
+        let warning = SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Deprecated flag").severity(Severity::Warning));
+        let report = FinalReport::from_result(Err::<(), _>(warning))
+            .with_exit_code_from(|error| if error.severity() == Severity::Warning { 2 } else { 1 });
+        assert_eq!(report.exit_code(), 2);
+        assert_eq!(FinalReport::failure(String::from("Config file not found")).exit_code(), 1);
+        assert_eq!(FinalReport::success().with_exit_code(5).exit_code(), 0);
+        assert_eq!(FinalReport::success().report(), ExitCode::SUCCESS);
+    }
+}
@@ -0,0 +1,280 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::formatting::{join_strings, strip_ansi};
+use crate::report::ReportDisplayInfo;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// 64-bit FNV-1a hasher, used for fingerprints since, unlike the hashers given by std, it gives the
+/// same values across executions, platforms and versions of Rust.
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) const fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    /// Hashes an optional text, writing values whose size doesn't depend on the platform.
+    pub(crate) fn write_text(&mut self, text: Option<&str>) {
+        match text {
+            None => self.write_u8(0),
+            Some(text) => {
+                self.write_u8(1);
+                self.write(text.as_bytes());
+                self.write_u8(0xff);
+            }
+        }
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+}
+
+impl SimpleErrorDisplayInfo {
+    /// Gives a number identifying this error, computed from its code, severity, explanation, where
+    /// it happened and the fingerprints of its explained causes, being the same across executions
+    /// and platforms, so it can be used to deduplicate errors or to compare them against a baseline
+    /// of errors already known.
+    ///
+    /// Lines and columns, solutions, notes, helps, labels and suggestions are left out, so errors
+    /// keep their fingerprint when the input around them changes, and colors are ignored too.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_text(self.code.as_deref());
+        hasher.write_text(Some(self.severity.name()));
+        hasher.write_text(self.reason.as_deref().map(strip_ansi).as_deref());
+        hasher.write_text(self.at.as_deref().map(strip_ansi).as_deref());
+        hasher.write_u64(self.explained_causes.len() as u64);
+        self.explained_causes.iter().for_each(|cause| hasher.write_u64(cause.fingerprint()));
+        hasher.finish()
+    }
+
+    /// Gives a number identifying this error like [SimpleErrorDisplayInfo::fingerprint] does, but
+    /// also taking into account the file and the lines and columns this error and its causes start
+    /// and end at, so the same error happening at different places of the input gets different
+    /// fingerprints.
+    pub fn positioned_fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(self.fingerprint());
+        for (line, column) in [self.on_line_and_column, self.up_to_line_an_column].into_iter().flatten() {
+            hasher.write_u64(line as u64);
+            hasher.write_u64(column as u64);
+        }
+        if let Some(file) = &self.file {
+            hasher.write_text(Some(file));
+        }
+        self.explained_causes.iter().for_each(|cause| hasher.write_u64(cause.positioned_fingerprint()));
+        hasher.finish()
+    }
+
+    /// Wraps this error into a [Fingerprinted], whose [Hash] and [Eq] are given by its fingerprint,
+    /// so it can be stored in sets and maps.
+    pub fn fingerprinted(self) -> Fingerprinted {
+        Fingerprinted::new(self)
+    }
+
+    /// Returns a copy of this error where causes with the same
+    /// [SimpleErrorDisplayInfo::fingerprint] are merged into the first of them, which gets a note
+    /// saying how many times it occurred and on which lines, like 'Occurred 12 times (lines 3, 7,
+    /// 19, …)', this is done on the causes of causes too.
+    ///
+    /// This is useful when the same mistake repeats across a large input, see
+    /// [crate::DisplayOptions::group_identical] to only merge them when displaying.
+    pub fn dedup_causes(&self) -> Self {
+        let mut res = self.clone();
+        res.explained_causes = group_identical(self.explained_causes.iter().map(|cause| cause.dedup_causes()).collect());
+        res
+    }
+}
+
+impl ReportDisplayInfo {
+    /// Returns a copy of this report where errors with the same
+    /// [SimpleErrorDisplayInfo::fingerprint] are merged into the first of them, noting how many
+    /// times they occurred, as [SimpleErrorDisplayInfo::dedup_causes] does with causes.
+    pub fn dedup_errors(&self) -> Self {
+        Self { errors: group_identical(self.errors.iter().map(|error| error.dedup_causes()).collect()), ..self.clone() }
+    }
+}
+
+/// How many lines of the merged errors are listed on the note given by [group_identical].
+const MAX_LISTED_LINES: usize = 3;
+
+/// Merges the errors with the same fingerprint into the first of them, keeping the order of the
+/// first occurrences and adding a note saying how many times every merged error occurred.
+pub(crate) fn group_identical(errors: Vec<SimpleErrorDisplayInfo>) -> Vec<SimpleErrorDisplayInfo> {
+    let mut grouped = Vec::<(SimpleErrorDisplayInfo, Vec<Option<usize>>)>::new();
+    let mut index_of_fingerprint = BTreeMap::<u64, usize>::new();
+    for error in errors {
+        let line = error.on_line_and_column.map(|(line, _)| line);
+        let fingerprint = error.fingerprint();
+        match index_of_fingerprint.get(&fingerprint) {
+            Some(index) => grouped[*index].1.push(line),
+            None => {
+                index_of_fingerprint.insert(fingerprint, grouped.len());
+                grouped.push((error, Vec::from([line])));
+            }
+        }
+    }
+    grouped.into_iter()
+        .map(|(mut error, lines)| {
+            if lines.len() > 1 {
+                let known_lines = lines.iter().flatten().collect::<Vec<_>>();
+                let listed_lines = join_strings(", ", known_lines.iter().take(MAX_LISTED_LINES).map(|line| format!("{line}"))
+                    .chain((known_lines.len() > MAX_LISTED_LINES).then(|| "…".into())));
+                let note = if known_lines.is_empty() {
+                    format!("Occurred {} times", lines.len())
+                } else {
+                    format!("Occurred {} times (lines {listed_lines})", lines.len())
+                };
+                error.notes.push(note);
+            }
+            error
+        })
+        .collect()
+}
+
+impl SimpleError<'_> {
+    /// Gives a number identifying this error that stays the same across executions and platforms,
+    /// so monitoring pipelines can group identical errors, this is the same as calling
+    /// [SimpleError::as_display_struct] without colors followed by
+    /// [SimpleErrorDisplayInfo::fingerprint].
+    pub fn fingerprint(&self) -> u64 {
+        self.as_display_struct(false).fingerprint()
+    }
+
+    /// Gives a number identifying this error along with the lines and columns it happens at, this
+    /// is the same as calling [SimpleError::as_display_struct] without colors followed by
+    /// [SimpleErrorDisplayInfo::positioned_fingerprint].
+    pub fn positioned_fingerprint(&self) -> u64 {
+        self.as_display_struct(false).positioned_fingerprint()
+    }
+}
+
+/// Wrapper over [SimpleErrorDisplayInfo] whose [Hash] and [Eq] implementations are given by its
+/// [SimpleErrorDisplayInfo::fingerprint], so errors can be stored in sets and maps to deduplicate
+/// them, to compare them against a baseline or to diff reports, without extracting keys by hand.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SimpleErrorDisplayInfo", into = "SimpleErrorDisplayInfo"))]
+#[derive(Debug, Clone)]
+pub struct Fingerprinted {
+    fingerprint: u64,
+    display_info: SimpleErrorDisplayInfo,
+}
+
+impl Fingerprinted {
+    /// Wraps *display_info*, computing its fingerprint once.
+    pub fn new(display_info: SimpleErrorDisplayInfo) -> Self {
+        Self { fingerprint: display_info.fingerprint(), display_info }
+    }
+
+    /// Returns the fingerprint of the wrapped error.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> SimpleErrorDisplayInfo {
+        self.display_info
+    }
+}
+
+impl From<SimpleErrorDisplayInfo> for Fingerprinted {
+    fn from(value: SimpleErrorDisplayInfo) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Fingerprinted> for SimpleErrorDisplayInfo {
+    fn from(value: Fingerprinted) -> Self {
+        value.display_info
+    }
+}
+
+impl Deref for Fingerprinted {
+    type Target = SimpleErrorDisplayInfo;
+
+    fn deref(&self) -> &Self::Target {
+        &self.display_info
+    }
+}
+
+impl PartialEq for Fingerprinted {
+    fn eq(&self, other: &Self) -> bool {
+        self.fingerprint == other.fingerprint
+    }
+}
+
+impl Eq for Fingerprinted {}
+
+impl Hash for Fingerprinted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.fingerprint.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::{SimpleError, SimpleErrorExplanation};
+    use crate::{DisplayOptions, Report};
+
+    #[test]
+    fn fingerprints_ignore_solutions_and_positions_but_not_codes() {
+        let source = "let a\nlet b x";
+        let unexpected = |code: &str, solution: &str| SimpleError::from(SimpleErrorExplanation::new().explanation("Unexpected token").code(code).solution(solution));
+        let first = unexpected("E1", "Remove it").at(&source[12..13]).with_source(source).as_display_struct(false);
+        let second = unexpected("E1", "Add a ;").at("x").start_point_of_error(9, 9).as_display_struct(false);
+        let other_code = unexpected("E2", "Remove it").at("x").as_display_struct(false);
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_ne!(first.fingerprint(), other_code.fingerprint());
+        assert_eq!(first.clone().fingerprinted(), Fingerprinted::new(second));
+        assert_ne!(first.fingerprinted(), other_code.fingerprinted());
+    }
+
+    #[test]
+    fn positioned_fingerprints_tell_apart_identical_errors_at_different_lines() {
+        let unexpected_at = |line| SimpleError::new().error_detail("Unexpected ;".to_string()).start_point_of_error(line, 2);
+        assert_eq!(unexpected_at(1).fingerprint(), unexpected_at(3).fingerprint());
+        assert_ne!(unexpected_at(1).positioned_fingerprint(), unexpected_at(3).positioned_fingerprint());
+        assert_eq!(unexpected_at(1).positioned_fingerprint(), unexpected_at(1).positioned_fingerprint());
+    }
+
+    #[test]
+    fn identical_causes_are_grouped_with_the_lines_they_occur_at() {
+        let input = "a\nx\nb\nx\nx\nx\nx";
+        let mut error = SimpleError::new().with_source(input).error_detail("Couldn't compile".to_string());
+        for (index, _) in input.match_indices('x') {
+            error.add_cause(SimpleError::new().at(&input[index..index + 1]).error_detail("Unknown identifier".to_string()));
+        }
+        error.add_cause(SimpleError::new().at(&input[0..1]).error_detail("Unused variable".to_string()));
+        let grouped = error.as_display_struct(false).dedup_causes();
+        assert_eq!(grouped.explained_causes().len(), 2);
+        assert_eq!(grouped.explained_causes()[0].notes(), &["Occurred 5 times (lines 2, 4, 5, …)".to_string()]);
+        assert!(grouped.explained_causes()[1].notes().is_empty());
+        let options = DisplayOptions { group_identical: true, ..DisplayOptions::default() };
+        assert_eq!(error.as_display_string_with_options(false, &options), grouped.as_display_string());
+        let report = Report::from(vec![SimpleError::from("Missing value".to_string()), SimpleError::from("Missing value".to_string())]);
+        assert_eq!(report.as_display_struct(false).as_display_string_with_options(&options), "Error: Missing value\nNote: Occurred 2 times");
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Gives *count* followed by *word*, adding an 's' when *count* isn't 1, like '1 cause' or
+/// '2 causes', or *on_empty* when *count* is 0.
+///
+/// This is what the built-in layout uses for English counts, see
+/// [crate::display_options::NumberFormatter] for other languages.
+pub fn pluralize<Num, ToPluralize, OnEmpty>(count: Num, word_to_pluralize: ToPluralize, on_empty: OnEmpty) -> String
+    where Num: Into<usize>, ToPluralize: AsRef<str>, OnEmpty: AsRef<str>
+{
+    match count.into() {
+        0 => on_empty.as_ref().to_string(),
+        1 => format!("1 {}", word_to_pluralize.as_ref()),
+        count => format!("{count} {}s", word_to_pluralize.as_ref())
+    }
+}
+
+/// Joins every string given by *strings* placing *separator* between them, without collecting them
+/// first.
+pub fn join_strings<Strings: IntoIterator<Item=Item>, Item: AsRef<str>>(separator: &str, strings: Strings) -> String {
+    let mut res = String::new();
+    let mut is_first_string = true;
+    strings.into_iter().for_each(|string| {
+        if !is_first_string {
+            res.push_str(separator)
+        }
+        res.push_str(string.as_ref());
+        is_first_string = false;
+    });
+    res
+}
+
+/// Indents every line of *text* but the first one by *spaces* spaces, this is how the built-in
+/// layout aligns the lines of a field under the label prefixing its first line, like
+/// `Solution: ` followed by a multi-line solution.
+pub fn indent_lines_except_first<Text: AsRef<str>>(text: Text, spaces: usize) -> String {
+    let spacing = " ".repeat(spaces);
+    join_strings("\n", text.as_ref().lines().enumerate().map(|(line_no, line)| {
+        if line_no == 0 { line.to_string() } else { format!("{spacing}{line}") }
+    }))
+}
+
+/// Gives how many characters of *text* are visible on a terminal, leaving out colors and other
+/// escape sequences, so texts can be aligned and wrapped regardless of their colors.
+pub fn display_width(text: &str) -> usize {
+    strip_ansi(text).chars().count()
+}
+
+/// Soft-wraps every line of *text* at the spaces closest to *width* visible characters (See
+/// [display_width]), where every line but the first one is considered to be indented by
+/// *hanging_indentation* spaces, as done later by [indent_lines_except_first]. Words longer than
+/// the width are left unbroken.
+pub fn wrap_lines(text: &str, width: usize, hanging_indentation: usize) -> String {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let mut current_line = String::new();
+        let mut current_len = 0;
+        for (word_no, word) in line.split(' ').enumerate() {
+            let available_width = if lines.is_empty() { width } else { width.saturating_sub(hanging_indentation) };
+            let word_len = display_width(word);
+            if current_len > 0 && current_len + 1 + word_len > available_width {
+                lines.push(core::mem::take(&mut current_line));
+                current_len = 0;
+            } else if word_no > 0 {
+                current_line.push(' ');
+                current_len += 1;
+            }
+            current_line.push_str(word);
+            current_len += word_len;
+        }
+        lines.push(current_line);
+    }
+    join_strings("\n", lines.iter())
+}
+
+/// Removes the ANSI escape sequences (Such as colors and styles) from the given string, along with
+/// OSC 8 hyperlinks, which keep their text, so texts built with colors can be written to logs and
+/// files without escape codes.
+pub fn strip_ansi(string: &str) -> String {
+    let mut res = String::with_capacity(string.len());
+    let mut chars = string.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char != '\u{1b}' {
+            res.push(char);
+            continue;
+        }
+        match chars.next() {
+            // Control Sequence: ESC [ parameters... final byte in '@'..='~'
+            Some('[') => {
+                for char in chars.by_ref() {
+                    if ('@'..='~').contains(&char) { break; }
+                }
+            }
+            // Operating System Command: ESC ] ... terminated by BEL or ESC \
+            Some(']') => {
+                while let Some(char) = chars.next() {
+                    if char == '\u{7}' { break; }
+                    if char == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::SimpleError;
+
+    #[test]
+    fn helpers_format_like_the_built_in_layout() {
+        assert_eq!(pluralize(2usize, "cause", "no causes"), "2 causes");
+        assert_eq!(pluralize(1usize, "cause", "no causes"), "1 cause");
+        assert_eq!(pluralize(0usize, "cause", "no causes"), "no causes");
+        assert_eq!(join_strings(", ", ["Missing ;", "Missing type"]), "Missing ;, Missing type");
+        assert_eq!(indent_lines_except_first("Error: a\nb\nc", 2), "Error: a\n  b\n  c");
+        assert_eq!(display_width("\u{1b}[31mañ\u{1b}[0m"), 2);
+        assert_eq!(wrap_lines("one two three four", 9, 4), "one two\nthree\nfour");
+    }
+
+    #[test]
+    fn escape_sequences_and_hyperlinks_are_stripped_from_plain_strings() {
+        assert_eq!(strip_ansi("\u{1b}[31mUnexpected\u{1b}[0m \u{1b}]8;;https://example.com\u{1b}\\token\u{1b}]8;;\u{1b}\\"), "Unexpected token");
+        let display = SimpleError::new().error_detail("\u{1b}[1mMissing value\u{1b}[0m".to_string()).as_display_struct(true);
+        assert_eq!(display.to_plain_string(), "Error: Missing value");
+    }
+}
@@ -1,42 +1,157 @@
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
-pub(crate) fn pluralize<Num, ToPluralize, OnEmpty>(n: Num, word_to_pluralize: ToPluralize, on_empty: OnEmpty) -> String
-    where Num: Into<usize>, ToPluralize: AsRef<str>, OnEmpty: AsRef<str>
-{
-    let res = match n.into() {
-        0 => return on_empty.as_ref().to_string(),
-        1 => format!("1 {}", word_to_pluralize.as_ref()),
-        n => format!("{n} {}s", word_to_pluralize.as_ref())
-    };
-    return res;
+pub(crate) use crate::fmt_util::{display_width, indent_lines_except_first, join_strings, pluralize, strip_ansi, wrap_lines};
+use crate::span::Span;
+
+/// Turns *text* into an OSC 8 hyperlink pointing at *url*, which terminals supporting them show as
+/// a clickable link, its escape sequences are removed along with colors by [strip_ansi].
+pub(crate) fn hyperlink(url: &str, text: &str) -> String {
+    format!("\u{1b}]8;;{url}\u{1b}\\{text}\u{1b}]8;;\u{1b}\\")
+}
+
+/// Surrounds every substring of *input* with *open* and *close*, the substrings must be references
+/// taken from *input*, those that aren't or that overlap a previous one are ignored.
+pub(crate) fn bracket_substrings(input: &str, substrings: &[&str], open: &str, close: &str) -> String {
+    let input_start = input.as_ptr() as usize;
+    let input_end = input_start + input.len();
+    let mut ranges = substrings.iter()
+        .map(|substring| (substring.as_ptr() as usize, substring.len()))
+        .filter(|(start, len)| *start >= input_start && start + len <= input_end)
+        .map(|(start, len)| (start - input_start, start - input_start + len))
+        .collect::<Vec<_>>();
+    ranges.sort();
+    let mut res = String::with_capacity(input.len() + ranges.len() * (open.len() + close.len()));
+    let mut written_up_to = 0;
+    for (start, end) in ranges {
+        if start < written_up_to { continue; }
+        res.push_str(&input[written_up_to..start]);
+        res.push_str(open);
+        res.push_str(&input[start..end]);
+        res.push_str(close);
+        written_up_to = end;
+    }
+    res.push_str(&input[written_up_to..]);
+    res
+}
+
+/// Returns the part of *substring* overlapping *part*, as a reference taken from *part*, both must
+/// be references taken from the same input, otherwise, or if they don't overlap, it will be None.
+pub(crate) fn clip_to<'part>(substring: &str, part: &'part str) -> Option<&'part str> {
+    let part_start = part.as_ptr() as usize;
+    let substring_start = substring.as_ptr() as usize;
+    let start = substring_start.max(part_start);
+    let end = (substring_start + substring.len()).min(part_start + part.len());
+    if start >= end {
+        return None;
+    }
+    part.get(start - part_start..end - part_start)
+}
+
+/// Splits *input* into consecutive parts, telling for each one of them whether it is covered by
+/// *excluded*, which must be references taken from *input*, where those parts of them lying outside
+/// *input* are ignored.
+pub(crate) fn split_excluded<'input>(input: &'input str, excluded: &[&str]) -> Vec<(&'input str, bool)> {
+    let mut spans = excluded.iter()
+        .filter_map(|excluded| clip_to(excluded, input))
+        .filter_map(|excluded| Span::of(input, excluded))
+        .filter(|span| !span.is_empty())
+        .collect::<Vec<_>>();
+    spans.sort();
+    let mut res = Vec::new();
+    let mut written_up_to = 0;
+    for span in spans {
+        let start = span.start.max(written_up_to);
+        if start >= span.end { continue; }
+        if start > written_up_to {
+            res.push((&input[written_up_to..start], false));
+        }
+        res.push((&input[start..span.end], true));
+        written_up_to = span.end;
+    }
+    if written_up_to < input.len() || res.is_empty() {
+        res.push((&input[written_up_to..], false));
+    }
+    res
 }
 
-pub(crate) fn join_strings<Strings: Iterator<Item=Item>, Item: AsRef<str>>(separator: &str, strings: Strings) -> String {
-    let mut res = String::new();
-    let mut is_first_string = true;
-    strings.for_each(|string| {
-        if !is_first_string {
-            res.push_str(separator)
+/// Turns *bytes* into a string where bytes that aren't valid UTF-8 are written as escapes like
+/// `\xff`, and so are non-printable characters other than new lines and tabs, like `\x00` or
+/// `\u{200b}`, said escapes are highlighted if *colorize* is true.
+pub(crate) fn escape_bytes(bytes: &[u8], colorize: bool) -> String {
+    let highlight = |escape: String| -> String {
+        #[cfg(feature = "colorization")]
+        if colorize {
+            use colored::Colorize;
+            return escape.reversed().to_string();
+        }
+        #[cfg(all(feature = "colorization-anstyle", not(feature = "colorization")))]
+        if colorize {
+            let style = anstyle::Style::new().effects(anstyle::Effects::INVERT);
+            return format!("{}{escape}{}", style.render(), style.render_reset());
         }
-        res.push_str(string.as_ref());
-        is_first_string = false;
-    });
+        #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+            let _ = colorize;
+        escape
+    };
+    let mut res = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        for char in chunk.valid().chars() {
+            let is_invisible = (char.is_control() && char != '\n' && char != '\t')
+                || matches!(char, '\u{200b}'..='\u{200f}' | '\u{2028}'..='\u{202e}' | '\u{2060}'..='\u{2064}' | '\u{feff}');
+            if !is_invisible {
+                res.push(char);
+            } else if (char as u32) < 0x80 {
+                res.push_str(&highlight(format!("\\x{:02x}", char as u32)));
+            } else {
+                res.push_str(&highlight(format!("\\u{{{:x}}}", char as u32)));
+            }
+        }
+        chunk.invalid().iter().for_each(|byte| res.push_str(&highlight(format!("\\x{byte:02x}"))));
+    }
     res
 }
 
-pub(crate) fn ident_lines_except_first(prefixed_contents: String, mut spaces: usize) -> String {
-    let mut spacing = String::new();
-    while spaces > 0 {
-        spacing.push_str(" ");
-        spaces -= 1;
+/// Removes the indentation shared by every non-blank line of the given string, along with its
+/// leading blank lines and trailing whitespace, so multi-line code samples written with the
+/// indentation of the code declaring them are shown as intended.
+///
+/// Indentation is compared char by char, so tabs and spaces are never considered equal, and when
+/// lines mix them differently, only the part they have in common is removed.
+pub(crate) fn dedent(string: &str) -> String {
+    let is_blank = |line: &str| line.trim().is_empty();
+    let common_indentation = string.lines()
+        .filter(|line| !is_blank(line))
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .reduce(|common, indentation| {
+            let common_len = common.char_indices().zip(indentation.chars())
+                .find(|((_, common_char), indentation_char)| common_char != indentation_char)
+                .map(|((offset, _), _)| offset)
+                .unwrap_or(common.len().min(indentation.len()));
+            &common[..common_len]
+        })
+        .unwrap_or_default();
+    let lines = string.lines()
+        .skip_while(|line| is_blank(line))
+        .map(|line| if is_blank(line) { "" } else { &line[common_indentation.len()..] });
+    join_strings("\n", lines).trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SimpleError, SimpleErrorExplanation};
+
+    #[test]
+    fn common_indentation_is_removed_char_by_char() {
+        assert_eq!(dedent("\n    Declare it:\n        let x = 1;\n\n    then use it.   \n"), "Declare it:\n    let x = 1;\n\nthen use it.");
+        assert_eq!(dedent("\t\tfn a() {\n\t\t\tb\n\t\t}"), "fn a() {\n\tb\n}");
+        assert_eq!(dedent("  \ta\n \tb"), " \ta\n\tb");
+        let verbatim = SimpleErrorExplanation::new().solution_verbatim("  x  ");
+        assert_eq!(SimpleError::from(verbatim).as_display_struct(false).as_display_string(), "Error: Unexplained error\nSolution:   x  ");
+        let dedented = SimpleErrorExplanation::new().solution("\n        Declare it:\n            let x = 1;\n    ");
+        assert_eq!(SimpleError::from(dedented).as_display_struct(false).as_display_string(),
+                   "Error: Unexplained error\nSolution: Declare it:\n              let x = 1;");
     }
-    let mut is_first_line = true;
-
-    let spaced_contents = join_strings("\n", prefixed_contents.lines().map(|line| {
-        let res = if is_first_line { line.to_string() } else { spacing.clone() + line };
-        is_first_line = false;
-        res
-    }));
-    spaced_contents
 }
@@ -0,0 +1,69 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::formatting::{join_strings, strip_ansi};
+use crate::report::ReportDisplayInfo;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// Gives this error in the single-line format used by GCC and Clang, like
+    /// `main.script:3:7: error: Variable 'x' doesn't exist [E001]`, followed by a
+    /// `main.script:1:1: note: ...` line for every explained cause, where *file_name* is the name of
+    /// the file the error happened on.
+    ///
+    /// Since editors and build systems already read this format, it lets users jump to the errors,
+    /// like through Vim's quickfix list. Colors are removed, multi-line explanations are joined
+    /// into a single line, and the line and column are left out when the error has no position.
+    pub fn as_gcc_string(&self, file_name: &str) -> String {
+        let mut lines = Vec::new();
+        lines.push(gcc_line(self, file_name, &self.severity.name().to_lowercase()));
+        self.push_gcc_cause_lines(file_name, &mut lines);
+        join_strings("\n", lines.iter())
+    }
+
+    fn push_gcc_cause_lines(&self, file_name: &str, lines: &mut Vec<String>) {
+        for cause in &self.explained_causes {
+            lines.push(gcc_line(cause, file_name, "note"));
+            cause.push_gcc_cause_lines(file_name, lines);
+        }
+    }
+}
+
+impl ReportDisplayInfo {
+    /// Gives every error of the report in the single-line format used by GCC and Clang, see
+    /// [SimpleErrorDisplayInfo::as_gcc_string].
+    pub fn as_gcc_string(&self, file_name: &str) -> String {
+        join_strings("\n", self.errors.iter().map(|error| error.as_gcc_string(file_name)))
+    }
+}
+
+/// Gives the line `file_name:line:column: kind: message [code]` for *display*.
+fn gcc_line(display: &SimpleErrorDisplayInfo, file_name: &str, kind: &str) -> String {
+    let position = display.on_line_and_column
+        .map(|(line, column)| format!(":{line}:{column}"))
+        .unwrap_or_default();
+    let message = display.reason.as_deref().map(strip_ansi).unwrap_or_else(|| String::from("Unexplained error"));
+    let message = join_strings(" ", message.lines().map(str::trim).filter(|line| !line.is_empty()));
+    let code = display.code.as_ref().map(|code| format!(" [{code}]")).unwrap_or_default();
+    format!("{file_name}{position}: {kind}: {message}{code}")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::{Report, SimpleError, SimpleErrorExplanation};
+
+    #[test]
+    fn errors_and_explained_causes_become_gcc_lines() {
+        let source = "let a = b;\nlet c = d;";
+        let error = SimpleError::new().with_source(source).at_span(19..20)
+            .error_detail(SimpleErrorExplanation::new().explanation("Variable 'd'\n  doesn't exist").code("E001"))
+            .with_cause(SimpleError::new().with_source(source).at_span(4..5).error_detail("Declared here".to_string()));
+        assert_eq!(error.as_display_struct(false).as_gcc_string("main.script"),
+                   "main.script:2:9: error: Variable 'd' doesn't exist [E001]\nmain.script:1:5: note: Declared here");
+        let report = Report::new().with_error(SimpleError::new()).with_error("Unexpected ;".to_string());
+        assert_eq!(report.as_display_struct(false).as_gcc_string("main.script"), "main.script: error: Unexplained error\nmain.script: error: Unexpected ;");
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde_json::{Map, Value};
+
+use crate::formatting::strip_ansi;
+use crate::report::ReportDisplayInfo;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// This is only implemented when using the serde feature.
+    ///
+    /// Gives this error as an entry of the `errors` array of a GraphQL response, so GraphQL servers
+    /// validating user-supplied snippets can return these errors as they are, where:
+    /// * `message` is the explanation of the error without colors.
+    /// * `locations` holds the line and column the error happens at, and it is left out when the
+    ///   error has no position.
+    /// * `extensions` holds the `code` and `solution` of the error, when given, and its explained
+    ///   causes as `causes`, given in this same shape.
+    pub fn as_graphql_error(&self) -> Value {
+        let mut error = Map::new();
+        let message = self.reason.as_deref().map(strip_ansi).unwrap_or_else(|| String::from("Unexplained error"));
+        error.insert("message".into(), Value::String(message));
+        if let Some((line, column)) = self.on_line_and_column {
+            let mut location = Map::new();
+            location.insert("line".into(), Value::from(line));
+            location.insert("column".into(), Value::from(column));
+            error.insert("locations".into(), Value::Array(Vec::from([Value::Object(location)])));
+        }
+        let mut extensions = Map::new();
+        if let Some(code) = &self.code {
+            extensions.insert("code".into(), Value::String(code.clone()));
+        }
+        if let Some(solution) = self.solution_with_steps() {
+            extensions.insert("solution".into(), Value::String(strip_ansi(&solution)));
+        }
+        if !self.explained_causes.is_empty() {
+            let causes = self.explained_causes.iter().map(|cause| cause.as_graphql_error()).collect();
+            extensions.insert("causes".into(), Value::Array(causes));
+        }
+        if !extensions.is_empty() {
+            error.insert("extensions".into(), Value::Object(extensions));
+        }
+        Value::Object(error)
+    }
+}
+
+impl ReportDisplayInfo {
+    /// This is only implemented when using the serde feature.
+    ///
+    /// Gives the errors of the report as the `errors` array of a GraphQL response, see
+    /// [SimpleErrorDisplayInfo::as_graphql_error].
+    pub fn as_graphql_errors(&self) -> Value {
+        Value::Array(self.errors.iter().map(|error| error.as_graphql_error()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use serde_json::json;
+
+    use crate::{Report, SimpleError, SimpleErrorExplanation};
+
+    #[test]
+    fn errors_are_given_in_the_shape_of_graphql_errors() {
+        let input = "a = 1\nb = ;";
+        let error = SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Missing value").solution("Give b a value").code("E001"))
+            .at(&input[10..11]).with_source(input).with_cause("Expected an expression".to_string());
+        assert_eq!(error.as_display_struct(false).as_graphql_error(), json!({
+            "message": "Missing value",
+            "locations": [{ "line": 2, "column": 5 }],
+            "extensions": { "code": "E001", "solution": "Give b a value", "causes": [{ "message": "Expected an expression" }] }
+        }));
+        let report = Report::new().with_error(SimpleError::new().error_detail("Unknown key".to_string()));
+        assert_eq!(report.as_display_struct(false).as_graphql_errors(), json!([{ "message": "Unknown key" }]));
+    }
+}
@@ -0,0 +1,217 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::formatting::join_strings;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// This is only implemented when using the html feature.
+    ///
+    /// Gives semantic HTML displaying this error, where every part of it is a `div` with its own
+    /// CSS class, so web pages can style them as they wish:
+    /// * `sde-error`: Holds the whole error, along with `sde-severity-error`, `sde-severity-warning`,
+    ///   `sde-severity-info` or `sde-severity-hint` depending on its severity.
+    /// * `sde-position`, `sde-at`, `sde-label`, `sde-code`, `sde-reason`, `sde-solution`,
+    ///   `sde-suggestion`, `sde-note` and `sde-help`: Hold each of the parts of the error, where
+    ///   suggestions that might not fix the error also have the `sde-possible-fix` class.
+    /// * `sde-doc-url`: Holds a link to the documentation of the error.
+    /// * `sde-solution-steps`: An ordered list holding the steps to solve the error.
+    /// * `sde-unexplained-causes`: Says how many causes weren't explained.
+    /// * `sde-causes`: An ordered list holding the explained causes, each of them displayed as an
+    ///   `sde-error`.
+    ///
+    /// Colors and styles given through colorization markers are turned into `span`s with classes
+    /// like `sde-fg-red`, `sde-bg-blue`, `sde-bold` or `sde-underline` instead of ANSI codes, and
+    /// texts keep their line breaks and indentation, so they are better styled using
+    /// `white-space: pre-wrap`.
+    pub fn to_html(&self) -> String {
+        let severity_class = format!("sde-severity-{}", self.severity.name().to_lowercase());
+        let mut res = format!("<div class=\"sde-error {severity_class}\">");
+        let labels = self.labels.iter().map(|label| label.as_display_string()).collect::<Vec<_>>();
+        let suggestions = self.suggestions.iter()
+            .map(|suggestion| (if suggestion.is_uncertain() { "sde-suggestion sde-possible-fix" } else { "sde-suggestion" }, suggestion.as_display_string()))
+            .collect::<Vec<_>>();
+        let unexplained_causes = Some(self.unexplained_causes)
+            .filter(|unexplained_causes| *unexplained_causes > 0)
+            .map(|unexplained_causes| format!("{unexplained_causes} unexplained cause{}",
+                                              if unexplained_causes == 1 { "" } else { "s" }));
+        [("sde-position", self.position_string().as_ref()), ("sde-at", self.at.as_ref())]
+            .into_iter()
+            .chain(labels.iter().map(|label| ("sde-label", Some(label))))
+            .chain([
+                ("sde-code", self.code.as_ref()),
+                ("sde-reason", self.reason.as_ref()),
+                ("sde-solution", self.solution.as_ref()),
+            ])
+            .filter_map(|(class, contents)| contents.map(|contents| (class, contents)))
+            .for_each(|(class, contents)| res.push_str(&format!("<div class=\"{class}\">{}</div>", ansi_to_html(contents))));
+        if !self.solution_steps.is_empty() {
+            res.push_str("<ol class=\"sde-solution-steps\">");
+            self.solution_steps.iter().for_each(|step| res.push_str(&format!("<li>{}</li>", ansi_to_html(step))));
+            res.push_str("</ol>");
+        }
+        suggestions.iter().map(|(class, suggestion)| (*class, suggestion))
+            .chain(self.notes.iter().map(|note| ("sde-note", note)))
+            .chain(self.helps.iter().map(|help| ("sde-help", help)))
+            .for_each(|(class, contents)| res.push_str(&format!("<div class=\"{class}\">{}</div>", ansi_to_html(contents))));
+        if let Some(doc_url) = &self.doc_url {
+            let doc_url = ansi_to_html(doc_url);
+            res.push_str(&format!("<div class=\"sde-doc-url\"><a href=\"{doc_url}\">{doc_url}</a></div>"));
+        }
+        if let Some(unexplained_causes) = &unexplained_causes {
+            res.push_str(&format!("<div class=\"sde-unexplained-causes\">{}</div>", ansi_to_html(unexplained_causes)));
+        }
+        if !self.explained_causes.is_empty() {
+            res.push_str("<ol class=\"sde-causes\">");
+            self.explained_causes.iter().for_each(|cause| res.push_str(&format!("<li>{}</li>", cause.to_html())));
+            res.push_str("</ol>");
+        }
+        res.push_str("</div>");
+        res
+    }
+}
+
+/// Styles given by ANSI Select Graphic Rendition codes.
+#[derive(Default, Clone, PartialEq)]
+struct AnsiStyle {
+    foreground: Option<&'static str>,
+    background: Option<&'static str>,
+    bold: bool,
+    dimmed: bool,
+    italic: bool,
+    underline: bool,
+    reversed: bool,
+    strikethrough: bool,
+}
+
+const COLORS: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+const BRIGHT_COLORS: [&str; 8] = ["bright-black", "bright-red", "bright-green", "bright-yellow", "bright-blue", "bright-magenta", "bright-cyan", "bright-white"];
+
+impl AnsiStyle {
+    fn apply(&mut self, codes: &str) {
+        let mut codes = codes.split(';').map(|code| code.parse::<u8>().unwrap_or(0));
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = AnsiStyle::default(),
+                1 => self.bold = true,
+                2 => self.dimmed = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.reversed = true,
+                9 => self.strikethrough = true,
+                22 => { self.bold = false; self.dimmed = false; }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reversed = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.foreground = Some(COLORS[(code - 30) as usize]),
+                39 => self.foreground = None,
+                40..=47 => self.background = Some(COLORS[(code - 40) as usize]),
+                49 => self.background = None,
+                90..=97 => self.foreground = Some(BRIGHT_COLORS[(code - 90) as usize]),
+                100..=107 => self.background = Some(BRIGHT_COLORS[(code - 100) as usize]),
+                // Extended colors (38;5;n or 38;2;r;g;b) have no class, so they are skipped.
+                38 | 48 => match codes.next() {
+                    Some(5) => { codes.next(); }
+                    Some(2) => { codes.by_ref().take(3).for_each(drop); }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn classes(&self) -> String {
+        let foreground = self.foreground.map(|color| format!("sde-fg-{color}"));
+        let background = self.background.map(|color| format!("sde-bg-{color}"));
+        let flags = [
+            (self.bold, "sde-bold"), (self.dimmed, "sde-dimmed"), (self.italic, "sde-italic"),
+            (self.underline, "sde-underline"), (self.reversed, "sde-reversed"), (self.strikethrough, "sde-strikethrough"),
+        ].into_iter().filter(|(is_set, _)| *is_set).map(|(_, class)| String::from(class));
+        join_strings(" ", foreground.into_iter().chain(background).chain(flags))
+    }
+}
+
+/// Escapes *text* so it can be placed inside HTML, turning its ANSI styles into `span`s with
+/// classes and removing any other escape sequence.
+fn ansi_to_html(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut style = AnsiStyle::default();
+    let mut is_span_open = false;
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\u{1b}' => match chars.next() {
+                Some('[') => {
+                    let mut parameters = String::new();
+                    let mut final_byte = None;
+                    for char in chars.by_ref() {
+                        if ('@'..='~').contains(&char) {
+                            final_byte = Some(char);
+                            break;
+                        }
+                        parameters.push(char);
+                    }
+                    if final_byte != Some('m') {
+                        continue;
+                    }
+                    let mut new_style = style.clone();
+                    new_style.apply(&parameters);
+                    if new_style == style {
+                        continue;
+                    }
+                    style = new_style;
+                    if is_span_open {
+                        res.push_str("</span>");
+                    }
+                    let classes = style.classes();
+                    is_span_open = !classes.is_empty();
+                    if is_span_open {
+                        res.push_str(&format!("<span class=\"{classes}\">"));
+                    }
+                }
+                Some(']') => {
+                    while let Some(char) = chars.next() {
+                        if char == '\u{7}' { break; }
+                        if char == '\u{1b}' && chars.next_if_eq(&'\\').is_some() { break; }
+                    }
+                }
+                _ => {}
+            },
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            char => res.push(char),
+        }
+    }
+    if is_span_open {
+        res.push_str("</span>");
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SimpleError, SimpleErrorExplanation};
+
+    #[test]
+    fn errors_become_escaped_html_with_styled_spans() {
+        let explanation = SimpleErrorExplanation::new().explanation("Unexpected <b> \u{1b}[1;31mtag\u{1b}[0m found").solution("Escape it as &lt;");
+        let display = SimpleError::from(explanation).with_cause(String::from("Invalid markup")).with_cause(SimpleError::new()).as_display_struct(true);
+        assert_eq!(display.to_html(), concat!(
+            "<div class=\"sde-error sde-severity-error\"><div class=\"sde-reason\">Unexpected &lt;b&gt; <span class=\"sde-fg-red sde-bold\">tag</span> found</div>",
+            "<div class=\"sde-solution\">Escape it as &amp;lt;</div><div class=\"sde-unexplained-causes\">1 unexplained cause</div>",
+            "<ol class=\"sde-causes\"><li><div class=\"sde-error sde-severity-error\"><div class=\"sde-reason\">Invalid markup</div></div></li></ol></div>"));
+    }
+
+    #[test]
+    fn doc_urls_become_escaped_links() {
+        let display = SimpleError::new().error_detail(SimpleErrorExplanation::new().doc_url("https://example.com?code=E1&lang=en")).as_display_struct(false);
+        assert!(display.to_html().contains(
+            "<div class=\"sde-doc-url\"><a href=\"https://example.com?code=E1&amp;lang=en\">https://example.com?code=E1&amp;lang=en</a></div>"));
+    }
+}
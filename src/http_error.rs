@@ -0,0 +1,200 @@
+use std::fmt::{Display, Formatter};
+use std::string::{String, ToString};
+
+use serde_json::{Map, Value};
+
+use crate::formatting::strip_ansi;
+use crate::severity::Severity;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+/// This is only implemented when using the axum or actix features.
+///
+/// Error answered to an HTTP request, holding the error along with the status code of the response,
+/// 400 (Bad Request) by default, so handlers of web frameworks can return the errors of parsers
+/// straight away, like `let ast = parse(&body)?;` on a handler returning `Result<_, HttpError>`.
+///
+/// The response body is the error serialized as JSON (See [SimpleErrorDisplayInfo]), or, when
+/// [HttpError::problem_json] is set, a `application/problem+json` object as in RFC 9457, whose
+/// `type` is the documentation link of the error, and holding the error as its `error` member.
+///
+/// It holds the error already turned into a [SimpleErrorDisplayInfo] without colors, so it doesn't
+/// borrow the input the error might point into and it can be sent across threads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpError {
+    error: SimpleErrorDisplayInfo,
+    status: u16,
+    problem_json: bool,
+}
+
+impl HttpError {
+    /// Creates an error answered with a 400 (Bad Request) status code.
+    pub fn new<'input, PError: Into<SimpleError<'input>>>(error: PError) -> Self {
+        Self { error: error.into().as_display_struct(false), status: 400, problem_json: false }
+    }
+
+    /// Sets the status code of the response, either as a number or as the `StatusCode` of the
+    /// `http` crate.
+    pub fn with_status<Status: Into<u16>>(mut self, status: Status) -> Self {
+        self.status = status.into();
+        self
+    }
+
+    /// Sets the status code of the response as the one given by *status_of* for the error, such
+    /// as giving a status code for every [crate::Severity] or for every
+    /// [SimpleErrorDisplayInfo::code].
+    pub fn with_status_from<StatusOf: FnOnce(&SimpleErrorDisplayInfo) -> u16>(mut self, status_of: StatusOf) -> Self {
+        self.status = status_of(&self.error);
+        self
+    }
+
+    /// Sets the status code of the response when the error has the code *code*, see
+    /// [SimpleErrorDisplayInfo::code], like `.with_status_for_code("E404", 404)`.
+    pub fn with_status_for_code<Status: Into<u16>>(mut self, code: &str, status: Status) -> Self {
+        if self.error.code() == Some(code) {
+            self.status = status.into();
+        }
+        self
+    }
+
+    /// Sets the status code of the response when the error has the severity *severity*, see
+    /// [SimpleErrorDisplayInfo::severity].
+    pub fn with_status_for_severity<Status: Into<u16>>(mut self, severity: Severity, status: Status) -> Self {
+        if self.error.severity() == severity {
+            self.status = status.into();
+        }
+        self
+    }
+
+    /// Indicates whether the body of the response is a `application/problem+json` object rather
+    /// than the error serialized as JSON.
+    pub fn problem_json(mut self, problem_json: bool) -> Self {
+        self.problem_json = problem_json;
+        self
+    }
+
+    /// Returns the error answered to the request.
+    pub fn error(&self) -> &SimpleErrorDisplayInfo {
+        &self.error
+    }
+
+    /// Returns the status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the content type of the body of the response.
+    pub fn content_type(&self) -> &'static str {
+        if self.problem_json { "application/problem+json" } else { "application/json" }
+    }
+
+    /// Returns the body of the response, see [HttpError].
+    pub fn body(&self) -> String {
+        let error = serde_json::to_value(&self.error).unwrap_or(Value::Null);
+        if !self.problem_json {
+            return error.to_string();
+        }
+        let mut problem = Map::new();
+        problem.insert("type".into(), Value::String(self.error.doc_url.clone().unwrap_or_else(|| "about:blank".to_string())));
+        problem.insert("title".into(), Value::String(self.error.reason.as_deref().map(strip_ansi).unwrap_or_else(|| "Unexplained error".to_string())));
+        problem.insert("status".into(), Value::from(self.status));
+        problem.insert("detail".into(), Value::String(self.error.summary()));
+        problem.insert("error".into(), error);
+        Value::Object(problem).to_string()
+    }
+}
+
+/// Displays the error without colors, see [SimpleErrorDisplayInfo::as_display_string].
+impl Display for HttpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.error.as_display_string())
+    }
+}
+
+impl<'input, PError: Into<SimpleError<'input>>> From<PError> for HttpError {
+    fn from(error: PError) -> Self {
+        Self::new(error)
+    }
+}
+
+/// Answers the request with the status code and body of this error, where unknown status codes
+/// are answered as 500 (Internal Server Error).
+#[cfg(feature = "axum")]
+impl axum_core::response::IntoResponse for HttpError {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = http::StatusCode::from_u16(self.status).unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+        (status, [(http::header::CONTENT_TYPE, self.content_type())], self.body()).into_response()
+    }
+}
+
+/// Answers the request with the status code and body of this error, where unknown status codes
+/// are answered as 500 (Internal Server Error).
+#[cfg(feature = "actix")]
+impl actix_web::ResponseError for HttpError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::from_u16(self.status).unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        actix_web::HttpResponse::build(self.status_code()).content_type(self.content_type()).body(self.body())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleErrorExplanation;
+
+    #[test]
+    fn errors_are_answered_as_json_or_as_problem_details() {
+        let error = HttpError::from(SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Missing value").code("E001")));
+        assert_eq!(error.status(), 400);
+        assert_eq!(error.content_type(), "application/json");
+        let body: Value = serde_json::from_str(&error.body()).unwrap();
+        assert_eq!(body["reason"], "Missing value");
+
+        let error = error.with_status(422u16).problem_json(true);
+        assert_eq!(error.content_type(), "application/problem+json");
+        let body: Value = serde_json::from_str(&error.body()).unwrap();
+        assert_eq!(body["type"], "about:blank");
+        assert_eq!(body["title"], "Missing value");
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["error"]["code"], "E001");
+    }
+
+    #[test]
+    fn handlers_return_errors_through_the_question_mark_operator() {
+        #[allow(clippy::result_large_err)]
+        fn handler() -> Result<(), HttpError> {
+            Err("Unknown key".to_string())?;
+            Ok(())
+        }
+        let error = handler().unwrap_err().with_status_from(|error| if error.code().is_some() { 409 } else { 404 });
+        assert_eq!(error.status(), 404);
+        assert_eq!(error.error().as_display_string(), "Error: Unknown key");
+    }
+
+    #[test]
+    #[cfg(feature = "axum")]
+    fn axum_responses_carry_the_status_and_content_type() {
+        use axum_core::response::IntoResponse;
+
+        let response = HttpError::from("Missing value".to_string()).with_status(http::StatusCode::UNPROCESSABLE_ENTITY).problem_json(true).into_response();
+        assert_eq!(response.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.headers()[http::header::CONTENT_TYPE], "application/problem+json");
+        assert_eq!(HttpError::from("Missing value".to_string()).with_status(1000u16).into_response().status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    #[cfg(feature = "actix")]
+    fn actix_responses_carry_the_status_chosen_for_the_error() {
+        use actix_web::ResponseError;
+
+        let error = HttpError::from(SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Unknown file").code("E404")))
+            .with_status_for_code("E404", 404u16).with_status_for_severity(Severity::Warning, 200u16);
+        assert_eq!(error.status_code(), actix_web::http::StatusCode::NOT_FOUND);
+        let response = error.error_response();
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Display;
+
+use lalrpop_util::ParseError;
+
+use crate::formatting::join_strings;
+use crate::simple_error::SimpleError;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+use crate::span::Span;
+
+/// This is only implemented when using the lalrpop-util feature.
+///
+/// Creates a SimpleError from a parse error given by a lalrpop parser whose locations are byte
+/// offsets, as with lalrpop's built-in lexer, where:
+/// * Unrecognized and extra tokens are explained as unexpected tokens, happening at the bytes of
+///   said token, where the tokens the parser expected are given as the solution.
+/// * Unrecognized ends of input and invalid tokens happen at the location they were found at, see
+///   [SimpleError::at_span].
+/// * User errors are turned into SimpleErrors through their own conversion.
+///
+/// Locations are kept as spans, so giving the input through [SimpleError::with_source] also gives
+/// the lines and columns of the error.
+impl<'input, Token: Display, Error: Into<SimpleError<'input>>> From<ParseError<usize, Token, Error>> for SimpleError<'input> {
+    fn from(value: ParseError<usize, Token, Error>) -> Self {
+        match value {
+            ParseError::InvalidToken { location } => {
+                SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Invalid token"))
+                    .at_span(Span::new(location, location))
+            }
+            ParseError::UnrecognizedEof { location, expected } => {
+                SimpleError::new().error_detail(expecting(SimpleErrorExplanation::new().explanation("Unexpected end of input"), &expected))
+                    .at_span(Span::new(location, location))
+            }
+            ParseError::UnrecognizedToken { token: (start, token, end), expected } => {
+                SimpleError::new().error_detail(expecting(SimpleErrorExplanation::new().explanation(format!("Unexpected token {token}")), &expected))
+                    .at_span(Span::new(start, end))
+            }
+            ParseError::ExtraToken { token: (start, token, end) } => {
+                SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation(format!("Unexpected extra token {token}"))
+                    .solution("Remove it"))
+                    .at_span(Span::new(start, end))
+            }
+            ParseError::User { error } => error.into(),
+        }
+    }
+}
+
+/// Gives the tokens a parser expected as the solution of *explanation*, if there are any.
+fn expecting<'input>(explanation: SimpleErrorExplanation<'input>, expected: &[String]) -> SimpleErrorExplanation<'input> {
+    match expected {
+        [] => explanation,
+        [expected] => explanation.solution(format!("Expected {expected}")),
+        expected => explanation.solution(format!("Expected one of {}", join_strings(", ", expected))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn parse_errors_explain_the_tokens_that_were_expected() {
+        let input = "1 + + 2";
+        let unrecognized: ParseError<usize, &str, String> = ParseError::UnrecognizedToken { token: (4, "+", 5), expected: vec!["number".to_string(), "\"(\"".to_string()] };
+        let error = SimpleError::from(unrecognized).with_source(input);
+        assert_eq!(error.as_display_struct(false).as_display_string(),
+                   "Position: On line 1 and column 5 up to line 1 and column 5\nAt: +\nError: Unexpected token +\nSolution: Expected one of number, \"(\"");
+        let eof: ParseError<usize, &str, String> = ParseError::UnrecognizedEof { location: 7, expected: vec!["number".to_string()] };
+        assert_eq!(SimpleError::from(eof).summary(), "Unexpected end of input");
+        let user: ParseError<usize, &str, String> = ParseError::User { error: "Number too big".to_string() };
+        assert_eq!(SimpleError::from(user).summary(), "Number too big");
+    }
+}
@@ -111,22 +111,22 @@
 //! function errors:
 //!
 //! - Creating the <i>missing variable error</i>: This is simply constructing a
-//! `CompilationError::MissingVariable` variant, where the variable name is just a reference to where
-//! it says 'missing_variable' in the original input, and since this is a parsing error, we can also
-//! use the [SimpleError::at] to indicate a bigger string where the error is happening, we will use
-//! it to reference 'if missing_variable > 0'.<br><br>
-//! ...Wait, we are using the `at` function, but that's implemented for [SimpleError], why is it
-//! working then? Well, the trait [SimpleErrorDetail] implements plenty of functions of
-//! [SimpleError], this is so you can use your struct (In this case `CompilationError`) as you were
-//! using a value of type [SimpleError].<br><br>
+//!   `CompilationError::MissingVariable` variant, where the variable name is just a reference to where
+//!   it says 'missing_variable' in the original input, and since this is a parsing error, we can also
+//!   use the [SimpleError::at] to indicate a bigger string where the error is happening, we will use
+//!   it to reference 'if missing_variable > 0'.<br><br>
+//!   ...Wait, we are using the `at` function, but that's implemented for [SimpleError], why is it
+//!   working then? Well, the trait [SimpleErrorDetail] implements plenty of functions of
+//!   [SimpleError], this is so you can use your struct (In this case `CompilationError`) as you were
+//!   using a value of type [SimpleError].<br><br>
 //! - Creating the <i>missing function error</i>: This isn't very different from our previous case,
-//! we just constructing a `CompilationError::MissingFunction`, as for the function `at`, this time
-//! we will reference 'return missing_function(missing_variable);'.<br><br>
+//!   we just constructing a `CompilationError::MissingFunction`, as for the function `at`, this time
+//!   we will reference 'return missing_function(missing_variable);'.<br><br>
 //! - Creating the <i>Error root</i>: This is something you'll perhaps never do in a real project,
-//! but we are going to use a base error, in this case  `CompilationError::RootCompilationError`
-//! where we will stack the errors using the [SimpleError::with_cause] function, stacking the
-//! missing variable and missing function errors, although this is just for showing it's
-//! functionality, you should just stack real causes.
+//!   but we are going to use a base error, in this case  `CompilationError::RootCompilationError`
+//!   where we will stack the errors using the [SimpleError::with_cause] function, stacking the
+//!   missing variable and missing function errors, although this is just for showing it's
+//!   functionality, you should just stack real causes.
 //!
 //! Once done, we can just can print our [SimpleError] and it will result in the error stack shown
 //! earlier.
@@ -196,41 +196,41 @@
 //! times while they still all the relevant information.
 //!
 //! - Colorizing explanations and solutions: With the [colored] crate you can colorize the
-//! explanations and solutions, for example, in `Variable {variable_name} doesn't exist` it would
-//! be great if we could turn the variable name into a bold and red name, taking the user's
-//! attention directly to it, like 'Variable <span style="color:red">**missing variable**</span>
-//! doesn't exist'.<br><br>
-//! For this, we can just replace
-//! `.explanation(format!("Variable {variable_name} doesn't exists."))` for
-//! `.explanation(format!("Variable {} doesn't exists.", variable_name.red().bold()))`.<br><br>
+//!   explanations and solutions, for example, in `Variable {variable_name} doesn't exist` it would
+//!   be great if we could turn the variable name into a bold and red name, taking the user's
+//!   attention directly to it, like 'Variable <span style="color:red">**missing variable**</span>
+//!   doesn't exist'.<br><br>
+//!   For this, we can just replace
+//!   `.explanation(format!("Variable {variable_name} doesn't exists."))` for
+//!   `.explanation(format!("Variable {} doesn't exists.", variable_name.red().bold()))`.<br><br>
 //! - Colorizing the input: The [string_colorization::colorize] takes an &str and then applies some
-//! colors and stylizations to other &str of it that we tell, this means that if we had a &str that
-//! is part of the &str taken as input, then we could colorize it!<br>
-//! For example, in out missing variable error, we used [SimpleError::at], where the substring
-//! referenced is 'if missing_variable > 0', thing is, our missing variable variant has another
-//! substring whose value is 'missing_variable', this means that if we used
-//! [SimpleErrorExplanation::colorization_marker] like this `SimpleErrorExplanation::new()
-//! .colorization_marker(variable_name, foreground::Red + style::Bold)`, then 'if missing_variable >
-//! 0' would look like 'if <span style="color:red">**missing_variable**</span> > 0'.<br><br>
-//! The down-side of this is that your struct or enum must have references to the original source,
-//! although this is often the case for many situations, like parsing values as in this situation.
-//! <br><br>
-//! We can also use [SimpleErrorExplanation::whole_input_colorization] to colorize everything
-//! written inside `at`, for example, `SimpleErrorExplanation::new()
-//! .complete_input_colorization(foreground::Blue + style::Italic + style::Dimmed)` will make it so
-//! `if missing_variable > 0` looks blue, italic and dimmed, telling the user it is not important.
-//! <br><br>But... now 'if missing_variable > 0' looks like <i style="color:lightblue;opacity:.7;">
-//! if **<span style="color: red;">missing_variable </span>** > 0</i>, not like
-//! <i style="color:lightblue;opacity:.7;">if </i>**<span style="color: red;">missing_variable
-//! </span>** <i style="color:lightblue;opacity:.7;">> 0</i>, why is 'missing_variable' also dimmed?
-//! This is because the complete_input_colorization set it to dim, while the colorization_marker
-//! didn't override this, to avoid this, we can use [string_colorization::style::Clear] to remove
-//! all the stylization from complete_input_colorization, this makes it so
-//! `SimpleErrorExplanation::new() .colorization_marker(variable_name, style::Clear +
-//! foreground::Red + style::Bold) .complete_input_colorization(foreground::Blue + style::Italic +
-//! style::Dimmed)` while turn 'if missing_variable > 0' into the desired
-//! <i style="color:lightblue;opacity:.7;">if </i>**<span style="color: red;">missing_variable
-//! </span>** <i style="color:lightblue;opacity:.7;">> 0</i>.
+//!   colors and stylizations to other &str of it that we tell, this means that if we had a &str that
+//!   is part of the &str taken as input, then we could colorize it!<br>
+//!   For example, in out missing variable error, we used [SimpleError::at], where the substring
+//!   referenced is 'if missing_variable > 0', thing is, our missing variable variant has another
+//!   substring whose value is 'missing_variable', this means that if we used
+//!   [SimpleErrorExplanation::colorization_marker] like this `SimpleErrorExplanation::new()
+//!   .colorization_marker(variable_name, foreground::Red + style::Bold)`, then 'if missing_variable >
+//!   0' would look like 'if <span style="color:red">**missing_variable**</span> > 0'.<br><br>
+//!   The down-side of this is that your struct or enum must have references to the original source,
+//!   although this is often the case for many situations, like parsing values as in this situation.
+//!   <br><br>
+//!   We can also use [SimpleErrorExplanation::whole_input_colorization] to colorize everything
+//!   written inside `at`, for example, `SimpleErrorExplanation::new()
+//!   .complete_input_colorization(foreground::Blue + style::Italic + style::Dimmed)` will make it so
+//!   `if missing_variable > 0` looks blue, italic and dimmed, telling the user it is not important.
+//!   <br><br>But... now 'if missing_variable > 0' looks like <i style="color:lightblue;opacity:.7;">
+//!   if **<span style="color: red;">missing_variable </span>** > 0</i>, not like
+//!   <i style="color:lightblue;opacity:.7;">if </i>**<span style="color: red;">missing_variable
+//!   </span>** <i style="color:lightblue;opacity:.7;">> 0</i>, why is 'missing_variable' also dimmed?
+//!   This is because the complete_input_colorization set it to dim, while the colorization_marker
+//!   didn't override this, to avoid this, we can use [string_colorization::style::Clear] to remove
+//!   all the stylization from complete_input_colorization, this makes it so
+//!   `SimpleErrorExplanation::new() .colorization_marker(variable_name, style::Clear +
+//!   foreground::Red + style::Bold) .complete_input_colorization(foreground::Blue + style::Italic +
+//!   style::Dimmed)` while turn 'if missing_variable > 0' into the desired
+//!   <i style="color:lightblue;opacity:.7;">if </i>**<span style="color: red;">missing_variable
+//!   </span>** <i style="color:lightblue;opacity:.7;">> 0</i>.
 //!
 //! ``` rust
 //! use colored::Colorize;
@@ -276,9 +276,10 @@
 //! use string_colorization::{foreground, style};
 //! use simple_detailed_error::{SimpleErrorExplanation, SimpleErrorDetail};
 //!
-//! colored::control::set_override(true); // This forces the colorization to be applied, this should
-//!                                       // not appear in your code, is written here to force it
-//!                                       // for testing purposes to show you this code is correct.
+//! colored::control::set_override(true); // This forces the colorization to be applied even when
+//!                                       // stderr isn't a terminal, this should not appear in your
+//!                                       // code, is written here to force it for testing purposes
+//!                                       // to show you this code is correct.
 //!
 //! let code_to_compile = "if missing_variable > 0 { return missing_function(missing_variable); }";
 //! let missing_variable_error = CompilationError::MissingVariable {variable_name: &code_to_compile[3..19] }
@@ -323,25 +324,146 @@
 //!
 //! # Features
 //!
-//! - ``std``: Implements the Error trait for SimpleError, it might also be used for future
-//! implementations that might require targeting std.
+//! - ``std``: Implements the Error trait for SimpleError, allows overriding colorization per
+//!   thread through [with_color_override] and writing errors into any [std::io::Write] through
+//!   [SimpleError::write_report], it might also be used for future implementations that might require
+//!   targeting std.
 //! - ``colorization``: Allows the colorization markers functions to be used on SimpleErrorExplanation,
-//! helping you to create beautiful colored error message to direct your user's attention.
+//!   helping you to create beautiful colored error message to direct your user's attention.
+//!   When disabled, these functions are still available but do nothing, so your implementations of
+//!   [SimpleErrorDetail] don't need to be gated behind this feature. The functions at [markers]
+//!   give colorizers by their meaning, like [markers::error_span], which resolve through the active
+//!   theme (See `with_theme`), so applications decide the actual styles.
+//! - ``colorization-anstyle``: Alternative to ``colorization`` where markers are
+//!   `anstyle::Style`s rather than colorizers (See [markers::Marker]), keeping the same functions, and
+//!   errors can be written through anstream with [SimpleError::write_to_stream], which keeps or
+//!   removes colors depending on each stream rather than on colored's process-global state. When
+//!   both features are enabled, markers keep being colorizers, it also enables ``std``.
 //! - ``serde``: Implements Serialize and Deserialize on SimpleErrorDisplayInfo, this is useful for
-//! storing logs of errors, especially for auditing.
+//!   storing logs of errors, especially for auditing, it also allows giving errors in the shape of
+//!   GraphQL responses through [SimpleErrorDisplayInfo::as_graphql_error], and attaching raw data
+//!   to errors through [SimpleErrorExplanation::data].
+//! - ``html``: Allows rendering errors as HTML through [SimpleErrorDisplayInfo::to_html], where
+//!   colors and styles are turned into CSS classes instead of ANSI codes.
+//! - ``codespan``: Allows converting SimpleError from and into codespan-reporting's Diagnostic
+//!   through [SimpleError::to_codespan_diagnostic] and [SimpleError::from_codespan_diagnostic],
+//!   helping projects migrate incrementally in either direction, it also enables ``std``.
+//! - ``miette``: Allows turning SimpleError into an error implementing miette's Diagnostic through
+//!   [SimpleError::to_miette_diagnostic], so applications already using miette's reporters can
+//!   report errors from this crate, it also enables ``std``.
+//! - ``ariadne``: Allows turning SimpleError into an ariadne Report through
+//!   [SimpleError::to_ariadne_report], giving errors ariadne's multi-line caret rendering, it also
+//!   enables ``std``.
+//! - ``lalrpop-util``: Allows converting the `ParseError`s given by lalrpop parsers into
+//!   SimpleError, explaining unexpected tokens along with the tokens that were expected.
+//! - ``axum``: Implements axum's `IntoResponse` for [HttpError], answering requests with errors
+//!   serialized as JSON, so handlers can return parser errors through the `?` operator, it also
+//!   enables ``std`` and ``serde``.
+//! - ``actix``: Implements actix-web's `ResponseError` for [HttpError], the same way as ``axum``
+//!   does for axum, it also enables ``std`` and ``serde``.
+//! - ``wasm``: Allows converting SimpleErrorDisplayInfo into structured `JsValue`s through
+//!   [SimpleErrorDisplayInfo::to_js_value] and into JavaScript errors through
+//!   [SimpleErrorDisplayInfo::to_js_error], so browser applications can show the same detailed
+//!   errors command line applications do, it also enables ``std`` and ``serde``.
+//! - ``ffi``: Allows flattening error trees into arrays of C structs through
+//!   [SimpleError::to_error_records], which are released through [ffi::sde_error_records_free], so
+//!   applications embedding Rust code through C can display detailed errors.
+//! - ``sync``: Requires every [SimpleErrorDetail] to be Send and Sync (See [MaybeSendSync]), making
+//!   SimpleError Send and Sync, so errors found on other threads, like on rayon or tokio pipelines,
+//!   can be sent back and aggregated.
+//! - ``full``: Enables every feature above.
 //!
 //! Currently, the ``std`` and ``colorization`` are enabled by default.
 
 extern crate alloc;
 
+pub use color_choice::ColorChoice;
+#[cfg(feature = "std")]
+pub use color_override::with_color_override;
+pub use diagnostics_index::DiagnosticsIndex;
+pub use display_options::{CauseOrdering, DisplayMode, DisplayOptions, TabRendering};
+pub use display_template::DisplayTemplate;
+pub use error_accumulator::ErrorAccumulator;
+#[cfg(feature = "std")]
+pub use final_report::FinalReport;
+pub use fingerprint::Fingerprinted;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub use http_error::HttpError;
+pub use localization::Translations;
+#[cfg(feature = "miette")]
+pub use miette_diagnostic::MietteAdapter;
+pub use report::Report;
+pub use result_ext::{OptionExt, ResultExt, ResultIteratorExt};
+pub use severity::{set_min_display_severity, Severity};
 pub use simple_error::SimpleError;
-pub use simple_error_detail::SimpleErrorDetail;
+pub use simple_error_detail::{DetailFromDisplay, MaybeSendSync, SimpleErrorDetail};
 pub use simple_error_display_info::SimpleErrorDisplayInfo;
 pub use simple_error_explanation::SimpleErrorExplanation;
+pub use source_map::SourceMap;
+pub use span::{Location, SourceId, Span};
+pub use stats::ErrorStats;
+pub use suggestion::{Applicability, SuggestionConflict};
+pub use timestamp::Timestamp;
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+pub use theme::Theme;
+#[cfg(all(feature = "std", any(feature = "colorization", feature = "colorization-anstyle")))]
+pub use theme::with_theme;
 
+#[cfg(feature = "colorization-anstyle")]
+mod anstyle_colorization;
+#[cfg(feature = "ariadne")]
+mod ariadne_report;
+mod checkstyle;
+pub mod color_choice;
+pub mod color_override;
+#[cfg(feature = "codespan")]
+mod codespan;
+pub mod compat;
+pub mod diagnostics_index;
+pub mod display_options;
+pub mod display_template;
+pub mod dual_render;
+pub mod error_accumulator;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod final_report;
+pub mod fingerprint;
+pub mod fmt_util;
+mod gcc_format;
+#[cfg(feature = "serde")]
+mod graphql;
+#[cfg(feature = "lalrpop-util")]
+mod lalrpop;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod http_error;
+pub mod localization;
+pub mod markers;
+#[cfg(feature = "miette")]
+pub mod miette_diagnostic;
+pub mod report;
+pub mod result_ext;
+pub mod severity;
 pub mod simple_error;
 pub mod simple_error_detail;
 pub mod simple_error_display_info;
 pub mod simple_error_explanation;
+pub mod source_map;
+pub mod span;
+pub mod stats;
+pub mod suggestion;
+mod tap;
+pub mod timestamp;
+pub mod tree_iter;
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+pub mod theme;
+#[cfg(feature = "std")]
+pub mod ui_test;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "std")]
+mod write_report;
 
 pub(crate) mod formatting;
\ No newline at end of file
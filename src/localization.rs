@@ -0,0 +1,422 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::display_options::DisplayOptions;
+
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+use crate::simple_error_detail::SimpleErrorDetail;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+
+/// Holds translations for the explanations, solutions, notes, helps and labels of errors, and for
+/// the labels of [DisplayOptions], in the same way gettext does.
+///
+/// Texts of errors having a [code](crate::SimpleErrorExplanation::code) are keyed by said code,
+/// given as the `msgctxt`, and by which text of the error they are, like `reason` or `note 1`, given
+/// as the `msgid`, so the values interpolated into them don't create a new text to translate for
+/// every error, their translations can then refer to the
+/// [metadata](crate::SimpleError::with_metadata) of the error by its key between braces, like in
+/// `No se encontró la variable {name}`. Texts of errors without a code are keyed by the text itself.
+///
+/// They can be extracted from an error through [Translations::extract], exported as a gettext PO
+/// file through [Translations::to_po_string] so they can be translated with the usual tooling,
+/// loaded back through [Translations::from_po_str], and applied to errors through
+/// [SimpleErrorDisplayInfo::translated] and to display options through
+/// [DisplayOptions::translated].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Translations {
+    entries: BTreeMap<(Option<String>, String), Entry>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Entry {
+    /// Translated text, empty when it isn't translated yet.
+    translated: String,
+    /// Text the entry was extracted from when it is keyed by the code of an error, shown to
+    /// translators as a comment.
+    original: Option<String>,
+}
+
+impl Translations {
+    /// Context the labels of [DisplayOptions] are keyed under, where their `msgid` is their
+    /// default text.
+    pub const DISPLAY_OPTIONS_CONTEXT: &'static str = "DisplayOptions";
+
+    /// Creates an empty set of translations.
+    pub fn new() -> Self {
+        Self { entries: BTreeMap::new() }
+    }
+
+    /// Collects every explanation, solution, note, help and label of this error and its causes, all of
+    /// them without a translation yet.
+    ///
+    /// The display should be created without colors, otherwise, the escape codes would become part
+    /// of the texts to translate.
+    pub fn extract(display: &SimpleErrorDisplayInfo) -> Self {
+        let mut res = Self::new();
+        res.extend_from(display);
+        res
+    }
+
+    /// Collects every explanation, solution, note, help and label of this error and its causes not
+    /// present yet in these translations.
+    pub fn extend_from(&mut self, display: &SimpleErrorDisplayInfo) {
+        texts_of(display).for_each(|(key, text)| {
+            let (context, original) = match &display.code {
+                Some(code) => (Some(code.clone()), Some(text.clone())),
+                None => (None, None),
+            };
+            self.entries.entry((context, key.unwrap_or_else(|| text.clone()))).or_insert(Entry { translated: String::new(), original });
+        });
+        display.explained_causes.iter().for_each(|cause| self.extend_from(cause));
+    }
+
+    /// Collects every label of these display options not present yet in these translations, keyed
+    /// under [Translations::DISPLAY_OPTIONS_CONTEXT].
+    pub fn extend_from_options(&mut self, options: &DisplayOptions) {
+        labels_of(&mut options.clone()).into_iter().for_each(|label| {
+            self.entries.entry((Some(Self::DISPLAY_OPTIONS_CONTEXT.to_string()), label.clone())).or_default();
+        });
+    }
+
+    /// Sets the translation for the given original text.
+    pub fn insert<Original: Into<String>, Translated: Into<String>>(&mut self, original: Original, translated: Translated) {
+        self.entries.insert((None, original.into()), Entry { translated: translated.into(), original: None });
+    }
+
+    /// Sets the translation for the given message id under the given context, such as the code of
+    /// an error along with `reason`, `solution`, `solution step 1`, `note 1`, `help 1` or `label 1`.
+    pub fn insert_in_context<Context: Into<String>, Id: Into<String>, Translated: Into<String>>(&mut self, context: Context, id: Id, translated: Translated) {
+        self.entries.insert((Some(context.into()), id.into()), Entry { translated: translated.into(), original: None });
+    }
+
+    /// Returns the translation for the given text, or the text itself when it isn't translated.
+    pub fn translate<'text>(&'text self, text: &'text str) -> &'text str {
+        self.lookup(None, text).unwrap_or(text)
+    }
+
+    /// Returns the translation for the given message id under the given context, if it's
+    /// translated.
+    pub fn translate_in_context(&self, context: &str, id: &str) -> Option<&str> {
+        self.lookup(Some(context), id)
+    }
+
+    fn lookup(&self, context: Option<&str>, id: &str) -> Option<&str> {
+        self.entries.get(&(context.map(ToString::to_string), id.to_string()))
+            .map(|entry| entry.translated.as_str())
+            .filter(|translated| !translated.is_empty())
+    }
+
+    /// Exports these translations as a gettext PO file, where every original text is a `msgid` and
+    /// its translation is the `msgstr`, texts keyed by the code of an error have said code as their
+    /// `msgctxt` and the text they were extracted from as a comment.
+    pub fn to_po_string(&self) -> String {
+        let mut res = String::new();
+        self.entries.iter().for_each(|((context, id), entry)| {
+            if let Some(original) = &entry.original {
+                original.lines().for_each(|line| res.push_str(&format!("#. {line}\n")));
+            }
+            if let Some(context) = context {
+                res.push_str(&format!("msgctxt \"{}\"\n", escape_po(context)));
+            }
+            res.push_str(&format!("msgid \"{}\"\nmsgstr \"{}\"\n\n", escape_po(id), escape_po(&entry.translated)));
+        });
+        res
+    }
+
+    /// Loads translations from a gettext PO file, such as one previously exported through
+    /// [Translations::to_po_string], comments and the PO header are ignored, and for plural
+    /// entries, only the first form of the translation, `msgstr[0]`, is kept.
+    pub fn from_po_str(po: &str) -> Result<Self, TranslationsParseError> {
+        let mut res = Self::new();
+        let mut pending = PendingEntry::default();
+        for (line_no, line) in po.lines().enumerate().map(|(line_no, line)| (line_no + 1, line.trim())) {
+            let parse_error = |reason| TranslationsParseError { line: line_no, reason };
+            let unquote = |quoted| unquote_po(quoted).ok_or(parse_error(TranslationsParseErrorReason::UnquotedText));
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(quoted) = line.strip_prefix("msgctxt ") {
+                res.insert_pending(core::mem::take(&mut pending));
+                pending.context = Some(unquote(quoted)?);
+                pending.field = PendingField::Context;
+            } else if let Some(quoted) = line.strip_prefix("msgid ") {
+                if pending.translated.is_some() {
+                    res.insert_pending(core::mem::take(&mut pending));
+                }
+                pending.id = Some(unquote(quoted)?);
+                pending.field = PendingField::Id;
+            } else if let Some(quoted) = line.strip_prefix("msgid_plural ") {
+                unquote(quoted)?;
+                pending.field = PendingField::Skipped;
+            } else if let Some((form, quoted)) = line.strip_prefix("msgstr").and_then(msgstr_form) {
+                if pending.id.is_none() {
+                    return Err(parse_error(TranslationsParseErrorReason::MsgstrWithoutMsgid));
+                }
+                let translated = unquote(quoted)?;
+                if form == 0 {
+                    pending.translated = Some(translated);
+                    pending.field = PendingField::Translated;
+                } else {
+                    pending.field = PendingField::Skipped;
+                }
+            } else if line.starts_with('"') {
+                let continuation = unquote(line)?;
+                match pending.field {
+                    PendingField::Context => pending.context.get_or_insert_with(String::new).push_str(&continuation),
+                    PendingField::Id => pending.id.get_or_insert_with(String::new).push_str(&continuation),
+                    PendingField::Translated => pending.translated.get_or_insert_with(String::new).push_str(&continuation),
+                    PendingField::Skipped => {}
+                    PendingField::None => return Err(parse_error(TranslationsParseErrorReason::UnexpectedLine)),
+                }
+            } else {
+                return Err(parse_error(TranslationsParseErrorReason::UnexpectedLine));
+            }
+        }
+        res.insert_pending(pending);
+        Ok(res)
+    }
+
+    fn insert_pending(&mut self, pending: PendingEntry) {
+        let PendingEntry { context, id: Some(id), translated: Some(translated), .. } = pending else { return; };
+        if context.is_some() || !id.is_empty() {
+            self.entries.insert((context, id), Entry { translated, original: None });
+        }
+    }
+}
+
+/// Entry of a PO file being read by [Translations::from_po_str].
+#[derive(Default)]
+struct PendingEntry {
+    context: Option<String>,
+    id: Option<String>,
+    translated: Option<String>,
+    field: PendingField,
+}
+
+/// Part of a [PendingEntry] continuation lines are appended to.
+#[derive(Default)]
+enum PendingField {
+    #[default]
+    None,
+    Context,
+    Id,
+    Translated,
+    Skipped,
+}
+
+/// Splits what follows `msgstr` into the plural form it translates, 0 when it isn't a plural one,
+/// and its quoted text.
+fn msgstr_form(rest: &str) -> Option<(usize, &str)> {
+    if let Some(quoted) = rest.strip_prefix(' ') {
+        return Some((0, quoted));
+    }
+    let (form, quoted) = rest.strip_prefix('[')?.split_once(']')?;
+    Some((form.trim().parse().ok()?, quoted))
+}
+
+/// Gives the texts of an error that can be translated, along with the key they get under the code
+/// of the error, like `reason` or `note 1`, none being given for those keyed by their own text.
+fn texts_of(display: &SimpleErrorDisplayInfo) -> impl Iterator<Item=(Option<String>, &String)> {
+    display.reason.iter().map(|reason| ("reason".to_string(), reason))
+        .chain(display.solution.iter().map(|solution| ("solution".to_string(), solution)))
+        .chain(numbered("solution step", display.solution_steps.iter()))
+        .chain(numbered("note", display.notes.iter()))
+        .chain(numbered("help", display.helps.iter()))
+        .chain(numbered("label", display.labels.iter().map(|label| &label.message)))
+        .map(|(key, text)| (display.code.as_ref().map(|_| key), text))
+}
+
+fn numbered<'text>(name: &'static str, texts: impl Iterator<Item=&'text String>) -> impl Iterator<Item=(String, &'text String)> {
+    texts.enumerate().map(move |(index, text)| (format!("{name} {}", index + 1), text))
+}
+
+/// Gives every label of these display options that can be translated.
+fn labels_of(options: &mut DisplayOptions) -> [&mut String; 27] {
+    [
+        &mut options.position_label, &mut options.at_label, &mut options.context_label, &mut options.label_label,
+        &mut options.code_label, &mut options.error_label, &mut options.warning_label, &mut options.info_label,
+        &mut options.hint_label, &mut options.solution_label, &mut options.suggestion_label, &mut options.possible_fix_label,
+        &mut options.note_label, &mut options.help_label, &mut options.more_info_label, &mut options.details_label,
+        &mut options.backtrace_label, &mut options.has_label, &mut options.cause_label, &mut options.causes_label,
+        &mut options.unexplained_error, &mut options.cause_header, &mut options.error_header,
+        &mut options.error_total_label, &mut options.count_conjunction,
+        &mut options.omitted_causes_text, &mut options.more_causes_prefix,
+    ]
+}
+
+/// Replaces every metadata key between braces, like `{name}`, by its value.
+fn interpolate(translated: &str, metadata: &[(String, String)]) -> String {
+    metadata.iter().fold(translated.to_string(), |res, (key, value)| res.replace(&format!("{{{key}}}"), value))
+}
+
+impl SimpleErrorDisplayInfo {
+    /// Returns a copy of this error and its causes whose explanations, solutions, notes, helps and
+    /// labels are replaced by their translations, texts without a translation are kept as they are.
+    ///
+    /// Texts of errors having a code are first looked up by said code, filling the metadata keys
+    /// between braces in their translation, and then by their own text.
+    pub fn translated(&self, translations: &Translations) -> Self {
+        let translate = |key: Option<String>, text: &String| {
+            let by_code = self.code.as_deref().zip(key)
+                .and_then(|(code, key)| translations.translate_in_context(code, &key))
+                .map(|translated| interpolate(translated, &self.metadata));
+            by_code.unwrap_or_else(|| translations.translate(text).to_string())
+        };
+        let mut texts = texts_of(self).map(|(key, text)| translate(key, text)).collect::<Vec<_>>().into_iter();
+        let mut res = self.clone();
+        res.reason = self.reason.as_ref().and_then(|_| texts.next());
+        res.solution = self.solution.as_ref().and_then(|_| texts.next());
+        res.solution_steps = texts.by_ref().take(self.solution_steps.len()).collect();
+        res.notes = texts.by_ref().take(self.notes.len()).collect();
+        res.helps = texts.by_ref().take(self.helps.len()).collect();
+        res.labels.iter_mut().zip(texts).for_each(|(label, translated)| label.message = translated);
+        res.explained_causes = self.explained_causes.iter().map(|cause| cause.translated(translations)).collect();
+        res
+    }
+}
+
+impl DisplayOptions {
+    /// Returns a copy of these display options whose labels are replaced by their translations
+    /// under [Translations::DISPLAY_OPTIONS_CONTEXT], labels without a translation are kept as they
+    /// are.
+    pub fn translated(&self, translations: &Translations) -> Self {
+        let mut res = self.clone();
+        labels_of(&mut res).into_iter().for_each(|label| {
+            if let Some(translated) = translations.translate_in_context(Translations::DISPLAY_OPTIONS_CONTEXT, label) {
+                *label = translated.to_string();
+            }
+        });
+        res
+    }
+}
+
+/// Error given by [Translations::from_po_str] when the PO file can't be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationsParseError {
+    /// Line (Starting from 1) where the error was found.
+    pub line: usize,
+    /// What was wrong on said line.
+    pub reason: TranslationsParseErrorReason,
+}
+
+/// What was wrong on a line of a PO file read through [Translations::from_po_str].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TranslationsParseErrorReason {
+    /// A text wasn't surrounded by double quotes.
+    UnquotedText,
+    /// A `msgstr` appeared before any `msgid`.
+    MsgstrWithoutMsgid,
+    /// The line isn't a comment, a `msgctxt`, a `msgid`, a `msgid_plural`, a `msgstr` nor a
+    /// continuation of them.
+    UnexpectedLine,
+}
+
+impl SimpleErrorDetail for TranslationsParseError {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        let line = self.line;
+        match self.reason {
+            TranslationsParseErrorReason::UnquotedText => SimpleErrorExplanation::new()
+                .explanation(format!("Text on line {line} isn't surrounded by double quotes."))
+                .solution("Write texts like this: msgid \"Your text\""),
+            TranslationsParseErrorReason::MsgstrWithoutMsgid => SimpleErrorExplanation::new()
+                .explanation(format!("Translation on line {line} doesn't belong to any original text."))
+                .solution("Write a msgid line with the original text before its msgstr."),
+            TranslationsParseErrorReason::UnexpectedLine => SimpleErrorExplanation::new()
+                .explanation(format!("Line {line} isn't a comment, a msgctxt, a msgid, a msgstr nor a continuation of them."))
+                .solution("Start comments with #, and texts with msgctxt, msgid, msgid_plural, msgstr or a double quote."),
+        }
+    }
+}
+
+fn escape_po(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    text.chars().for_each(|char| match char {
+        '\\' => res.push_str("\\\\"),
+        '"' => res.push_str("\\\""),
+        '\n' => res.push_str("\\n"),
+        '\t' => res.push_str("\\t"),
+        char => res.push(char),
+    });
+    res
+}
+
+fn unquote_po(quoted: &str) -> Option<String> {
+    let quoted = quoted.trim().strip_prefix('"')?.strip_suffix('"')?;
+    let mut res = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            res.push(char);
+            continue;
+        }
+        match chars.next()? {
+            'n' => res.push('\n'),
+            't' => res.push('\t'),
+            escaped => res.push(escaped),
+        }
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::simple_error::SimpleError;
+
+    #[derive(Debug)]
+    struct MissingVariable(String);
+
+    impl SimpleErrorDetail for MissingVariable {
+        fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+            SimpleErrorExplanation::new()
+                .code("E0425")
+                .explanation(format!("Variable {} doesn't exist.", self.0))
+                .note("Variables must be declared before they are used.")
+        }
+    }
+
+    fn missing_variable(name: &str) -> SimpleErrorDisplayInfo {
+        SimpleError::new().error_detail(MissingVariable(name.to_string())).with_metadata("name", name).as_display_struct(false)
+    }
+
+    #[test]
+    fn coded_errors_are_keyed_by_their_code_and_filled_with_their_metadata() {
+        let mut translations = Translations::extract(&missing_variable("x"));
+        translations.extend_from(&missing_variable("y"));
+        assert_eq!(translations.entries.len(), 2);
+        translations.insert_in_context("E0425", "reason", "La variable {name} no existe.");
+        let translated = missing_variable("y").translated(&translations);
+        assert_eq!(translated.reason.as_deref(), Some("La variable y no existe."));
+        assert_eq!(translated.notes, vec!["Variables must be declared before they are used.".to_string()]);
+    }
+
+    #[test]
+    fn display_options_labels_are_extracted_and_translated() {
+        let mut translations = Translations::new();
+        translations.extend_from_options(&DisplayOptions::default());
+        assert!(translations.to_po_string().contains("msgctxt \"DisplayOptions\"\nmsgid \"Solution\"\n"));
+        translations.insert_in_context(Translations::DISPLAY_OPTIONS_CONTEXT, "Solution", "Solución");
+        let options = DisplayOptions::default().translated(&translations);
+        assert_eq!(options.solution_label, "Solución");
+        assert_eq!(options.error_label, "Error");
+    }
+
+    #[test]
+    fn po_files_round_trip_and_accept_contexts_and_plurals() {
+        let mut translations = Translations::extract(&missing_variable("x"));
+        translations.insert_in_context("E0425", "note 1", "Las variables deben declararse antes de usarse.");
+        translations.insert("Unexplained", "Sin explicar");
+        assert_eq!(Translations::from_po_str(&translations.to_po_string()).map(|read| read.translate_in_context("E0425", "note 1").map(ToString::to_string)),
+                   Ok(Some("Las variables deben declararse antes de usarse.".to_string())));
+        let po = "msgid \"\"\nmsgstr \"Language: es\\n\"\n\nmsgctxt \"E0001\"\nmsgid \"reason\"\nmsgstr \"Razón\"\n\n\
+                  msgid \"One cause\"\nmsgid_plural \"Many causes\"\nmsgstr[0] \"Una causa\"\nmsgstr[1] \"Varias causas\"\n";
+        let read = Translations::from_po_str(po).unwrap();
+        assert_eq!(read.translate_in_context("E0001", "reason"), Some("Razón"));
+        assert_eq!(read.translate("One cause"), "Una causa");
+        assert_eq!(read.entries.len(), 2);
+    }
+}
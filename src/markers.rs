@@ -0,0 +1,59 @@
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+use crate::theme::current_style;
+
+/// Style given to colorization markers, like [crate::SimpleErrorExplanation::colorization_marker].
+///
+/// With the colorization feature this is a `string_colorization::Colorizer`, while with only the
+/// colorization-anstyle feature this is an `anstyle::Style`, so the same implementations of
+/// [crate::SimpleErrorDetail::explain_error] work with either backend as long as they take their
+/// styles from the functions in this module.
+#[cfg(feature = "colorization")]
+pub type Marker = string_colorization::Colorizer;
+
+/// Style given to colorization markers, like [crate::SimpleErrorExplanation::colorization_marker].
+///
+/// With the colorization feature this is a `string_colorization::Colorizer`, while with only the
+/// colorization-anstyle feature this is an `anstyle::Style`, so the same implementations of
+/// [crate::SimpleErrorDetail::explain_error] work with either backend as long as they take their
+/// styles from the functions in this module.
+#[cfg(all(feature = "colorization-anstyle", not(feature = "colorization")))]
+pub type Marker = anstyle::Style;
+
+/// Style for the parts of the input causing the error, as given by the active [crate::theme::Theme],
+/// so implementations of [crate::SimpleErrorDetail::explain_error] describe what this part of the
+/// input means rather than which color it takes.
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+pub fn error_span() -> Marker {
+    current_style(|theme| &theme.error_span)
+}
+
+/// Style for the input surrounding the error, usually given to
+/// [crate::SimpleErrorExplanation::whole_input_colorization].
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+pub fn context() -> Marker {
+    current_style(|theme| &theme.context)
+}
+
+/// Style for the parts of the input related to how the error might be solved.
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+pub fn hint() -> Marker {
+    current_style(|theme| &theme.hint)
+}
+
+/// Without the 'colorization' or 'colorization-anstyle' features this gives nothing, it only
+/// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+/// said features are enabled or not.
+#[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+pub fn error_span() {}
+
+/// Without the 'colorization' or 'colorization-anstyle' features this gives nothing, it only
+/// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+/// said features are enabled or not.
+#[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+pub fn context() {}
+
+/// Without the 'colorization' or 'colorization-anstyle' features this gives nothing, it only
+/// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+/// said features are enabled or not.
+#[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+pub fn hint() {}
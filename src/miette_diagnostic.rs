@@ -0,0 +1,176 @@
+use std::boxed::Box;
+use std::fmt::{Display, Formatter};
+use std::format;
+use std::ops::Range;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use miette::{Diagnostic, LabeledSpan, Severity as MietteSeverity, SourceCode};
+
+use crate::formatting::join_strings;
+use crate::severity::Severity;
+use crate::simple_error::SimpleError;
+use crate::span::Span;
+
+/// This is only implemented when using the miette feature.
+///
+/// Error implementing miette's [Diagnostic], built through [SimpleError::to_miette_diagnostic], so
+/// applications already reporting errors through miette can report the errors of this crate too,
+/// for example, by returning `Err(error.to_miette_diagnostic().into())` from a function returning
+/// `miette::Result`.
+///
+/// It holds everything it needs to be reported, so it doesn't borrow the error it was built from.
+#[derive(Debug, Clone)]
+pub struct MietteAdapter {
+    message: String,
+    code: Option<String>,
+    severity: Severity,
+    help: Option<String>,
+    url: Option<String>,
+    source_code: Option<String>,
+    labels: Vec<LabeledSpan>,
+    related: Vec<MietteAdapter>,
+}
+
+impl<'input> SimpleError<'input> {
+    /// This is only implemented when using the miette feature.
+    ///
+    /// Turns this error into a [MietteAdapter] implementing miette's [Diagnostic], where:
+    /// * The explanation becomes the message, while the code, severity and documentation link are
+    ///   kept as the diagnostic's code, severity and url, where hints and infos become advices.
+    /// * The solution, its steps, helps and notes are joined into the help.
+    /// * The source given on [SimpleError::with_source] becomes the source code, and the location
+    ///   given on [SimpleError::at] becomes the primary label, as long as it is found on it.
+    /// * Labels, parts of the input given colorization markers and emphasized spans become labels.
+    /// * Causes located in the source become labels holding their explanation, and the rest
+    ///   become related diagnostics.
+    pub fn to_miette_diagnostic(&self) -> MietteAdapter {
+        self.to_miette_adapter(None)
+    }
+
+    fn to_miette_adapter(&self, inherited_source: Option<&'input str>) -> MietteAdapter {
+        let source = self.current_source().or(inherited_source);
+        let display = self.as_display_struct(false);
+        let explanation = self.current_error_detail().as_ref()
+            .map(|error_detail| error_detail.explain_error().evaluated())
+            .unwrap_or_default();
+        let help = join_strings("\n", explanation.solution.iter().cloned()
+            .chain(explanation.solution_steps.iter().enumerate().map(|(step_no, step)| format!("{}. {step}", step_no + 1)))
+            .chain(explanation.helps.iter().cloned())
+            .chain(explanation.notes.iter().map(|note| format!("note: {note}"))));
+        let mut res = MietteAdapter {
+            message: display.reason().unwrap_or("Unexplained error").to_string(),
+            code: explanation.code.clone(),
+            severity: explanation.severity,
+            help: Some(help).filter(|help| !help.is_empty()),
+            url: explanation.doc_url.clone(),
+            source_code: source.map(|source| source.to_string()),
+            labels: Vec::new(),
+            related: Vec::new(),
+        };
+        if let Some(source) = source {
+            if let (Some(span), _, _) = self.resolve_position(Some(source)) {
+                res.labels.push(LabeledSpan::new_primary_with_span(None, Range::from(span)));
+            }
+            res.labels.extend(explanation.labels.iter()
+                .filter_map(|(location, message)| location.span(source)
+                    .map(|span| LabeledSpan::new_with_span(Some(message.clone()), Range::from(span)))));
+            #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+            res.labels.extend(explanation.colorization_markers.iter()
+                .filter_map(|(marked, _)| Span::of(source, marked))
+                .chain(explanation.span_colorization_markers.iter().map(|(span, _)| *span))
+                .map(|span| LabeledSpan::new_with_span(None, Range::from(span))));
+            res.labels.extend(explanation.marked_spans.iter()
+                .filter_map(|marked| Span::of(source, marked))
+                .map(|span| LabeledSpan::new_with_span(None, Range::from(span))));
+        }
+        for cause in self.current_causes() {
+            let cause_source = cause.current_source().or(source);
+            match (cause_source == source, cause.resolve_position(cause_source)) {
+                (true, (Some(span), _, _)) => {
+                    let message = cause.as_display_struct(false).reason().map(str::to_string);
+                    res.labels.push(LabeledSpan::new_with_span(message, Range::from(span)));
+                    res.related.extend(cause.current_causes().iter().map(|cause| cause.to_miette_adapter(source)));
+                }
+                _ => {
+                    if cause.as_display_struct(false).is_explained() {
+                        res.related.push(cause.to_miette_adapter(source));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl Display for MietteAdapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for MietteAdapter {}
+
+impl Diagnostic for MietteAdapter {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.code.as_ref().map(|code| Box::new(code) as Box<dyn Display + 'a>)
+    }
+
+    fn severity(&self) -> Option<MietteSeverity> {
+        Some(match self.severity {
+            Severity::Hint | Severity::Info => MietteSeverity::Advice,
+            Severity::Warning => MietteSeverity::Warning,
+            Severity::Error => MietteSeverity::Error,
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.help.as_ref().map(|help| Box::new(help) as Box<dyn Display + 'a>)
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.url.as_ref().map(|url| Box::new(url) as Box<dyn Display + 'a>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.source_code.as_ref().map(|source_code| source_code as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item=LabeledSpan> + '_>> {
+        if self.labels.is_empty() {
+            return None;
+        }
+        Some(Box::new(self.labels.iter().cloned()))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item=&'a dyn Diagnostic> + 'a>> {
+        if self.related.is_empty() {
+            return None;
+        }
+        Some(Box::new(self.related.iter().map(|related| related as &dyn Diagnostic)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleErrorExplanation;
+
+    #[test]
+    fn errors_become_miette_diagnostics_with_labels_and_related_causes() {
+        let input = "let x = y;";
+        let explanation = SimpleErrorExplanation::new().explanation("Variable y doesn't exist").solution("Declare y").code("E1")
+            .label(&input[4..5], "Assigned here");
+        let error = SimpleError::new().with_source(input).at(&input[8..9]).error_detail(explanation)
+            .with_cause(String::from("Couldn't resolve imports"));
+        let diagnostic = error.to_miette_diagnostic();
+        assert_eq!(diagnostic.to_string(), "Variable y doesn't exist");
+        assert_eq!(diagnostic.code().map(|code| code.to_string()).as_deref(), Some("E1"));
+        assert_eq!(diagnostic.help().map(|help| help.to_string()).as_deref(), Some("Declare y"));
+        assert_eq!(diagnostic.severity(), Some(MietteSeverity::Error));
+        let labels = diagnostic.labels().unwrap().map(|label| (label.primary(), label.inner().offset(), label.label().map(String::from))).collect::<Vec<_>>();
+        assert_eq!(labels, [(true, 8, None), (false, 4, Some("Assigned here".to_string()))]);
+        let related = diagnostic.related().unwrap().map(|related| related.to_string()).collect::<Vec<_>>();
+        assert_eq!(related, ["Couldn't resolve imports"]);
+    }
+}
@@ -0,0 +1,525 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::hash::Hasher;
+use core::time::Duration;
+
+use crate::color_choice::ColorChoice;
+use crate::diagnostics_index::DiagnosticsIndex;
+use crate::display_options::{DisplayOptions, NumberFormatter};
+use crate::fingerprint::StableHasher;
+use crate::formatting::join_strings;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+use crate::span::SourceId;
+use crate::stats::severity_counts;
+
+/// Holds independent errors found while processing an input, such as every error found by the
+/// different phases of a compiler, along with how long each phase took.
+///
+/// Reports can be created from any collection of errors, like `Report::from(errors)` or
+/// `errors.into_iter().collect::<Report>()`, and their errors can be numbered and followed by a
+/// line summarizing them through [Report::numbered] and [Report::summary_footer].
+#[derive(Debug, Default, Clone)]
+pub struct Report<'input> {
+    errors: Vec<SimpleError<'input>>,
+    phase_timings: Vec<PhaseTiming>,
+    numbered: bool,
+    summary_footer: bool,
+    timing_footer: bool,
+    success_message: Option<String>,
+    tool_info: Option<ToolInfo>,
+    tool_info_footer: bool,
+}
+
+/// Name and version of the tool producing a report, so archived reports can be traced back to the
+/// version of the tool that generated them.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolInfo {
+    /// Name of the tool, like 'mytool'.
+    pub name: String,
+    /// Version of the tool, like '1.4.2'.
+    pub version: String,
+}
+
+/// How long a phase of the processing took, such as 'parsing' or 'type checking'.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    /// Name of the phase.
+    pub name: String,
+    /// How long the phase took.
+    pub duration: Duration,
+}
+
+impl<'input> Report<'input> {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self {
+            errors: Vec::new(),
+            phase_timings: Vec::new(),
+            numbered: false,
+            summary_footer: false,
+            timing_footer: false,
+            success_message: None,
+            tool_info: None,
+            tool_info_footer: false,
+        }
+    }
+
+    /// Adds an error to this report.
+    pub fn push<PError: Into<SimpleError<'input>>>(&mut self, error: PError) {
+        self.errors.push(error.into());
+    }
+
+    /// Adds an error to this report.
+    pub fn with_error<PError: Into<SimpleError<'input>>>(mut self, error: PError) -> Self {
+        self.push(error);
+        self
+    }
+
+    /// Tells if this report doesn't hold any error.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Tells if this report holds any error, this is the opposite of [Report::is_empty].
+    pub fn has_errors(&self) -> bool {
+        !self.is_empty()
+    }
+
+    /// Returns the errors on this report, in the order they were added.
+    pub fn errors(&self) -> &[SimpleError<'input>] {
+        &self.errors
+    }
+
+    /// Returns a copy of this report keeping at most *max_errors* of its errors, in the order they
+    /// were added, for showing a few representative errors of large reports.
+    ///
+    /// Which errors are kept only depends on *seed* and on the errors themselves (See
+    /// [SimpleErrorDisplayInfo::fingerprint]), not on the order they were added in, so the same
+    /// seed keeps the same errors between runs, making sampled reports comparable across CI runs.
+    pub fn sampled(&self, max_errors: usize, seed: u64) -> Self {
+        let fingerprints = self.errors.iter().map(|error| error.as_display_struct(false).fingerprint());
+        let errors = sampled_indexes(fingerprints, max_errors, seed).into_iter()
+            .map(|index| self.errors[index].clone())
+            .collect();
+        Self {
+            errors,
+            phase_timings: self.phase_timings.clone(),
+            numbered: self.numbered,
+            summary_footer: self.summary_footer,
+            timing_footer: self.timing_footer,
+            success_message: self.success_message.clone(),
+            tool_info: self.tool_info.clone(),
+            tool_info_footer: self.tool_info_footer,
+        }
+    }
+
+    /// Keeps only the errors happening in the sources given by *unchanged_source_ids* (See
+    /// [SimpleError::in_source]), removing errors of any other source and errors without one.
+    ///
+    /// This lets watch-mode tools reuse the errors of the sources that didn't change, then adding
+    /// the errors found again on the changed ones through [Report::merge_fresh], instead of
+    /// recomputing the whole report.
+    pub fn retain_for_sources<SourceIds: IntoIterator<Item=SourceId>>(&mut self, unchanged_source_ids: SourceIds) {
+        let unchanged_source_ids = unchanged_source_ids.into_iter().collect::<BTreeSet<_>>();
+        self.errors.retain(|error| error.current_source_id().is_some_and(|source_id| unchanged_source_ids.contains(&source_id)));
+    }
+
+    /// Adds the errors of *fresh*, usually found on the sources that changed, to this report,
+    /// skipping those already present, where errors are told apart by their source (See
+    /// [SimpleError::in_source]) and their positioned fingerprint (See
+    /// [SimpleErrorDisplayInfo::positioned_fingerprint]), so the same error happening at different
+    /// places is kept once per place. The rest of *fresh*, like its phase timings, is discarded.
+    pub fn merge_fresh(&mut self, fresh: Report<'input>) {
+        let key_of = |error: &SimpleError| (error.current_source_id(), error.as_display_struct(false).positioned_fingerprint());
+        let mut known_keys = self.errors.iter().map(key_of).collect::<BTreeSet<_>>();
+        fresh.errors.into_iter()
+            .filter(|error| known_keys.insert(key_of(error)))
+            .for_each(|error| self.errors.push(error));
+    }
+
+    /// Indexes the errors of this report by where they happen, see [DiagnosticsIndex].
+    pub fn diagnostics_index(&self) -> DiagnosticsIndex<'_, 'input> {
+        DiagnosticsIndex::new(self)
+    }
+
+    /// Records how long a phase of the processing took.
+    pub fn record_phase<Name: Into<String>>(&mut self, name: Name, duration: Duration) {
+        self.phase_timings.push(PhaseTiming { name: name.into(), duration });
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Runs *phase* recording how long it took under the given name.
+    #[cfg(feature = "std")]
+    pub fn time_phase<Name: Into<String>, Res, Phase: FnOnce() -> Res>(&mut self, name: Name, phase: Phase) -> Res {
+        let start = std::time::Instant::now();
+        let res = phase();
+        self.record_phase(name, start.elapsed());
+        res
+    }
+
+    /// Returns the timings recorded through [Report::record_phase] and [Report::time_phase].
+    pub fn phase_timings(&self) -> &[PhaseTiming] {
+        &self.phase_timings
+    }
+
+    /// Returns the sum of the durations of every recorded phase.
+    pub fn total_duration(&self) -> Duration {
+        self.phase_timings.iter().map(|timing| timing.duration).sum()
+    }
+
+    /// Indicates whether every error is preceded by a header numbering it, like
+    /// '- Error nº 2 of 3 -'.
+    pub fn numbered(mut self, numbered: bool) -> Self {
+        self.numbered = numbered;
+        self
+    }
+
+    /// Indicates whether the errors are followed by a line counting them by severity, like 'Found
+    /// 2 errors and 1 warning', shown only if the report holds any error.
+    pub fn summary_footer(mut self, summary_footer: bool) -> Self {
+        self.summary_footer = summary_footer;
+        self
+    }
+
+    /// Indicates whether the report ends with a line saying how long the processing took, like
+    /// 'Completed with errors in 3.2s'.
+    pub fn timing_footer(mut self, timing_footer: bool) -> Self {
+        self.timing_footer = timing_footer;
+        self
+    }
+
+    /// Sets the message shown instead of the errors when the report doesn't hold any, like
+    /// 'Compiled successfully', by default nothing is shown.
+    pub fn success_message<Message: Into<String>>(mut self, success_message: Message) -> Self {
+        self.success_message = Some(success_message.into());
+        self
+    }
+
+    /// Sets the name and version of the tool producing this report, which are kept when
+    /// serializing it, for example, through `with_tool_info(env!("CARGO_PKG_NAME"),
+    /// env!("CARGO_PKG_VERSION"))`.
+    pub fn with_tool_info<Name: Into<String>, Version: Into<String>>(mut self, name: Name, version: Version) -> Self {
+        self.tool_info = Some(ToolInfo { name: name.into(), version: version.into() });
+        self
+    }
+
+    /// Returns the name and version of the tool producing this report, if set through
+    /// [Report::with_tool_info].
+    pub fn tool_info(&self) -> Option<&ToolInfo> {
+        self.tool_info.as_ref()
+    }
+
+    /// Indicates whether the report ends with a line saying which tool generated it, like
+    /// 'Generated by mytool 1.4.2', shown only if the tool was set through
+    /// [Report::with_tool_info].
+    pub fn tool_info_footer(mut self, tool_info_footer: bool) -> Self {
+        self.tool_info_footer = tool_info_footer;
+        self
+    }
+
+    /// Gives a string displaying this report with colors when the standard error is a terminal or
+    /// when they are forced through `colored::control::set_override(true)` (See
+    /// [ColorChoice::Stderr]), this is the same as displaying it, see
+    /// [ReportDisplayInfo::as_display_string].
+    pub fn render(&self) -> String {
+        self.as_display_struct(ColorChoice::Stderr).as_display_string()
+    }
+
+    /// Turns this report into a [ReportDisplayInfo], where every error is turned into a
+    /// [SimpleErrorDisplayInfo] as in [SimpleError::as_display_struct].
+    pub fn as_display_struct<Choice: Into<ColorChoice>>(&self, colorize: Choice) -> ReportDisplayInfo {
+        let colorize = colorize.into();
+        self.as_display_struct_of(self.errors.iter().map(|error| error.as_display_struct(colorize)).collect())
+    }
+
+    /// Turns this report into a [ReportDisplayInfo] whose errors are *errors*, which are the
+    /// displays of the errors of this report.
+    pub(crate) fn as_display_struct_of(&self, errors: Vec<SimpleErrorDisplayInfo>) -> ReportDisplayInfo {
+        ReportDisplayInfo {
+            errors,
+            phase_timings: self.phase_timings.clone(),
+            numbered: self.numbered,
+            summary_footer: self.summary_footer,
+            timing_footer: self.timing_footer,
+            success_message: self.success_message.clone(),
+            tool_info: self.tool_info.clone(),
+            tool_info_footer: self.tool_info_footer,
+        }
+    }
+}
+
+/// Creates a report holding *errors*, in the same order.
+impl<'input, PError: Into<SimpleError<'input>>> From<Vec<PError>> for Report<'input> {
+    fn from(errors: Vec<PError>) -> Self {
+        errors.into_iter().collect()
+    }
+}
+
+/// Creates a report holding the errors of the iterator, in the same order.
+impl<'input, PError: Into<SimpleError<'input>>> FromIterator<PError> for Report<'input> {
+    fn from_iter<Errors: IntoIterator<Item=PError>>(errors: Errors) -> Self {
+        let mut report = Self::new();
+        errors.into_iter().for_each(|error| report.push(error));
+        report
+    }
+}
+
+/// Reports display through the [ReportDisplayInfo] struct calling to
+/// [ReportDisplayInfo::as_display_string].
+impl<'input> Display for Report<'input> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// Holds the information of a [Report] in order to display it, and if the `serde` feature is
+/// enabled, it also implements [serde::Serialize] and [serde::Deserialize], including the timing of
+/// every phase.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReportDisplayInfo {
+    pub(crate) errors: Vec<SimpleErrorDisplayInfo>,
+    pub(crate) phase_timings: Vec<PhaseTiming>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) numbered: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) summary_footer: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) timing_footer: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) success_message: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tool_info: Option<ToolInfo>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tool_info_footer: bool,
+}
+
+/// Implements display by calling [ReportDisplayInfo::as_display_string]
+impl Display for ReportDisplayInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.as_display_string())
+    }
+}
+
+impl ReportDisplayInfo {
+    /// Returns a copy of this report keeping at most *max_errors* of its errors, chosen the same
+    /// way as [Report::sampled] does.
+    pub fn sampled(&self, max_errors: usize, seed: u64) -> Self {
+        let fingerprints = self.errors.iter().map(|error| error.fingerprint());
+        let errors = sampled_indexes(fingerprints, max_errors, seed).into_iter()
+            .map(|index| self.errors[index].clone())
+            .collect();
+        Self { errors, ..self.clone() }
+    }
+
+    /// Returns the name and version of the tool that produced the report, if any.
+    pub fn tool_info(&self) -> Option<&ToolInfo> {
+        self.tool_info.as_ref()
+    }
+
+    /// Gives a string displaying every error of the report separated by an empty line, numbered if
+    /// they were set to be numbered, or the success message if there aren't any errors, followed by
+    /// a line counting them if the summary footer was enabled, by how long the processing took if
+    /// the timing footer was enabled, and by which tool generated the report if the tool info
+    /// footer was enabled.
+    pub fn as_display_string(&self) -> String {
+        self.as_display_string_with_options(&DisplayOptions::default())
+    }
+
+    /// Gives a string displaying this report as in [ReportDisplayInfo::as_display_string], but
+    /// displaying every error using the labels, indentation and cause separator given on
+    /// *options*, where errors less serious than [DisplayOptions::min_severity] are left out, and
+    /// identical errors are merged when [DisplayOptions::group_identical] is set, see
+    /// [ReportDisplayInfo::dedup_errors].
+    pub fn as_display_string_with_options(&self, options: &DisplayOptions) -> String {
+        if options.group_identical {
+            let options = DisplayOptions { group_identical: false, ..options.clone() };
+            return self.dedup_errors().as_display_string_with_options(&options);
+        }
+        let success_message = self.success_message.clone().filter(|_| self.errors.is_empty());
+        let shown_errors = self.errors.iter()
+            .filter(|error| options.min_severity.filter(|min_severity| error.severity < *min_severity).is_none())
+            .collect::<Vec<_>>();
+        let errors_count = shown_errors.len();
+        let summary = Some(severities_summary(&shown_errors)).filter(|_| self.summary_footer && errors_count > 0);
+        let errors = shown_errors.into_iter().enumerate()
+            .map(|(index, error)| {
+                let error_string = error.as_display_string_with_options(options);
+                if !self.numbered {
+                    return error_string;
+                }
+                let NumberFormatter { ordinal, number, .. } = options.number_formatter;
+                format!("- {} {} {} {} -\n{error_string}", options.error_header, ordinal(index + 1), options.error_total_label, number(errors_count))
+            })
+            .chain(success_message)
+            .chain(summary);
+        let footer = self.timing_footer.then(|| {
+            let total_duration = self.phase_timings.iter().map(|timing| timing.duration).sum();
+            let outcome = if self.errors.is_empty() { "Completed" } else { "Completed with errors" };
+            format!("{outcome} in {}", format_duration(total_duration))
+        });
+        let tool_info_footer = self.tool_info.as_ref()
+            .filter(|_| self.tool_info_footer)
+            .map(|tool_info| format!("Generated by {} {}", tool_info.name, tool_info.version));
+        join_strings("\n\n", errors.chain(footer).chain(tool_info_footer))
+    }
+}
+
+/// Chooses at most *max_errors* indexes of the errors with the given fingerprints, in ascending
+/// order, ranking every error by hashing *seed*, its fingerprint and how many errors with the same
+/// fingerprint came before it, so the choice doesn't depend on the order of the errors.
+fn sampled_indexes<Fingerprints: Iterator<Item=u64>>(fingerprints: Fingerprints, max_errors: usize, seed: u64) -> Vec<usize> {
+    let mut occurrences = BTreeMap::<u64, u64>::new();
+    let mut ranked_indexes = fingerprints.enumerate()
+        .map(|(index, fingerprint)| {
+            let occurrence = occurrences.entry(fingerprint).or_default();
+            let mut hasher = StableHasher::new();
+            hasher.write_u64(seed);
+            hasher.write_u64(fingerprint);
+            hasher.write_u64(*occurrence);
+            *occurrence += 1;
+            (hasher.finish(), index)
+        })
+        .collect::<Vec<_>>();
+    ranked_indexes.sort_unstable();
+    let mut indexes = ranked_indexes.into_iter().take(max_errors).map(|(_, index)| index).collect::<Vec<_>>();
+    indexes.sort_unstable();
+    indexes
+}
+
+/// Counts *errors* by severity, from the most serious one, like 'Found 2 errors and 1 warning'.
+fn severities_summary(errors: &[&SimpleErrorDisplayInfo]) -> String {
+    let counts = severity_counts(|severity| errors.iter().filter(|error| error.severity == severity).count());
+    format!("Found {}", counts.unwrap_or_default())
+}
+
+/// Formats a duration as seconds with a decimal, like '3.2s', or as milliseconds when it's lower
+/// than a second, like '320ms'.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() >= 1 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::{Severity, SimpleErrorExplanation};
+
+    #[test]
+    fn timing_footers_add_up_every_recorded_phase() {
+        let mut report = Report::new().with_error("A".to_string()).with_error("B".to_string()).timing_footer(true);
+        report.record_phase("parse", Duration::from_millis(3000));
+        report.record_phase("check", Duration::from_millis(200));
+        assert_eq!(report.total_duration(), Duration::from_millis(3200));
+        assert_eq!(report.phase_timings().iter().map(|timing| timing.name.as_str()).collect::<Vec<_>>(), ["parse", "check"]);
+        assert_eq!(report.as_display_struct(false).as_display_string(), "Error: A\n\nError: B\n\nCompleted with errors in 3.2s");
+        assert_eq!(Report::new().timing_footer(true).as_display_struct(false).as_display_string(), "Completed in 0ms");
+    }
+
+    #[test]
+    fn success_messages_only_show_on_reports_without_errors() {
+        let report = Report::new().success_message("No errors found").timing_footer(true);
+        assert!(report.is_empty() && !report.has_errors());
+        assert_eq!(report.as_display_struct(false).as_display_string(), "No errors found\n\nCompleted in 0ms");
+        assert_eq!(Report::new().render(), "");
+        let failed = Report::new().success_message("No errors found").with_error("Unexpected ;".to_string());
+        assert!(failed.has_errors());
+        assert_eq!(failed.as_display_struct(false).as_display_string(), "Error: Unexpected ;");
+    }
+
+    #[test]
+    fn tool_info_is_only_shown_through_its_footer() {
+        let report = Report::new().success_message("No errors found").with_tool_info("checker", "1.4.2");
+        assert_eq!(report.tool_info(), Some(&ToolInfo { name: "checker".to_string(), version: "1.4.2".to_string() }));
+        assert_eq!(report.as_display_struct(false).as_display_string(), "No errors found");
+        let report = report.tool_info_footer(true);
+        assert_eq!(report.as_display_struct(false).as_display_string(), "No errors found\n\nGenerated by checker 1.4.2");
+        assert_eq!(Report::new().tool_info_footer(true).as_display_struct(false).as_display_string(), "");
+    }
+
+    #[test]
+    fn samples_depend_on_the_seed_but_not_on_the_order_of_errors() {
+        let report_of = |indexes: &mut dyn Iterator<Item=usize>| {
+            let mut report = Report::new();
+            indexes.for_each(|index| report.push(format!("Error nº {index}")));
+            report
+        };
+        let report = report_of(&mut (0..50));
+        let sampled = report.sampled(10, 7).as_display_struct(false).as_display_string();
+        assert_eq!(report.sampled(10, 7).errors().len(), 10);
+        assert_eq!(report.sampled(10, 7).as_display_struct(false).as_display_string(), sampled);
+        assert_eq!(report.as_display_struct(false).sampled(10, 7).as_display_string(), sampled);
+        assert_ne!(report.sampled(10, 8).as_display_struct(false).as_display_string(), sampled);
+        let sorted_lines = |rendered: &str| rendered.lines().map(String::from).collect::<BTreeSet<_>>();
+        let reversed = report_of(&mut (0..50).rev()).sampled(10, 7).as_display_struct(false).as_display_string();
+        assert_eq!(sorted_lines(&reversed), sorted_lines(&sampled));
+        assert_eq!(report.sampled(100, 1).errors().len(), 50);
+    }
+
+    #[test]
+    fn watch_mode_keeps_unchanged_sources_and_merges_fresh_errors_once() {
+        let (main, lib) = (SourceId(1), SourceId(2));
+        let mut report = Report::new()
+            .with_error(SimpleError::from("Unused variable".to_string()).in_source(main))
+            .with_error(SimpleError::from("Missing ;".to_string()).in_source(lib))
+            .with_error("Missing configuration".to_string());
+        report.retain_for_sources([main]);
+        assert_eq!(report.as_display_struct(false).as_display_string(), "Error: Unused variable");
+        let fresh = Report::new()
+            .with_error(SimpleError::from("Unused variable".to_string()).in_source(main))
+            .with_error(SimpleError::from("Unused variable".to_string()).in_source(lib))
+            .with_error(SimpleError::from("Missing type".to_string()).in_source(lib));
+        report.merge_fresh(fresh);
+        assert_eq!(report.errors().iter().map(|error| error.current_source_id()).collect::<Vec<_>>(), [Some(main), Some(lib), Some(lib)]);
+        assert_eq!(report.as_display_struct(false).as_display_string(), "Error: Unused variable\n\nError: Unused variable\n\nError: Missing type");
+    }
+
+    #[test]
+    fn numbered_reports_from_collections_end_with_a_summary_by_severity() {
+        let warning = SimpleError::from(SimpleErrorExplanation::new().explanation("Unused variable x").severity(Severity::Warning));
+        let report = Report::from(vec![SimpleError::from("Missing ;".to_string()), warning.clone(), "Missing type".to_string().into()])
+            .numbered(true).summary_footer(true);
+        assert_eq!(report.as_display_struct(false).as_display_string(), concat!(
+            "- Error nº 1 of 3 -\nError: Missing ;\n\n- Error nº 2 of 3 -\nWarning: Unused variable x\n\n",
+            "- Error nº 3 of 3 -\nError: Missing type\n\nFound 2 errors and 1 warning"));
+        let collected = [warning].into_iter().collect::<Report>().summary_footer(true);
+        assert_eq!(collected.as_display_struct(false).as_display_string(), "Warning: Unused variable x\n\nFound 1 warning");
+        assert_eq!(Report::new().summary_footer(true).as_display_struct(false).as_display_string(), "");
+    }
+
+    #[test]
+    fn merge_fresh_keeps_identical_errors_happening_at_different_places() {
+        let error_at = |line, column| SimpleError::new().error_detail("Unexpected ;".to_string()).in_source(SourceId(1)).start_point_of_error(line, column);
+        let mut report = Report::new().with_error(error_at(1, 4));
+        report.merge_fresh(Report::new().with_error(error_at(1, 4)).with_error(error_at(3, 2)).with_error(error_at(3, 2)));
+        let positions = report.errors().iter().map(|error| *error.current_start_point_of_error()).collect::<Vec<_>>();
+        assert_eq!(positions, [Some((1, 4)), Some((3, 2))]);
+    }
+
+    #[test]
+    fn numbered_reports_use_the_error_header_labels_and_number_formatter() {
+        let options = DisplayOptions {
+            error_header: "Fehler Nr.".to_string(),
+            error_total_label: "von".to_string(),
+            number_formatter: NumberFormatter { ordinal: |number| format!("{number}."), ..NumberFormatter::default() },
+            ..DisplayOptions::default()
+        };
+        let report = Report::new().numbered(true).with_error("A".to_string()).with_error("B".to_string());
+        let rendered = report.as_display_struct(false).as_display_string_with_options(&options);
+        assert_eq!(rendered, "- Fehler Nr. 1. von 2 -\nError: A\n\n- Fehler Nr. 2. von 2 -\nError: B");
+    }
+}
@@ -0,0 +1,204 @@
+// These traits exist to give SimpleErrors as the errors of results, which are big as they hold a lot
+// of information.
+#![allow(clippy::result_large_err)]
+
+use alloc::string::String;
+use core::any::Any;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::simple_error::SimpleError;
+use crate::simple_error_detail::SimpleErrorDetail;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+
+/// Enriches the error of a [Result] inline, turning it into a [SimpleError] without having to
+/// build explanations manually, for example `fs::read_to_string(path).map_err(|err|
+/// err.to_string()).explain_err(|| "reading config failed")`.
+pub trait ResultExt<'input, T> {
+    /// Responds to: What happened.
+    ///
+    /// Wraps the error into a new [SimpleError] explained by the text returned by *explainer*,
+    /// where the original error is kept as its cause. The closure is only called on [Err].
+    fn explain_err<Explanation, Explainer>(self, explainer: Explainer) -> Result<T, SimpleError<'input>>
+        where Explanation: Into<String>, Explainer: FnOnce() -> Explanation;
+
+    /// Responds to: How to solve it.
+    ///
+    /// Sets the text returned by *solver* as the solution of the error, keeping its explanation.
+    /// The closure is only called on [Err].
+    fn solution_err<Solution, Solver>(self, solver: Solver) -> Result<T, SimpleError<'input>>
+        where Solution: Into<String>, Solver: FnOnce() -> Solution;
+
+    /// Responds to: Where did it happen, usually on parsing errors.
+    ///
+    /// Sets where the error happened as in [SimpleError::at].
+    fn at_err(self, location_str: &'input str) -> Result<T, SimpleError<'input>>;
+}
+
+impl<'input, T, Error: Into<SimpleError<'input>>> ResultExt<'input, T> for Result<T, Error> {
+    fn explain_err<Explanation, Explainer>(self, explainer: Explainer) -> Result<T, SimpleError<'input>>
+        where Explanation: Into<String>, Explainer: FnOnce() -> Explanation {
+        self.map_err(|error| SimpleError::new().error_detail(explainer().into()).with_cause(error))
+    }
+
+    fn solution_err<Solution, Solver>(self, solver: Solver) -> Result<T, SimpleError<'input>>
+        where Solution: Into<String>, Solver: FnOnce() -> Solution {
+        self.map_err(|error| {
+            let error = error.into();
+            let detail = SolvedDetail { detail: error.current_error_detail().clone(), solution: solver().into() };
+            error.error_detail(detail)
+        })
+    }
+
+    fn at_err(self, location_str: &'input str) -> Result<T, SimpleError<'input>> {
+        self.map_err(|error| error.into().at(location_str))
+    }
+}
+
+/// Turns a [None] into a [SimpleError] inline, for example `variables.get(name).explain_err(||
+/// format!("Variable {name} doesn't exists"))`.
+pub trait OptionExt<'input, T> {
+    /// Responds to: What happened.
+    ///
+    /// Turns [None] into a [SimpleError] explained by the text returned by *explainer*. The
+    /// closure is only called on [None].
+    fn explain_err<Explanation, Explainer>(self, explainer: Explainer) -> Result<T, SimpleError<'input>>
+        where Explanation: Into<String>, Explainer: FnOnce() -> Explanation;
+
+    /// Responds to: How to solve it.
+    ///
+    /// Turns [None] into a [SimpleError] whose solution is the text returned by *solver*. The
+    /// closure is only called on [None].
+    fn solution_err<Solution, Solver>(self, solver: Solver) -> Result<T, SimpleError<'input>>
+        where Solution: Into<String>, Solver: FnOnce() -> Solution;
+
+    /// Responds to: Where did it happen, usually on parsing errors.
+    ///
+    /// Turns [None] into a [SimpleError] happening at *location_str* as in [SimpleError::at].
+    fn at_err(self, location_str: &'input str) -> Result<T, SimpleError<'input>>;
+}
+
+impl<'input, T> OptionExt<'input, T> for Option<T> {
+    fn explain_err<Explanation, Explainer>(self, explainer: Explainer) -> Result<T, SimpleError<'input>>
+        where Explanation: Into<String>, Explainer: FnOnce() -> Explanation {
+        self.ok_or_else(|| SimpleError::new().error_detail(explainer().into()))
+    }
+
+    fn solution_err<Solution, Solver>(self, solver: Solver) -> Result<T, SimpleError<'input>>
+        where Solution: Into<String>, Solver: FnOnce() -> Solution {
+        self.ok_or_else(|| SimpleError::new().error_detail(SimpleErrorExplanation::new().solution(solver())))
+    }
+
+    fn at_err(self, location_str: &'input str) -> Result<T, SimpleError<'input>> {
+        self.ok_or_else(|| SimpleError::new().at(location_str))
+    }
+}
+
+/// Collects iterators of results without stopping at the first error, so batch validations can
+/// report every failure at once, for example `fields.iter().map(validate_field).collect_errors()`.
+pub trait ResultIteratorExt<'input, T> {
+    /// Gives every [Ok] value in order when there are no errors, otherwise gives a single
+    /// [SimpleError] holding every error as its causes, in the same order they were found, as done
+    /// by collecting them into a [SimpleError].
+    fn collect_errors(self) -> Result<Vec<T>, SimpleError<'input>>;
+}
+
+impl<'input, T, Error: Into<SimpleError<'input>>, Results: Iterator<Item=Result<T, Error>>> ResultIteratorExt<'input, T> for Results {
+    fn collect_errors(self) -> Result<Vec<T>, SimpleError<'input>> {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for result in self {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(error.into()),
+            }
+        }
+        if errors.is_empty() { Ok(values) } else { Err(errors.into_iter().collect()) }
+    }
+}
+
+/// Error detail replacing the solution given by another error detail, used by
+/// [ResultExt::solution_err].
+#[derive(Debug)]
+struct SolvedDetail<'input> {
+    detail: Option<Arc<dyn SimpleErrorDetail + 'input>>,
+    solution: String,
+}
+
+impl<'input> SimpleErrorDetail for SolvedDetail<'input> {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        self.detail.as_ref()
+            .map(|detail| detail.explain_error())
+            .unwrap_or_default()
+            .solution(self.solution.clone())
+    }
+
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.detail.as_ref().map(|detail| detail.extra_fields()).unwrap_or_default()
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        self.detail.as_ref().and_then(|detail| detail.as_any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn results_and_options_are_explained_inline() {
+        let input = "let a = b";
+        let result: Result<(), String> = Err("Variable b doesn't exist".to_string());
+        let error = result.explain_err(|| "Couldn't run the script").solution_err(|| "Declare b").at_err(&input[4..9]).unwrap_err();
+        assert_eq!(error.as_display_struct(false).as_display_string(),
+                   "At: a = b\nError: Couldn't run the script\nSolution: Declare b\nCause: \n  - Error: Variable b doesn't exist");
+        assert_eq!(None::<u8>.explain_err(|| "Missing value").unwrap_err().as_display_struct(false).as_display_string(), "Error: Missing value");
+        assert_eq!(Some(3).explain_err(|| "Missing value").unwrap(), 3);
+    }
+
+    #[test]
+    fn collected_results_give_every_value_or_every_error() {
+        let values: Result<Vec<u8>, SimpleError> = vec![Ok::<u8, String>(1), Ok(2)].into_iter().collect_errors();
+        assert_eq!(values.unwrap(), [1, 2]);
+        let errors = vec![Ok(1), Err("Missing name".to_string()), Err("Missing age".to_string())].into_iter().collect_errors().unwrap_err();
+        assert_eq!(errors.summary(), "Unexplained error (2 causes: Missing name, Missing age)");
+    }
+
+    #[test]
+    fn solved_errors_can_still_be_downcast_to_their_detail() {
+        let result: Result<(), SimpleError> = Err(SimpleError::new().error_detail("Missing ;".to_string()));
+        let error = result.solution_err(|| "Add a ;").unwrap_err();
+        assert_eq!(error.downcast_detail::<String>().map(String::as_str), Some("Missing ;"));
+        assert!(error.detail_is::<String>());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn solved_errors_keep_the_extra_fields_of_their_detail() {
+        #[derive(Debug)]
+        struct UnexpectedToken;
+
+        impl SimpleErrorDetail for UnexpectedToken {
+            fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+                SimpleErrorExplanation::new().explanation("Unexpected token")
+            }
+
+            fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+                serde_json::Map::from_iter([("token".into(), "}".into())])
+            }
+        }
+
+        let result: Result<(), SimpleError> = Err(SimpleError::new().error_detail(UnexpectedToken));
+        let display = result.solution_err(|| "Remove the }").unwrap_err().as_display_struct(false);
+        let serialized = serde_json::to_value(&display).unwrap();
+        assert_eq!(serialized["token"], "}");
+        assert_eq!(serialized["solution"], "Remove the }");
+        let deserialized: crate::SimpleErrorDisplayInfo = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, display);
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(feature = "std")]
+use core::cell::Cell;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How serious an error is, ordered from the least serious, [Severity::Hint], up to the most
+/// serious, [Severity::Error], which is the default.
+///
+/// The severity of an error is given through [crate::SimpleErrorExplanation::severity], and it
+/// changes the label of the line explaining the error, like 'Warning: Variable x is never used'.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// Suggestion that might improve the input, but that isn't wrong.
+    Hint,
+    /// Information about the input.
+    Info,
+    /// Something that is likely wrong, but that doesn't prevent the input from being processed.
+    Warning,
+    /// Something that is wrong and prevents the input from being processed.
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// Gives the default label for this severity, like 'Warning'.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Severity::Hint => "Hint",
+            Severity::Info => "Info",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    const fn to_u8(severity: Option<Severity>) -> u8 {
+        match severity {
+            None => 0,
+            Some(Severity::Hint) => 1,
+            Some(Severity::Info) => 2,
+            Some(Severity::Warning) => 3,
+            Some(Severity::Error) => 4,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    const fn from_u8(severity: u8) -> Option<Severity> {
+        match severity {
+            1 => Some(Severity::Hint),
+            2 => Some(Severity::Info),
+            3 => Some(Severity::Warning),
+            4 => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static MIN_DISPLAY_SEVERITY: Cell<Option<Severity>> = const { Cell::new(None) };
+}
+
+#[cfg(not(feature = "std"))]
+static MIN_DISPLAY_SEVERITY: AtomicU8 = AtomicU8::new(Severity::to_u8(None));
+
+/// Sets the minimum severity errors must have to be shown when displayed through their [Display]
+/// implementation, errors less serious than this are left out along with their causes, and
+/// [None], the default, shows all of them.
+///
+/// This lets third party code simply doing `println!("{error}")` honor modes like a quiet mode
+/// where only errors are shown, a minimum severity set on the displayed error through
+/// [crate::SimpleError::min_display_severity] takes precedence over this one.
+///
+/// When using the std feature, enabled by default, it is set for the current thread only, so tests
+/// and threads rendering errors don't affect each other, otherwise, it is set for the whole
+/// program.
+///
+/// [Display]: core::fmt::Display
+pub fn set_min_display_severity(min_severity: Option<Severity>) {
+    #[cfg(feature = "std")]
+    MIN_DISPLAY_SEVERITY.with(|current| current.set(min_severity));
+    #[cfg(not(feature = "std"))]
+    MIN_DISPLAY_SEVERITY.store(Severity::to_u8(min_severity), Ordering::Relaxed);
+}
+
+/// Returns the minimum severity set through [set_min_display_severity].
+pub fn min_display_severity() -> Option<Severity> {
+    #[cfg(feature = "std")]
+    return MIN_DISPLAY_SEVERITY.with(Cell::get);
+    #[cfg(not(feature = "std"))]
+    return Severity::from_u8(MIN_DISPLAY_SEVERITY.load(Ordering::Relaxed));
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_display_severity_is_set_per_thread() {
+        set_min_display_severity(Some(Severity::Error));
+        let other_thread_severity = std::thread::spawn(min_display_severity).join().unwrap();
+        assert_eq!((min_display_severity(), other_thread_severity), (Some(Severity::Error), None));
+        set_min_display_severity(None);
+    }
+}
@@ -1,13 +1,23 @@
-use alloc::collections::VecDeque;
+use alloc::borrow::Cow;
+use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::any::Any;
 use core::fmt::{Display, Formatter};
 
+use crate::color_choice::ColorChoice;
+use crate::color_override::current_color_override;
+use crate::display_options::{DisplayMode, DisplayOptions};
+use crate::formatting::{bracket_substrings, clip_to, escape_bytes, split_excluded};
+use crate::severity::{min_display_severity, Severity};
 use crate::simple_error_detail::SimpleErrorDetail;
-use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+use crate::simple_error_display_info::{LabelDisplayInfo, SimpleErrorDisplayInfo};
 use crate::simple_error_explanation::SimpleErrorExplanation;
+use crate::span::{last_char_offset, line_and_column, lines_around, Location, SourceId, Span};
+use crate::suggestion::{apply_suggestions, Applicability, SuggestionConflict};
+use crate::timestamp::Timestamp;
 
 /// Holds information to explain an error, such as its detail (What happened and how to solve it),
 /// what errors caused this error, or for parsing errors, at which lines and column did they start /
@@ -19,16 +29,65 @@ pub struct SimpleError<'input> {
     start_point_of_error: Option<(usize, usize)>,
     end_point_of_error: Option<(usize, usize)>,
     causes: Vec<SimpleError<'input>>,
+    source: Option<&'input str>,
+    source_id: Option<SourceId>,
+    file: Option<String>,
+    metadata: Vec<(String, String)>,
+    timestamp: Option<Timestamp>,
+    uncolorized: Vec<Location<'input>>,
+    min_display_severity: Option<Severity>,
+    #[cfg(feature = "std")]
+    backtrace: Option<Arc<std::backtrace::Backtrace>>,
+    #[cfg(feature = "std")]
+    source_display: SourceDisplay,
 }
 
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Display of an error kept once it is given as the [std::error::Error::source] of the error it
+/// causes, which is left empty when cloning the error, so errors cloned out of an error tree and
+/// changed afterward don't keep a stale display.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct SourceDisplay(std::sync::OnceLock<SimpleErrorDisplayInfo>);
+
+#[cfg(feature = "std")]
+impl Clone for SourceDisplay {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for SourceDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SourceDisplay(..)")
+    }
+}
+
+/// Creates a SimpleError giving the same display as *value*, including its labels, suggestions and
+/// causes, so deserialized errors can be stacked as causes of other errors and displayed again with
+/// other options.
 impl From<SimpleErrorDisplayInfo> for SimpleError<'_> {
     fn from(value: SimpleErrorDisplayInfo) -> Self {
+        let error_detail = DisplayedDetail::of(&value).map(|detail| Arc::new(detail) as Arc<dyn SimpleErrorDetail>);
         let mut res = Self{
-            where_: value.at.map(|at|At::Owned(at)),
-            error_detail: if value.reason.is_some()||value.solution.is_some(){Some(Arc::new((value.reason, value.solution)))}else{None},
+            where_: value.at.map(At::Owned),
+            error_detail,
             start_point_of_error: value.on_line_and_column,
             end_point_of_error: value.up_to_line_an_column,
-            causes: value.explained_causes.into_iter().map(|cause|SimpleError::from(cause)).collect(),
+            causes: value.explained_causes.into_iter().map(SimpleError::from).collect(),
+            source: None,
+            source_id: None,
+            file: value.file,
+            metadata: value.metadata,
+            timestamp: value.timestamp,
+            uncolorized: Vec::new(),
+            min_display_severity: None,
+            #[cfg(feature = "std")]
+            backtrace: None,
+            #[cfg(feature = "std")]
+            source_display: SourceDisplay::default(),
         };
         for _ in 0..value.unexplained_causes{
             res.causes.push(SimpleError::new())
@@ -37,17 +96,101 @@ impl From<SimpleErrorDisplayInfo> for SimpleError<'_> {
     }
 }
 
+/// Detail of an error rebuilt from a display, holding the labels and suggestions already resolved
+/// by said display, since they can't be given again through a [SimpleErrorExplanation] without the
+/// source they point at.
+#[derive(Debug)]
+struct DisplayedDetail {
+    explanation: SimpleErrorExplanation<'static>,
+    labels: Vec<LabelDisplayInfo>,
+    suggestions: Vec<LabelDisplayInfo>,
+    backtrace: Option<String>,
+    source_lines: Vec<(usize, String)>,
+    #[cfg(feature = "serde")]
+    extra_fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl DisplayedDetail {
+    /// Rebuilds the detail of a display, if it holds any.
+    fn of(value: &SimpleErrorDisplayInfo) -> Option<Self> {
+        #[cfg(feature = "serde")]
+        let has_extra_fields = !value.extra_fields.is_empty() || value.data.is_some();
+        #[cfg(not(feature = "serde"))]
+        let has_extra_fields = false;
+        if value.reason.is_none() && value.solution.is_none() && value.solution_steps.is_empty() && value.code.is_none() && value.severity == Severity::Error && value.notes.is_empty() && value.helps.is_empty() && value.doc_url.is_none()
+            && value.labels.is_empty() && value.suggestions.is_empty() && value.backtrace.is_none() && value.source_lines.is_empty() && !has_extra_fields {
+            return None;
+        }
+        let mut explanation = SimpleErrorExplanation::new();
+        explanation.explanation = value.reason.clone();
+        explanation.solution = value.solution.clone();
+        explanation.solution_steps = value.solution_steps.clone();
+        explanation.code = value.code.clone();
+        explanation.severity = value.severity;
+        explanation.notes = value.notes.clone();
+        explanation.helps = value.helps.clone();
+        explanation.doc_url = value.doc_url.clone();
+        #[cfg(feature = "serde")]
+        {
+            explanation.data = value.data.clone();
+        }
+        Some(Self {
+            explanation,
+            labels: value.labels.clone(),
+            suggestions: value.suggestions.clone(),
+            backtrace: value.backtrace.clone(),
+            source_lines: value.source_lines.clone(),
+            #[cfg(feature = "serde")]
+            extra_fields: value.extra_fields.clone(),
+        })
+    }
+}
+
+impl SimpleErrorDetail for DisplayedDetail {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        self.explanation.clone()
+    }
+
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.extra_fields.clone()
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+}
+
+/// Describes a labeled place of the source along with its message.
+fn label_display_info(location: Location, source: Option<&str>, message: String) -> LabelDisplayInfo {
+    let at = location.text(source)
+        .map(|text| format!("'{text}'"))
+        .or_else(|| match location {
+            Location::Span(span) => Some(format!("bytes {} up to {}", span.start, span.end)),
+            Location::Str(_) => None,
+        });
+    let on_line_and_column = source
+        .and_then(|source| location.span(source).map(|span| line_and_column(source, span.start)));
+    LabelDisplayInfo { at, on_line_and_column, message, applicability: None }
+}
+
+/// Span of an error inside its source, along with the lines and columns it starts and ends at.
+pub(crate) type ResolvedPosition = (Option<Span>, Option<(usize, usize)>, Option<(usize, usize)>);
+
 #[derive(Debug, Clone)]
 enum At<'input> {
     Referenced(&'input str),
     Owned(String),
+    Span(Span),
+    Bytes(&'input [u8]),
 }
 
 impl <'input> At<'input> {
-    fn as_str(&self)->&str{
+    fn as_str(&self)->Option<&str>{
         match self{
-            At::Referenced(referenced) => {*referenced}
-            At::Owned(owned) => {&*owned}
+            At::Referenced(referenced) => {Some(*referenced)}
+            At::Owned(owned) => {Some(owned)}
+            At::Span(_) | At::Bytes(_) => {None}
         }
     }
 }
@@ -61,26 +204,121 @@ impl<'input, T: SimpleErrorDetail + 'input> From<T> for SimpleError<'input> {
     }
 }
 
+/// Creates a SimpleError whose details is the first value of the pair, happening at the bytes given
+/// by the second value (See [SimpleError::at_span]), this is useful along with
+/// [SimpleError::chain_from].
+impl<'input, T: SimpleErrorDetail + 'input> From<(T, Span)> for SimpleError<'input> {
+    /// Creates a SimpleError whose details is the first value, happening at the second value
+    fn from((value, span): (T, Span)) -> Self {
+        SimpleError::new().error_detail(value).at_span(span)
+    }
+}
+
+/// Creates an error without a detail having every error of the iterator as its causes, so the
+/// errors found on a validation pass can be gathered through `errors.into_iter().collect()`, see
+/// [SimpleError::aggregate] to give this error a detail.
+impl<'input> FromIterator<SimpleError<'input>> for SimpleError<'input> {
+    /// Creates an error without a detail having every error of the iterator as its causes.
+    fn from_iter<Errors: IntoIterator<Item=SimpleError<'input>>>(errors: Errors) -> Self {
+        let mut res = SimpleError::new();
+        errors.into_iter().for_each(|error| res.add_cause(error));
+        res
+    }
+}
+
 /// This is only implemented when using the std feature, enabled by default.
 ///
 /// SimpleError implements [std::error::Error] as it also implements [Display] and [Debug].
+///
+/// Since [std::error::Error::source] can only return `'static` errors, and SimpleErrors might borrow
+/// their input, their source is the first explained cause displayed without colors as an owned
+/// [SimpleErrorDisplayInfo], whose source is its own first explained cause, so tools walking error
+/// chains, like `anyhow`, traverse the error tree through the first cause of every error.
+///
+/// Every cause is displayed on its own, so causes whose positions are only known through the
+/// source of the errors they cause (See [SimpleError::with_source]) are given without them, and
+/// the display of every cause is computed once and kept for later calls.
 #[cfg(feature = "std")]
-impl<'input> std::error::Error for SimpleError<'input> {}
+impl<'input> std::error::Error for SimpleError<'input> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.causes.iter()
+            .map(|cause| cause.source_display.0.get_or_init(|| cause.as_display_struct(ColorChoice::Never)))
+            .find(|cause| cause.is_explained())
+            .map(|cause| cause as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Creates a SimpleError from a boxed std error through [SimpleError::from_std_error].
+#[cfg(feature = "std")]
+impl From<Box<dyn std::error::Error + Send + Sync>> for SimpleError<'_> {
+    /// Creates a SimpleError from a boxed std error through [SimpleError::from_std_error].
+    fn from(value: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        SimpleError::from_std_error(&*value)
+    }
+}
 
 /// SimpleErrors can display through the [SimpleErrorDisplayInfo] struct calling to
 /// [SimpleErrorDisplayInfo::as_display_string].
 impl<'input> Display for SimpleError<'input> {
     /// SimpleErrors can display through the [SimpleErrorDisplayInfo] struct calling to
     /// [SimpleErrorDisplayInfo::as_display_string].
+    ///
+    /// Errors less serious than the minimum severity set through
+    /// [SimpleError::min_display_severity] or [crate::severity::set_min_display_severity] are left
+    /// out, displaying nothing if this error itself is less serious than it.
+    ///
+    /// Colors are kept when the standard error is a terminal or when they are forced through
+    /// `colored::control::set_override(true)`, see [ColorChoice::Stderr].
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.write_str(&self.as_display_struct(true).as_display_string())
+        let display = self.as_display_struct(ColorChoice::Stderr);
+        let display = match self.min_display_severity.or_else(min_display_severity) {
+            Some(min_severity) => display.filtered_by_min_severity(min_severity),
+            None => Some(display),
+        };
+        match display {
+            Some(display) => f.write_str(&display.as_display_string()),
+            None => Ok(()),
+        }
     }
 }
 
 impl<'input> SimpleError<'input> {
     /// Creates a new SimpleError where no information is given.
     pub fn new() -> Self {
-        Self { where_: None, error_detail: None, start_point_of_error: None, end_point_of_error: None, causes: Vec::new() }
+        Self {
+            where_: None,
+            error_detail: None,
+            start_point_of_error: None,
+            end_point_of_error: None,
+            causes: Vec::new(),
+            source: None,
+            source_id: None,
+            file: None,
+            metadata: Vec::new(),
+            timestamp: None,
+            uncolorized: Vec::new(),
+            min_display_severity: None,
+            #[cfg(feature = "std")]
+            backtrace: None,
+            #[cfg(feature = "std")]
+            source_display: SourceDisplay::default(),
+        }
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Creates a SimpleError explained by the [Display] text of *error*, whose causes are created
+    /// the same way by walking its [std::error::Error::source] chain, this way, existing std errors
+    /// can be stacked on error trees without implementing [SimpleErrorDetail] for them.
+    #[cfg(feature = "std")]
+    pub fn from_std_error(error: &dyn std::error::Error) -> Self {
+        let mut res = SimpleError::new().error_detail(error.to_string());
+        if let Some(source) = error.source() {
+            res.add_cause(SimpleError::from_std_error(source));
+        }
+        res
     }
 
     /// Responds to: What and how to solve it.
@@ -101,12 +339,149 @@ impl<'input> SimpleError<'input> {
 
     /// Responds to: Where did it happen, usually on parsing errors.
     ///
-    /// Adds a referenced string to show where the error happened, for example 'At: let a = ...'.
+    /// Adds an owned string to show where the error happened, for example 'At: let a = ...', this
+    /// is useful for errors found on strings built while parsing, like normalized or decoded input,
+    /// which can't be referenced by the error. Colorization markers given as references don't apply
+    /// to owned strings, see [SimpleErrorExplanation::colorization_marker_in_at] instead.
     pub fn at_owned<T:ToString>(mut self, location_str: T) -> Self {
         self.where_ = Some(At::Owned(location_str.to_string()));
         self
     }
 
+    /// Responds to: Where did it happen, usually on parsing errors.
+    ///
+    /// Adds a string that might be either referenced or owned to show where the error happened,
+    /// behaving as [SimpleError::at] for borrowed strings, so they keep being colorized by
+    /// references into them, and as [SimpleError::at_owned] for owned ones.
+    pub fn at_cow(mut self, location_str: Cow<'input, str>) -> Self {
+        self.where_ = Some(match location_str {
+            Cow::Borrowed(location_str) => At::Referenced(location_str),
+            Cow::Owned(location_str) => At::Owned(location_str),
+        });
+        self
+    }
+
+    /// Responds to: Where did it happen, usually on parsing errors.
+    ///
+    /// Adds a referenced byte string to show where the error happened, bytes that aren't valid
+    /// UTF-8 and non-printable characters are shown as escapes, like `\x00`, and highlighted when
+    /// displaying with colors, as these invisible bytes are often the actual cause of the error.
+    pub fn at_bytes(mut self, location_bytes: &'input [u8]) -> Self {
+        self.where_ = Some(At::Bytes(location_bytes));
+        self
+    }
+
+    /// Responds to: Where did it happen, usually on parsing errors.
+    ///
+    /// Indicates the bytes of the input where the error happened, this is an alternative to
+    /// [SimpleError::at] for errors built from offsets, such as those produced by lexers not
+    /// retaining references to the input.
+    ///
+    /// These offsets refer to the input registered through [SimpleError::with_source] on this
+    /// error or any of its ancestors, if there isn't any, it is shown as the range of bytes it
+    /// covers, for example 'At: bytes 4 up to 9'.
+    pub fn at_span<SpanLike: Into<Span>>(mut self, span: SpanLike) -> Self {
+        self.where_ = Some(At::Span(span.into()));
+        self
+    }
+
+    /// Registers the whole input where this error and its causes happened, so that for every one
+    /// of them whose [SimpleError::at] is a reference taken from *source*, or whose
+    /// [SimpleError::at_span] is given, their start and end points are computed automatically
+    /// instead of having to call [SimpleError::start_point_of_error] and
+    /// [SimpleError::end_point_of_error] by hand, where lines and columns start from 1.
+    ///
+    /// It also allows errors located through [SimpleError::at_span] to show the text they refer to,
+    /// and to apply their [SimpleErrorExplanation::colorization_marker_span] markers.
+    ///
+    /// Causes can register their own source, which is then used for them and their own causes.
+    pub fn with_source(mut self, source: &'input str) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Indicates which of the inputs of the application this error happened in, like a file of a
+    /// project, so reports can tell apart the errors of each input, such as when recomputing only
+    /// the errors of changed files through [crate::Report::retain_for_sources], or to give errors
+    /// the sources registered on a [crate::SourceMap].
+    pub fn in_source(mut self, source_id: SourceId) -> Self {
+        self.source_id = Some(source_id);
+        self
+    }
+
+    /// Indicates the name of the file this error happened in, like `config/main.dsl`, which is
+    /// shown on the 'Position' line of this error, like in 'In file config/main.dsl, on line 3 and
+    /// column 1', so errors of a multi-file project tell which file they come from.
+    pub fn in_file<Name: Into<String>>(mut self, name: Name) -> Self {
+        self.file = Some(name.into());
+        self
+    }
+
+    /// Attaches the pair *key* and *value* to this error, like a request ID, a tenant name or a
+    /// retry hint, so services can add context to errors without rewriting their explanations.
+    ///
+    /// Metadata is kept on its [SimpleErrorDisplayInfo], where it is serialized, and it is shown on
+    /// a 'Details' block when [DisplayOptions::show_metadata] is set.
+    pub fn with_metadata<Key: Into<String>, Value: Into<String>>(mut self, key: Key, value: Value) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Records the moment this error happened at, either as milliseconds since the Unix epoch or,
+    /// when using the std feature, as a `SystemTime`, like `.timestamp(SystemTime::now())`, see
+    /// [Timestamp].
+    ///
+    /// The moment isn't displayed, but it is kept on its [SimpleErrorDisplayInfo], where it is
+    /// serialized, so archives of serialized errors record when each of them happened.
+    pub fn timestamp<Time: Into<Timestamp>>(mut self, time: Time) -> Self {
+        self.timestamp = Some(time.into());
+        self
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Records the backtrace of where this function is called from, so errors can be traced back
+    /// to the code creating them, which is especially useful for unexplained errors showing up on
+    /// production logs, see [SimpleError::backtrace].
+    ///
+    /// As with [std::backtrace::Backtrace::capture], the backtrace is only captured when the
+    /// `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables enable it. Backtraces are
+    /// shown along with the error when [DisplayOptions::show_backtraces] is set, and they are
+    /// kept as text on [SimpleErrorDisplayInfo], so they are serialized too.
+    #[cfg(feature = "std")]
+    pub fn capture_backtrace(self) -> Self {
+        self.with_backtrace(std::backtrace::Backtrace::capture())
+    }
+
+    /// Records *backtrace* as the backtrace of this error, see [SimpleError::capture_backtrace].
+    #[cfg(feature = "std")]
+    pub(crate) fn with_backtrace(mut self, backtrace: std::backtrace::Backtrace) -> Self {
+        self.backtrace = Some(Arc::new(backtrace));
+        self
+    }
+
+    /// Excludes the part of the input at *location* from colorization and emphasis, so neither the
+    /// colorization markers nor [SimpleErrorExplanation::mark_span] style it, regardless of which
+    /// markers the error detail gives, this is useful to control exactly which styling reaches the
+    /// terminal, like for string literals holding sequences that look like ANSI codes, or for
+    /// personal data.
+    ///
+    /// *location* is either a reference taken from the input or a [Span] of the input registered
+    /// through [SimpleError::with_source], and it also applies to the causes of this error.
+    pub fn never_colorize<LocationLike: Into<Location<'input>>>(mut self, location: LocationLike) -> Self {
+        self.uncolorized.push(location.into());
+        self
+    }
+
+    /// Excludes every part of the input at *locations* from colorization and emphasis, see
+    /// [SimpleError::never_colorize].
+    pub fn never_colorize_all<LocationLike, Locations>(mut self, locations: Locations) -> Self
+        where LocationLike: Into<Location<'input>>,
+              Locations: IntoIterator<Item=LocationLike> {
+        self.uncolorized.extend(locations.into_iter().map(|location| location.into()));
+        self
+    }
+
     /// Responds to: Where does this error starts to happen, usually on parsing errors.
     ///
     /// For example: 'From line 3 and column 5'.
@@ -131,6 +506,40 @@ impl<'input> SimpleError<'input> {
         self
     }
 
+    /// Responds to: Why did it happen.
+    ///
+    /// Builds a chain of errors from *errors*, given from the outermost to the innermost, where
+    /// every error is the only cause of the one before it, such as include stacks where file A
+    /// included file B, which failed at some line, giving the outermost error, or None when
+    /// *errors* is empty.
+    ///
+    /// Errors might be given as pairs of a detail and a [Span], like
+    /// `SimpleError::chain_from([(included_a, span_a), (failed_b, span_b)])`.
+    pub fn chain_from<PError: Into<SimpleError<'input>>, Errors: IntoIterator<Item=PError>>(errors: Errors) -> Option<Self> {
+        let errors = errors.into_iter().map(|error| error.into()).collect::<Vec<_>>();
+        errors.into_iter().rev().reduce(|cause, error| error.with_cause(cause))
+    }
+
+    /// Responds to: Why did it happen.
+    ///
+    /// Creates an error whose detail is *root_detail* having every error of *errors* as its
+    /// causes, such as gathering every error found on a validation pass under an error saying the
+    /// validation failed.
+    pub fn aggregate<PError: Into<SimpleError<'input>>, Errors: IntoIterator<Item=PError>, ErrorDetail: SimpleErrorDetail + 'input>(errors: Errors, root_detail: ErrorDetail) -> Self {
+        errors.into_iter().map(|error| error.into()).collect::<SimpleError>().error_detail(root_detail)
+    }
+
+    /// Responds to: Why did it happen.
+    ///
+    /// Adds the error inside *result* as a cause of this one when it is an [Err], doing nothing
+    /// when it is [Ok].
+    pub fn caused_by_result<T, PError: Into<SimpleError<'input>>>(mut self, result: Result<T, PError>) -> Self {
+        if let Err(cause) = result {
+            self.add_cause(cause.into());
+        }
+        self
+    }
+
     /// Removes all the causes on why this error happened.
     pub fn without_causes(mut self) -> Self {
         self.causes = Vec::new();
@@ -144,106 +553,448 @@ impl<'input> SimpleError<'input> {
         self.causes.push(cause.into());
     }
 
-    fn __as_display_struct(&self) -> SimpleErrorDisplayInfo {
+    /// Returns the text where this error happened, and if said text belongs to *source* (Or it was
+    /// given as a span), the span covering it.
+    fn resolve_at<'resolved>(&'resolved self, source: Option<&'resolved str>) -> (Option<&'resolved str>, Option<Span>) {
+        match &self.where_ {
+            None => (None, None),
+            Some(At::Referenced(referenced)) => (Some(*referenced), source.and_then(|source| Span::of(source, referenced))),
+            Some(At::Owned(owned)) => (Some(owned.as_str()), None),
+            Some(At::Span(span)) => (source.and_then(|source| span.slice(source)), Some(*span)),
+            Some(At::Bytes(_)) => (None, None),
+        }
+    }
+
+    /// Returns the span of this error inside *source* along with the lines and columns it starts
+    /// and ends at, either given explicitly or computed from said span.
+    pub(crate) fn resolve_position(&self, source: Option<&str>) -> ResolvedPosition {
+        let (_, where_span) = self.resolve_at(source);
+        let source_and_span = source.zip(where_span);
+        let start_point_of_error = self.start_point_of_error
+            .or_else(|| source_and_span.map(|(source, span)| line_and_column(source, span.start)));
+        let end_point_of_error = self.end_point_of_error
+            .or_else(|| source_and_span.filter(|(_, span)| !span.is_empty())
+                .map(|(source, span)| line_and_column(source, last_char_offset(source, span.end))));
+        (where_span, start_point_of_error, end_point_of_error)
+    }
+
+    /// Explains this error once for every coloring on *colorings*, giving a [SimpleErrorDisplayInfo]
+    /// for each of them in the same order, while the error tree is only traversed once.
+    fn __as_display_structs(&self, colorings: &[bool], context_lines: usize, inherited_source: Option<&'input str>, inherited_uncolorized: &[Location<'input>]) -> Vec<SimpleErrorDisplayInfo> {
+        let source = self.source.or(inherited_source);
         let error_explanation = self.error_detail.as_ref()
-            .map(|error_detail| error_detail.explain_error())
+            .map(|error_detail| error_detail.explain_error().evaluated())
             .unwrap_or_default();
+        #[cfg(feature = "serde")]
+        let data = error_explanation.data.clone();
 
-        #[cfg(feature = "colorization")]
-            let SimpleErrorExplanation { whole_marker: general_colorizer, explanation: error_description, solution, colorization_markers: substring_colorizers } = error_explanation;
-        #[cfg(not(feature = "colorization"))]
-            let SimpleErrorExplanation { explanation: error_description, solution, .. } = error_explanation;
+        #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+            let SimpleErrorExplanation { whole_marker: general_colorizer, explanation: error_description, solution, solution_steps, code, severity, notes, helps, doc_url, labels, suggestions, colorization_markers: substring_colorizers, span_colorization_markers, at_colorization_markers, marked_spans, .. } = error_explanation;
+        #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+            let SimpleErrorExplanation { explanation: error_description, solution, solution_steps, code, severity, notes, helps, doc_url, labels, suggestions, marked_spans, .. } = error_explanation;
 
+        let (where_text, where_span) = self.resolve_at(source);
+        let display_where = |colorize: bool| match (where_text, where_span) {
+            (Some(where_), _) => {
+                let uncolorized = self.uncolorized.iter().chain(inherited_uncolorized)
+                    .filter_map(|location| location.text(source))
+                    .collect::<Vec<_>>();
+                let parts = split_excluded(where_, &uncolorized);
+                let emphasized_spans = parts.iter()
+                    .filter(|(_, is_uncolorized)| !is_uncolorized)
+                    .flat_map(|(part, _)| marked_spans.iter().filter_map(|span| clip_to(span, part)))
+                    .collect::<Vec<_>>();
+                #[cfg(feature = "colorization")]
+                    let where_ = if colorize && colored::control::SHOULD_COLORIZE.should_colorize() {
+                    let span_colorizers = span_colorization_markers.iter().cloned()
+                        .filter_map(|(span, colorizer)| source.and_then(|source| span.slice(source)).map(|string| (string, colorizer)));
+                    let at_colorizers = at_colorization_markers.iter().cloned()
+                        .filter_map(|(span, colorizer)| span.slice(where_).map(|string| (string, colorizer)));
+                    let substring_colorizers = substring_colorizers.iter().cloned()
+                        .chain(span_colorizers)
+                        .chain(at_colorizers)
+                        .chain(emphasized_spans.iter().map(|span| (*span, string_colorization::style::Underline.into())))
+                        .collect::<Vec<_>>();
+                    parts.iter()
+                        .map(|(part, is_uncolorized)| if *is_uncolorized {
+                            part.to_string()
+                        } else {
+                            string_colorization::colorize(part, general_colorizer.clone(), substring_colorizers.iter()
+                                .filter_map(|(substring, colorizer)| clip_to(substring, part).map(|substring| (substring, colorizer.clone())))
+                                .collect())
+                        })
+                        .collect()
+                } else {
+                    bracket_substrings(where_, &emphasized_spans, ">>>", "<<<")
+                };
+                #[cfg(all(feature = "colorization-anstyle", not(feature = "colorization")))]
+                    let where_ = if colorize {
+                    let span_colorizers = span_colorization_markers.iter().cloned()
+                        .filter_map(|(span, style)| source.and_then(|source| span.slice(source)).map(|string| (string, style)));
+                    let at_colorizers = at_colorization_markers.iter().cloned()
+                        .filter_map(|(span, style)| span.slice(where_).map(|string| (string, style)));
+                    let substring_colorizers = substring_colorizers.iter().cloned().chain(span_colorizers).chain(at_colorizers).collect::<Vec<_>>();
+                    parts.iter()
+                        .map(|(part, is_uncolorized)| if *is_uncolorized {
+                            part.to_string()
+                        } else {
+                            crate::anstyle_colorization::colorize(part, general_colorizer, substring_colorizers.iter()
+                                .filter_map(|(substring, style)| clip_to(substring, part).map(|substring| (substring, *style)))
+                                .collect(), &emphasized_spans)
+                        })
+                        .collect()
+                } else {
+                    bracket_substrings(where_, &emphasized_spans, ">>>", "<<<")
+                };
+                #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+                    let where_ = bracket_substrings(where_, &emphasized_spans, ">>>", "<<<");
+                Some(where_)
+            }
+            (None, Some(span)) => Some(format!("bytes {} up to {}", span.start, span.end)),
+            (None, None) => match self.where_ {
+                Some(At::Bytes(bytes)) => Some(escape_bytes(bytes, colorize)),
+                _ => None,
+            },
+        }.filter(|string| !string.is_empty()).map(|string| string.trim().to_string());
+        let wheres = colorings.iter().map(|colorize| display_where(*colorize)).collect::<Vec<_>>();
 
-        #[cfg(feature = "colorization")]
-            let where_ = self.where_.clone()
-            .map(|where_| string_colorization::colorize(where_.as_str(), general_colorizer, substring_colorizers))
-            .filter(|string| !string.is_empty()).map(|string| string.trim().to_string());
-        #[cfg(not(feature = "colorization"))]
-            let where_ = self.where_.clone()
-            .filter(|string| !string.as_str().is_empty()).map(|string| string.as_str().trim().to_string());
+        let (_, start_point_of_error, end_point_of_error) = self.resolve_position(source);
 
+        let labels = labels.into_iter()
+            .map(|(label_location, message)| label_display_info(label_location, source, message))
+            .collect();
+        let suggestions = suggestions.into_iter()
+            .map(|(suggestion_location, replacement, applicability)| {
+                let message = if replacement.is_empty() { "Remove it".to_string() } else { format!("Replace it with '{replacement}'") };
+                LabelDisplayInfo { applicability, ..label_display_info(suggestion_location, source, message) }
+            })
+            .collect();
 
+        let uncolorized_of_causes = [&self.uncolorized[..], inherited_uncolorized].concat();
         let mut unexplained_causes = 0;
-        let mut explained_causes = self.causes.iter()
-            .map(|cause| cause.__as_display_struct())
-            .filter(|cause| {
-                let is_explained = cause.is_explained();
-                if !is_explained { unexplained_causes += 1 };
-                is_explained
-            })
-            .collect::<Vec<_>>();
-        explained_causes.sort_by_key(|error| error.complexity());
+        let mut explained_causes = colorings.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+        for cause in &self.causes {
+            let cause_displays = cause.__as_display_structs(colorings, context_lines, source, &uncolorized_of_causes);
+            if cause_displays.first().is_some_and(|cause| cause.is_explained()) {
+                explained_causes.iter_mut().zip(cause_displays).for_each(|(explained_causes, cause)| explained_causes.push(cause));
+            } else {
+                unexplained_causes += 1;
+            }
+        }
 
-        SimpleErrorDisplayInfo::new(where_, error_description, solution,
-                                    self.start_point_of_error.clone(), self.end_point_of_error.clone(), unexplained_causes, explained_causes)
+        let mut res = SimpleErrorDisplayInfo::new(None, error_description, solution,
+                                                  start_point_of_error, end_point_of_error, unexplained_causes, Vec::new());
+        res.solution_steps = solution_steps;
+        res.labels = labels;
+        res.suggestions = suggestions;
+        if let Some(displayed_detail) = self.downcast_detail::<DisplayedDetail>() {
+            res.labels.extend(displayed_detail.labels.iter().cloned());
+            res.suggestions.extend(displayed_detail.suggestions.iter().cloned());
+        }
+        res.code = code;
+        res.severity = severity;
+        res.notes = notes;
+        res.helps = helps;
+        res.doc_url = doc_url;
+        res.file = self.file.clone();
+        res.metadata = self.metadata.clone();
+        res.timestamp = self.timestamp;
+        #[cfg(feature = "std")]
+        if let Some(backtrace) = &self.backtrace {
+            res.backtrace = Some(backtrace.to_string());
+        }
+        if let (Some(source), Some((first_line, _)), true) = (source, start_point_of_error, context_lines > 0) {
+            let last_line = end_point_of_error.map(|(last_line, _)| last_line).unwrap_or(first_line);
+            res.source_lines = lines_around(source, first_line, last_line, context_lines);
+        }
+        if let Some(displayed_detail) = self.downcast_detail::<DisplayedDetail>() {
+            res.backtrace = res.backtrace.or_else(|| displayed_detail.backtrace.clone());
+            if res.source_lines.is_empty() {
+                res.source_lines = displayed_detail.source_lines.clone();
+            }
+        }
+        #[cfg(feature = "serde")]
+        if let Some(error_detail) = &self.error_detail {
+            res.extra_fields = error_detail.extra_fields();
+        }
+        #[cfg(feature = "serde")]
+        {
+            res.data = data;
+        }
+        wheres.into_iter().zip(explained_causes)
+            .map(|(at, explained_causes)| SimpleErrorDisplayInfo { at, explained_causes, ..res.clone() })
+            .collect()
     }
 
     /// Turns this SimpleError into a [SimpleErrorDisplayInfo], the string might have terminal color
-    /// if indicated on the variable *colorize*.
+    /// if indicated on the variable *colorize*, being either a [ColorChoice] or a bool (See
+    /// [ColorChoice::from]), unless the current thread is running inside
+    /// [crate::with_color_override], in which case the override is used instead.
+    ///
+    /// When no colors are requested, any escape code is removed from the resulting texts instead of
+    /// toggling `colored`'s global state, so rendering plain errors doesn't affect other threads.
+    ///
+    /// ```rust
+    /// use simple_detailed_error::SimpleError;
+    ///
+    /// colored::control::set_override(true);
+    /// let plain_renderer = std::thread::spawn(|| {
+    ///     let error = SimpleError::new().error_detail("Unexpected \u{1b}[31m;\u{1b}[0m".to_string());
+    ///     (0..1000).all(|_| error.as_display_struct(false).as_display_string() == "Error: Unexpected ;")
+    /// });
+    /// // Meanwhile, colors stay forced for the rest of the threads.
+    /// assert!((0..1000).all(|_| colored::control::SHOULD_COLORIZE.should_colorize()));
+    /// assert!(plain_renderer.join().unwrap());
+    /// ```
     ///
     /// This struct is a serializable one when the feature 'serde' is enabled, making it useful to
     /// share errors through platforms and also save them for future auditing.
-    pub fn as_display_struct(&self, colorize: bool) -> SimpleErrorDisplayInfo {
-        #[cfg(feature = "colorization")]
-            let forced_no_colorization = !colorize && colored::control::SHOULD_COLORIZE.should_colorize();
-        #[cfg(feature = "colorization")]
-        if forced_no_colorization {
-            colored::control::SHOULD_COLORIZE.set_override(false);
-        }
-        let res = self.__as_display_struct();
-        #[cfg(feature = "colorization")]
-        if forced_no_colorization {
-            colored::control::SHOULD_COLORIZE.set_override(true);
+    pub fn as_display_struct<Choice: Into<ColorChoice>>(&self, colorize: Choice) -> SimpleErrorDisplayInfo {
+        self.as_display_struct_with_context(colorize, 0)
+    }
+
+    /// Turns this SimpleError into a [SimpleErrorDisplayInfo] as [SimpleError::as_display_struct]
+    /// does, but also keeping up to *context_lines* lines of the source given on
+    /// [SimpleError::with_source] before and after the lines every error happens on, so they can be
+    /// shown around the error, see [DisplayOptions::context_lines].
+    pub fn as_display_struct_with_context<Choice: Into<ColorChoice>>(&self, colorize: Choice, context_lines: usize) -> SimpleErrorDisplayInfo {
+        let colorize = current_color_override().unwrap_or_else(|| colorize.into().should_colorize());
+        let mut res = self.__as_display_structs(&[colorize], context_lines, None, &[]).pop().unwrap_or_default();
+        if !colorize {
+            res.strip_ansi();
         }
         res
     }
 
+    /// Turns this SimpleError into two [SimpleErrorDisplayInfo]s at once, the first one with
+    /// terminal colors and the second one without them, being the same as calling
+    /// [SimpleError::as_display_struct] with [ColorChoice::Always] and with [ColorChoice::Never],
+    /// but explaining and traversing the error tree only once, which is useful when the error is
+    /// both shown on a terminal and written to a log file.
+    pub fn as_display_structs_both(&self) -> (SimpleErrorDisplayInfo, SimpleErrorDisplayInfo) {
+        let mut displays = self.__as_display_structs(&[true, false], 0, None, &[]).into_iter();
+        let colored = displays.next().unwrap_or_default();
+        let mut plain = displays.next().unwrap_or_default();
+        plain.strip_ansi();
+        (colored, plain)
+    }
+
+    /// Gives a string displaying this error using the labels, indentation and cause separator given
+    /// on *options*, this is the same as calling [SimpleError::as_display_struct_with_context] with
+    /// [DisplayOptions::context_lines] followed by
+    /// [SimpleErrorDisplayInfo::as_display_string_with_options].
+    pub fn as_display_string_with_options<Choice: Into<ColorChoice>>(&self, colorize: Choice, options: &DisplayOptions) -> String {
+        self.as_display_struct_with_context(colorize, options.context_lines).as_display_string_with_options(options)
+    }
+
+    /// Gives a string displaying this error as given by *mode*, this is the same as calling
+    /// [SimpleError::as_display_struct] followed by
+    /// [SimpleErrorDisplayInfo::as_display_string_in_mode], where colors are only kept on
+    /// [DisplayMode::Regular], for example, `error.as_display_string_in_mode(DisplayMode::Snapshot)`
+    /// gives output suitable for golden-file tests.
+    pub fn as_display_string_in_mode(&self, mode: DisplayMode) -> String {
+        self.as_display_struct(mode == DisplayMode::Regular).as_display_string_in_mode(mode)
+    }
+
+    /// Gives a single line summarizing this error and its causes, like `Couldn't compile code (2
+    /// causes: Missing variable x, Missing function f)`, this is the same as calling
+    /// [SimpleError::as_display_struct] without colors followed by
+    /// [SimpleErrorDisplayInfo::summary].
+    pub fn summary(&self) -> String {
+        self.as_display_struct(false).summary()
+    }
+
+    /// Tells whether this error and *other* explain the same, that is, whether they give the same
+    /// explanations, solutions, codes, labels, suggestions and positions, and whether their causes
+    /// do too, in the same order, while colors are ignored. This lets tests assert on the structure
+    /// of errors rather than on their rendered strings.
+    ///
+    /// This is the same as comparing the results of calling [SimpleError::as_display_struct]
+    /// without colors on both errors, so details giving the same explanation are considered equal
+    /// even if they are of different types.
+    pub fn structurally_eq(&self, other: &SimpleError) -> bool {
+        self.as_display_struct(false) == other.as_display_struct(false)
+    }
+
     /// Returns all the errors not holding any other errors, for example, if we had four errors A B
     /// C and D, where A had B and C as causes, and C had D as cause, it would return B and D as
     /// leaf errors as they are the only errors in the error tree not having any sub-errors /
     /// causes.
     pub fn leaf_errors(&self) -> Vec<&Self> {
         if self.causes.is_empty() {
-            return vec![&self];
+            vec![self]
         } else {
             self.causes.iter().flat_map(|cause| cause.leaf_errors()).collect()
         }
     }
 
+    /// Returns all the errors not holding any other errors as [SimpleError::leaf_errors] does,
+    /// along with their ancestors ordered from this error down to the parent of the leaf, for
+    /// example, if we had four errors A B C and D, where A had B and C as causes, and C had D as
+    /// cause, it would return B with A as its only ancestor, and D with A and C as its ancestors,
+    /// so callers can render trails like 'while parsing A → while checking C → D'. When this error
+    /// has no causes, it is returned with no ancestors.
+    pub fn leaf_errors_with_path(&self) -> Vec<(&Self, Vec<&Self>)> {
+        let mut result = Vec::new();
+        self.leaf_errors_with_path_int(&mut Vec::new(), &mut result);
+        result
+    }
+
+    fn leaf_errors_with_path_int<'selflf>(&'selflf self, ancestors: &mut Vec<&'selflf Self>, result: &mut Vec<(&'selflf Self, Vec<&'selflf Self>)>) {
+        if self.causes.is_empty() {
+            result.push((self, ancestors.clone()));
+            return;
+        }
+        ancestors.push(self);
+        self.causes.iter().for_each(|cause| cause.leaf_errors_with_path_int(ancestors, result));
+        ancestors.pop();
+    }
+
     /// In case this error represents an error tree, it returns every leaf error itself, and as
     /// causes of these errors they have their respective ancestors, for example, if we had four
     /// errors A B C and D, where A had B and C as causes, and C had D as cause, then the leafs
     /// would be B and D, for B it returns it with A as a cause, and for D it returns it with C as a
     /// cause, which also will have A as a cause.
+    ///
+    /// Only the errors themselves are copied, not their causes, see [SimpleError::inverted_chains]
+    /// to go through the inverted chains without copying any error.
     pub fn inverted_error_tree(&self) -> Vec<SimpleError<'input>> {
-        let mut result = Default::default();
-        let mut current_stack = Default::default();
-        self.revese_errors_int(&mut result, &mut current_stack);
-
-        result.into_iter().map(|mut errors_stack| {
-            let mut reverse_error = errors_stack.remove(errors_stack.len() - 1).clone().without_causes();
-            while !errors_stack.is_empty() {
-                reverse_error = errors_stack.remove(errors_stack.len() - 1).clone().without_causes().with_cause(reverse_error);
+        self.inverted_chains()
+            .map(|chain| chain.into_iter().rev()
+                .fold(None, |inverted_error: Option<SimpleError<'input>>, error| {
+                    let mut error = error.clone_without_causes();
+                    error.causes.extend(inverted_error);
+                    Some(error)
+                })
+                .unwrap_or_default())
+            .collect()
+    }
+
+    /// Gives a new error tree where every error of this one is replaced by the result of calling
+    /// *map* on it, such as for rewriting explanations before showing them through a public API.
+    ///
+    /// Causes are transformed before the errors they caused, so when *map* is called on an error,
+    /// its causes have already been transformed.
+    pub fn map_tree<Map: FnMut(SimpleError<'input>) -> SimpleError<'input>>(&self, mut map: Map) -> SimpleError<'input> {
+        self.map_tree_int(&mut map)
+    }
+
+    fn map_tree_int<Map: FnMut(SimpleError<'input>) -> SimpleError<'input>>(&self, map: &mut Map) -> SimpleError<'input> {
+        let causes = self.causes.iter().map(|cause| cause.map_tree_int(map)).collect();
+        let mut error = self.clone_without_causes();
+        error.causes = causes;
+        map(error)
+    }
+
+    /// Gives a new error tree where the causes for which *predicate* returns false are dropped
+    /// along with their own causes, such as for stripping internal-only causes before showing an
+    /// error to users. This error is always kept, even if *predicate* would return false for it.
+    pub fn filter_causes<Predicate: FnMut(&SimpleError<'input>) -> bool>(&self, mut predicate: Predicate) -> SimpleError<'input> {
+        self.filter_causes_int(&mut predicate)
+    }
+
+    /// Gives a new error tree where the causes less serious than *min_severity* are dropped along
+    /// with their own causes, as given by their [SimpleError::current_severity], such as for
+    /// hiding warnings and hints unless they are requested. This error is always kept, even if it
+    /// is less serious than *min_severity*.
+    ///
+    /// To only leave them out when displaying, see [DisplayOptions::min_severity].
+    pub fn filter_min_severity(&self, min_severity: Severity) -> SimpleError<'input> {
+        self.filter_causes(|cause| cause.current_severity() >= min_severity)
+    }
+
+    fn filter_causes_int<Predicate: FnMut(&SimpleError<'input>) -> bool>(&self, predicate: &mut Predicate) -> SimpleError<'input> {
+        let mut error = self.clone_without_causes();
+        for cause in &self.causes {
+            if predicate(cause) {
+                error.causes.push(cause.filter_causes_int(predicate));
             }
-            reverse_error
-        }).collect::<Vec<_>>()
+        }
+        error
     }
 
-    fn revese_errors_int<'selflf>(&'selflf self, result: &mut Vec<Vec<&'selflf Self>>, current_stack: &mut VecDeque<&'selflf Self>) {
-        current_stack.push_front(self);
-        if self.causes.is_empty() {
-            result.push(current_stack.clone().into_iter().collect());
-        } else {
-            self.causes.iter().for_each(|cause| cause.revese_errors_int(result, current_stack));
+    fn clone_without_causes(&self) -> SimpleError<'input> {
+        SimpleError {
+            where_: self.where_.clone(),
+            error_detail: self.error_detail.clone(),
+            start_point_of_error: self.start_point_of_error,
+            end_point_of_error: self.end_point_of_error,
+            causes: Vec::new(),
+            source: self.source,
+            source_id: self.source_id,
+            file: self.file.clone(),
+            metadata: self.metadata.clone(),
+            timestamp: self.timestamp,
+            uncolorized: self.uncolorized.clone(),
+            min_display_severity: self.min_display_severity,
+            #[cfg(feature = "std")]
+            backtrace: self.backtrace.clone(),
+            #[cfg(feature = "std")]
+            source_display: SourceDisplay::default(),
         }
-        current_stack.pop_front();
     }
 
     /// Returns the value for the indicated [SimpleError::at], it will be None if you haven't set it
     /// before through said function.
     pub fn current_at(&self) -> Option<&str> {
-        self.where_.as_ref().map(|where_|where_.as_str())
+        self.where_.as_ref().and_then(|where_|where_.as_str())
+    }
+
+    /// Returns the value for the indicated [SimpleError::at_bytes], it will be None if you haven't
+    /// set it before through said function.
+    pub fn current_at_bytes(&self) -> Option<&'input [u8]> {
+        match self.where_ {
+            Some(At::Bytes(bytes)) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the value for the indicated [SimpleError::at_span], it will be None if you haven't
+    /// set it before through said function.
+    pub fn current_at_span(&self) -> Option<Span> {
+        match self.where_ {
+            Some(At::Span(span)) => Some(span),
+            _ => None,
+        }
+    }
+
+    /// Returns the value for the indicated [SimpleError::with_source], it will be None if you
+    /// haven't set it before through said function.
+    pub fn current_source(&self) -> Option<&'input str> {
+        self.source
+    }
+
+    /// Returns the value for the indicated [SimpleError::in_source], it will be None if you haven't
+    /// set it before through said function.
+    pub fn current_source_id(&self) -> Option<SourceId> {
+        self.source_id
+    }
+
+    /// Returns the value for the indicated [SimpleError::in_file], it will be None if you haven't
+    /// set it before through said function.
+    pub fn current_file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns the key and value pairs attached through [SimpleError::with_metadata], in the order
+    /// they were attached.
+    pub fn current_metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Returns the value for the indicated [SimpleError::timestamp], it will be None if you haven't
+    /// set it before through said function.
+    pub fn current_timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+    }
+
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Returns the backtrace recorded through [SimpleError::capture_backtrace], it will be None if
+    /// you haven't called said function.
+    #[cfg(feature = "std")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.backtrace.as_deref()
     }
 
     /// Returns the value for the indicated [SimpleError::error_detail], it will be None if you
@@ -252,6 +1003,21 @@ impl<'input> SimpleError<'input> {
         &self.error_detail
     }
 
+    /// Returns the detail of this error as its concrete type *T*, it will be None if there is no
+    /// detail, if it is of another type, or if it doesn't support downcasting, see
+    /// [SimpleErrorDetail::as_any].
+    pub fn downcast_detail<T: Any>(&self) -> Option<&T> {
+        self.error_detail.as_ref()
+            .and_then(|error_detail| error_detail.as_any())
+            .and_then(|error_detail| error_detail.downcast_ref::<T>())
+    }
+
+    /// Tells if the detail of this error is of type *T*, letting callers branch on specific kinds of
+    /// errors, see [SimpleError::downcast_detail].
+    pub fn detail_is<T: Any>(&self) -> bool {
+        self.downcast_detail::<T>().is_some()
+    }
+
     /// Returns the value for the indicated [SimpleError::start_point_of_error], it will be None if
     /// you haven't set it before through said function.
     pub fn current_start_point_of_error(&self) -> &Option<(usize, usize)> {
@@ -264,6 +1030,51 @@ impl<'input> SimpleError<'input> {
         &self.end_point_of_error
     }
 
+    /// Applies every suggestion given by this error and its causes through
+    /// [SimpleErrorExplanation::suggestion] on *source*, in the order they appear on it, this is
+    /// useful for tools implementing a mode fixing errors automatically.
+    ///
+    /// Suggestions that can't be located on *source* are skipped, and repeated ones are applied
+    /// once, but if two different suggestions replace overlapping parts of *source*, nothing is
+    /// applied and a [SuggestionConflict] is returned instead.
+    pub fn apply_suggestions(&self, source: &str) -> Result<String, SuggestionConflict> {
+        apply_suggestions(self, source, |_| true)
+    }
+
+    /// Applies the suggestions of this error and its causes as [SimpleError::apply_suggestions]
+    /// does, but only those marked as [Applicability::MachineApplicable] through
+    /// [SimpleErrorExplanation::suggestion_with_applicability], which are safe to apply without
+    /// the user reviewing them.
+    pub fn apply_machine_applicable_suggestions(&self, source: &str) -> Result<String, SuggestionConflict> {
+        apply_suggestions(self, source, |applicability| applicability == Some(Applicability::MachineApplicable))
+    }
+
+    /// Sets the minimum severity this error and its causes must have to be shown when displayed
+    /// through [Display], taking precedence over the one set through
+    /// [crate::severity::set_min_display_severity], errors less serious than it are left out.
+    pub fn min_display_severity(mut self, min_severity: Severity) -> Self {
+        self.min_display_severity = Some(min_severity);
+        self
+    }
+
+    /// Returns the severity given by this error's [SimpleErrorExplanation::severity], errors without
+    /// an error detail are considered a [Severity::Error].
+    pub fn current_severity(&self) -> Severity {
+        self.error_detail.as_ref()
+            .map(|error_detail| error_detail.explain_error().severity)
+            .unwrap_or_default()
+    }
+
+    /// Returns the code given by this error's [SimpleErrorExplanation::code], if any.
+    pub fn current_code(&self) -> Option<String> {
+        self.error_detail.as_ref().and_then(|error_detail| error_detail.explain_error().code)
+    }
+
+    /// Returns how many errors this one holds, counting itself and all of its causes recursively.
+    pub(crate) fn node_count(&self) -> usize {
+        1 + self.causes.iter().map(|cause| cause.node_count()).sum::<usize>()
+    }
+
     /// Returns the value for the causes indicated through [SimpleError::with_cause] or/and
     /// [SimpleError::add_cause], said vec will be empty if you haven't called any of these
     /// functions before.
@@ -271,3 +1082,225 @@ impl<'input> SimpleError<'input> {
         &self.causes
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn std_errors_are_imported_with_their_source_chain() {
+        #[derive(Debug)]
+        struct ConfigError(std::io::Error);
+
+        impl core::fmt::Display for ConfigError {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.write_str("Couldn't read the config")
+            }
+        }
+
+        impl std::error::Error for ConfigError {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(ConfigError(std::io::Error::other("Disk unavailable")));
+        let error = SimpleError::from(boxed);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: Couldn't read the config\nCause: \n  - Error: Disk unavailable");
+    }
+
+    #[test]
+    fn sources_give_lines_and_columns_to_every_located_error() {
+        let source = "fn main() {\n  let a = missing;\n}";
+        let start = source.find("missing").unwrap();
+        let error = "Couldn't compile".to_string()
+            .with_cause("Variable doesn't exist".to_string().at(&source[start..start + 7]))
+            .with_cause(SimpleError::new().error_detail("Invalid declaration".to_string()).at_span(start - 8..start + 7))
+            .with_source(source);
+        let rendered = error.as_display_struct(false).as_display_string();
+        assert!(rendered.contains("- Position: On line 2 and column 11 up to line 2 and column 17\n  - At: missing"), "{rendered}");
+        assert!(rendered.contains("- Position: On line 2 and column 3 up to line 2 and column 17\n  - At: let a = missing"), "{rendered}");
+    }
+
+    #[test]
+    fn invisible_bytes_are_escaped() {
+        let input = b"ab\x00c\xffd\te\xe2\x80\x8b";
+        let error = SimpleError::new().error_detail("Unexpected bytes".to_string()).at_bytes(input);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "At: ab\\x00c\\xffd\te\\u{200b}\nError: Unexpected bytes");
+    }
+
+    #[test]
+    fn excluded_regions_are_never_marked() {
+        let input = "let secret = value;";
+        let marking = |span| SimpleErrorExplanation::new().explanation("Invalid declaration").mark_span(span);
+        let error = SimpleError::new().at(input).error_detail(marking(&input[4..18])).never_colorize(&input[4..10]);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "At: let secret>>> = value<<<;\nError: Invalid declaration");
+        let error = SimpleError::new().with_source(input).error_detail("Couldn't compile".to_string()).never_colorize(4..18)
+            .with_cause(SimpleError::new().at(input).error_detail(marking(&input[4..18])));
+        let rendered = error.as_display_struct(false).as_display_string();
+        assert!(rendered.contains("At: let secret = value;") && !rendered.contains(">>>"), "{rendered}");
+    }
+
+    #[test]
+    fn chains_nest_every_error_as_the_cause_of_the_previous_one() {
+        let chain = SimpleError::chain_from(["Couldn't load main.script", "Couldn't read utils.script", "File not found"].map(String::from)).unwrap();
+        assert_eq!(chain.as_display_struct(false).as_display_string(),
+                   "Error: Couldn't load main.script\nCause: \n  - Error: Couldn't read utils.script\n  - Cause: \n      - Error: File not found");
+        assert!(SimpleError::chain_from(Vec::<SimpleError>::new()).is_none());
+    }
+
+    #[test]
+    fn trees_are_rebuilt_mapping_children_first_or_dropping_causes() {
+        let error = |explanation: &str| SimpleError::new().error_detail(explanation.to_string());
+        let tree = error("Request failed")
+            .with_cause(error("Internal: pool exhausted").with_cause(error("Internal: timeout")))
+            .with_cause(error("Invalid user id"));
+        let public = tree.filter_causes(|cause| !cause.summary().starts_with("Internal"));
+        assert_eq!(public.as_display_struct(false).as_display_string(), "Error: Request failed\nCause: \n  - Error: Invalid user id");
+        let mut visited = Vec::new();
+        let redacted = tree.map_tree(|error| {
+            visited.push(error.as_display_struct(false).reason.unwrap_or_default());
+            error.error_detail("Redacted".to_string())
+        });
+        assert_eq!(visited, ["Internal: timeout", "Internal: pool exhausted", "Invalid user id", "Request failed"]);
+        assert!(redacted.iter().all(|(_, error)| error.summary().starts_with("Redacted")));
+    }
+
+    #[test]
+    fn errors_are_aggregated_under_a_root() {
+        let errors = ["Missing name", "Missing age"].map(|explanation| SimpleError::from(explanation.to_string()));
+        let collected = errors.clone().into_iter().collect::<SimpleError>();
+        assert_eq!(collected.summary(), "Unexplained error (2 causes: Missing name, Missing age)");
+        let aggregated = SimpleError::aggregate(errors, "Validation failed".to_string());
+        assert_eq!(aggregated.summary(), "Validation failed (2 causes: Missing name, Missing age)");
+    }
+
+    #[test]
+    fn structurally_equal_errors_render_the_same_tree() {
+        let error = SimpleError::from("Couldn't compile".to_string()).with_cause("Missing ;".to_string());
+        let same_texts = SimpleError::from(SimpleErrorExplanation::new().explanation("Couldn't compile")).with_cause("Missing ;".to_string());
+        let other_cause = SimpleError::from("Couldn't compile".to_string()).with_cause("Missing type".to_string());
+        assert!(error.structurally_eq(&same_texts));
+        assert!(!error.structurally_eq(&other_cause));
+        assert!(SimpleError::from("Unexpected \u{1b}[31m;\u{1b}[0m".to_string()).structurally_eq(&SimpleError::from("Unexpected ;".to_string())));
+    }
+
+    #[test]
+    fn backtraces_are_only_shown_when_asked_and_kept_on_displays() {
+        let error = SimpleError::new().error_detail("Unexpected state".to_string()).with_backtrace(std::backtrace::Backtrace::force_capture());
+        assert_eq!(error.backtrace().map(std::backtrace::Backtrace::status), Some(std::backtrace::BacktraceStatus::Captured));
+        let display = error.as_display_struct(false);
+        assert!(display.backtrace().is_some());
+        assert_eq!(display.as_display_string(), "Error: Unexpected state");
+        let options = DisplayOptions { show_backtraces: true, ..DisplayOptions::default() };
+        assert!(display.as_display_string_with_options(&options).starts_with("Error: Unexpected state\nBacktrace: "));
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false).backtrace(), display.backtrace());
+    }
+
+    #[test]
+    fn positions_name_the_file_they_belong_to() {
+        let error = SimpleError::new().error_detail("Unknown key".to_string()).start_point_of_error(3, 1).in_file("config/main.dsl");
+        let display = error.as_display_struct(false);
+        assert_eq!(display.file(), Some("config/main.dsl"));
+        assert!(display.as_display_string().contains("In file config/main.dsl, on line 3 and column 1"));
+        let only_file = SimpleError::new().error_detail("Unknown key".to_string()).in_file("main.dsl");
+        assert!(only_file.as_display_struct(false).as_display_string().contains("In file main.dsl"));
+        let no_file = SimpleError::new().error_detail("Unknown key".to_string()).start_point_of_error(3, 1);
+        assert!(no_file.as_display_struct(false).as_display_string().contains("On line 3 and column 1"));
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+    }
+
+    #[test]
+    fn source_lines_around_the_error_are_shown_as_context() {
+        let input = "a = 1\nb = 2\nc = ;\nd = 4\ne = 5\nf = 6";
+        let error = SimpleError::new().error_detail("Missing value".to_string()).at(&input[14..15]).with_source(input);
+        let options = DisplayOptions { context_lines: 1, ..DisplayOptions::default() };
+        let rendered = error.as_display_string_with_options(false, &options);
+        assert!(rendered.contains("Context:   2 | b = 2\n         > 3 | c = ;\n           4 | d = 4\n"));
+        assert!(!rendered.contains("e = 5"));
+        assert!(!error.as_display_string_with_options(false, &DisplayOptions::default()).contains("Context"));
+        let display = error.as_display_struct_with_context(false, 2);
+        assert_eq!(display.source_lines().len(), 5);
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+    }
+
+    #[test]
+    fn colored_and_plain_displays_are_rendered_in_one_pass() {
+        let input = "let x = ;\nfoo";
+        let cause = SimpleError::new().at("foo").error_detail("Unknown identifier".to_string());
+        let error = SimpleError::new().with_source(input).at("let x = ;").error_detail("Missing value".to_string()).with_cause(cause).with_cause(SimpleError::new());
+        let (colored, plain) = error.as_display_structs_both();
+        assert_eq!(colored, error.as_display_struct(ColorChoice::Always));
+        assert_eq!(plain, error.as_display_struct(false));
+        assert_eq!(colored.explained_causes().len(), 1);
+    }
+
+    #[test]
+    fn metadata_is_kept_and_only_shown_when_asked() {
+        let error = SimpleError::new().error_detail("Missing value".to_string()).with_metadata("request_id", "abc").with_metadata("tenant", "acme");
+        assert_eq!(error.current_metadata().len(), 2);
+        let display = error.as_display_struct(false);
+        assert_eq!(display.metadata()[1], ("tenant".to_string(), "acme".to_string()));
+        assert_eq!(display.as_display_string(), "Error: Missing value");
+        let options = DisplayOptions { show_metadata: true, ..DisplayOptions::default() };
+        assert_eq!(display.as_display_string_with_options(&options), "Error: Missing value\nDetails: request_id: abc\n         tenant: acme");
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+    }
+
+    #[test]
+    fn causes_below_the_minimum_severity_are_left_out() {
+        let explained = |severity: Severity, explanation: &'static str| SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation(explanation).severity(severity));
+        let error = explained(Severity::Error, "Couldn't compile")
+            .with_cause(explained(Severity::Hint, "Consider renaming y"))
+            .with_cause(explained(Severity::Warning, "Unused variable x"));
+        let filtered = error.filter_min_severity(Severity::Warning);
+        assert_eq!(filtered.current_causes().len(), 1);
+        assert_eq!(filtered.as_display_struct(false).as_display_string(), "Error: Couldn't compile\nCause: \n  - Warning: Unused variable x");
+        let options = DisplayOptions { min_severity: Some(Severity::Warning), ..DisplayOptions::default() };
+        assert_eq!(error.as_display_string_with_options(false, &options), filtered.as_display_struct(false).as_display_string());
+        assert_eq!(error.filter_min_severity(Severity::Hint).current_causes().len(), 2);
+    }
+
+    #[test]
+    fn leaves_are_given_along_with_the_errors_leading_to_them() {
+        let explained = |reason: &str| SimpleError::new().error_detail(reason.to_string());
+        let error = explained("Couldn't compile").with_cause(explained("Missing ;")).with_cause(explained("Invalid type").with_cause(explained("Unknown type Strng")));
+        let reason_of = |error: &SimpleError| error.as_display_struct(false).reason().unwrap().to_string();
+        let paths = error.leaf_errors_with_path().into_iter().map(|(leaf, path)| (reason_of(leaf), path.into_iter().map(reason_of).collect::<Vec<_>>())).collect::<Vec<_>>();
+        assert_eq!(paths, vec![
+            ("Missing ;".to_string(), vec!["Couldn't compile".to_string()]),
+            ("Unknown type Strng".to_string(), vec!["Couldn't compile".to_string(), "Invalid type".to_string()]),
+        ]);
+        assert!(SimpleError::new().leaf_errors_with_path()[0].1.is_empty());
+    }
+
+    #[test]
+    fn locations_are_kept_borrowed_or_owned_as_given() {
+        let input = "let a = 1".to_string();
+        let borrowed = SimpleError::new().at_cow(Cow::Borrowed(&input[4..5])).error_detail("Unused variable".to_string());
+        assert_eq!(borrowed.current_at(), Some("a"));
+        let owned = SimpleError::new().at_cow(Cow::Owned(input.to_uppercase())).error_detail("Unused variable".to_string());
+        assert_eq!(owned.as_display_struct(false).as_display_string(), "At: LET A = 1\nError: Unused variable");
+    }
+
+    #[test]
+    fn borrowed_errors_are_std_errors_and_their_displays_chain_their_causes() {
+        use std::error::Error;
+
+        let input = String::from("let x = ;");
+        let error = SimpleError::new().at(&input[4..5]).error_detail("a".to_string())
+            .with_cause("b".to_string().with_cause("c".to_string()));
+        let boxed: Box<dyn Error + '_> = Box::new(SimpleError::new().error_detail("a".to_string()).with_cause(SimpleError::new())
+            .with_cause("b".to_string().with_cause("c".to_string())));
+        let chain = core::iter::successors(boxed.source(), |error| (*error).source())
+            .map(|error| error.to_string().lines().next().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(chain, ["Error: b", "Error: c"]);
+
+        let display = error.as_display_struct(false);
+        let chain = core::iter::successors(Some(&display as &(dyn Error + 'static)), |error| (*error).source())
+            .map(|error| error.to_string().lines().next().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(chain, ["At: x", "Error: b", "Error: c"]);
+    }
+}
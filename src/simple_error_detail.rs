@@ -1,7 +1,10 @@
+use alloc::boxed::Box;
 use alloc::sync::Arc;
-use core::fmt::Debug;
-use alloc::string::String;
+use core::any::Any;
+use core::fmt::{Debug, Display};
+use alloc::string::{String, ToString};
 
+use crate::color_choice::ColorChoice;
 use crate::simple_error::SimpleError;
 use crate::simple_error_explanation::SimpleErrorExplanation;
 use crate::SimpleErrorDisplayInfo;
@@ -9,13 +12,37 @@ use crate::SimpleErrorDisplayInfo;
 /// Implementors give a textual explanation on why an error happen and how to solve it through the
 /// method [SimpleErrorDetail::explain_error], this means you'll implement this trait for your
 /// specific error types.
-pub trait SimpleErrorDetail: Debug {
+///
+/// When using the sync feature, implementors must also be [Send] and [Sync] (See [MaybeSendSync]),
+/// making [SimpleError] [Send] and [Sync] too.
+pub trait SimpleErrorDetail: Debug + MaybeSendSync {
     /// Explains what the happening of this error ([SimpleErrorExplanation::explanation]) and how to
     /// solve it [SimpleErrorExplanation::solution].
     ///
     /// While both explanation and solution are optional, it is expected for implementors to at
     /// least give an explanation, and highly recommended to also give a solution for it.
-    fn explain_error(&self) -> SimpleErrorExplanation;
+    fn explain_error(&self) -> SimpleErrorExplanation<'_>;
+
+    /// This is only implemented when using the serde feature.
+    ///
+    /// Gives domain-specific data about this error, like the name of the broken rule or the kind
+    /// of the offending token, which is merged into the serialized [SimpleErrorDisplayInfo] of this
+    /// error, so said data travels along with it. By default it gives no fields.
+    ///
+    /// Fields named as the ones of [SimpleErrorDisplayInfo], like `reason`, should be avoided, as
+    /// they would be repeated on the serialized error.
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
+
+    /// Gives this error as [Any], so it can be downcast back to its concrete type through
+    /// [SimpleError::downcast_detail] and [SimpleError::detail_is] after being propagated as a
+    /// generic [SimpleError]. By default it gives None, as only `'static` types can be [Any], so
+    /// implementors wanting to support downcasting should return `Some(self)`.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 
     /// Turns this error value into a [SimpleError] containing both the error itself and the
     /// location it happened at on a certain string, this is specially useful when your error
@@ -49,26 +76,111 @@ pub trait SimpleErrorDetail: Debug {
         SimpleError::new().error_detail(self).with_cause(cause.into())
     }
 
+    /// Turns this error value into a [SimpleError] holding the error inside *result* as its cause
+    /// when it is an [Err], or without causes when it is [Ok], letting you wrap whatever failed
+    /// below in a single expression, like
+    /// `MyError::CouldNotLoadConfig.caused_by_result(read_config())`.
+    fn caused_by_result<'input, T, PError: Into<SimpleError<'input>>>(self, result: Result<T, PError>) -> SimpleError<'input> where Self: Sized + 'input {
+        SimpleError::new().error_detail(self).caused_by_result(result)
+    }
+
     /// Turns this error into a [SimpleErrorDisplayInfo], which will hold at most a reason and a
     /// solution.
-    fn to_display_struct(self, colorize: bool) -> SimpleErrorDisplayInfo where Self: Sized {
+    fn to_display_struct<Choice: Into<ColorChoice>>(self, colorize: Choice) -> SimpleErrorDisplayInfo where Self: Sized {
         SimpleError::new().error_detail(self).as_display_struct(colorize)
     }
 }
 
+/// Bound required on every [SimpleErrorDetail], being [Send] and [Sync] when using the sync
+/// feature, so errors can be sent to and shared with other threads, such as when aggregating the
+/// errors of rayon or tokio pipelines, while without said feature every type meets it.
+///
+/// Generic implementations of [SimpleErrorDetail] should require this bound on the types they wrap,
+/// so they compile whether the sync feature is enabled or not.
+pub trait MaybeSendSync: sync_bound::SyncBound {}
+
+impl<T: sync_bound::SyncBound + ?Sized> MaybeSendSync for T {}
+
+/// Holds the bound behind [MaybeSendSync], which changes with the sync feature.
+mod sync_bound {
+    #[cfg(feature = "sync")]
+    pub trait SyncBound: Send + Sync {}
+
+    #[cfg(feature = "sync")]
+    impl<T: Send + Sync + ?Sized> SyncBound for T {}
+
+    #[cfg(not(feature = "sync"))]
+    pub trait SyncBound {}
+
+    #[cfg(not(feature = "sync"))]
+    impl<T: ?Sized> SyncBound for T {}
+}
+
 /// Deref implementation of SimpleErrorDetail for Arc
 impl<'lf> SimpleErrorDetail for Arc<dyn SimpleErrorDetail + 'lf> {
     /// Deref implementation of SimpleErrorDetail for Arc
-    fn explain_error(&self) -> SimpleErrorExplanation {
-        (&**self).explain_error()
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        (**self).explain_error()
+    }
+
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        (**self).extra_fields()
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        (**self).as_any()
     }
 }
 
 /// Deref implementation of SimpleErrorDetail for Arc of anything that is [SimpleErrorDetail].
 impl<T: SimpleErrorDetail> SimpleErrorDetail for Arc<T> {
     /// Deref implementation of SimpleErrorDetail for Arc of anything that is [SimpleErrorDetail].
-    fn explain_error(&self) -> SimpleErrorExplanation {
-        (&**self).explain_error()
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        (**self).explain_error()
+    }
+
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        (**self).extra_fields()
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        (**self).as_any()
+    }
+}
+
+/// Deref implementation of SimpleErrorDetail for Box
+impl<'lf> SimpleErrorDetail for Box<dyn SimpleErrorDetail + 'lf> {
+    /// Deref implementation of SimpleErrorDetail for Box
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        (**self).explain_error()
+    }
+
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        (**self).extra_fields()
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        (**self).as_any()
+    }
+}
+
+/// Deref implementation of SimpleErrorDetail for Box of anything that is [SimpleErrorDetail].
+impl<T: SimpleErrorDetail> SimpleErrorDetail for Box<T> {
+    /// Deref implementation of SimpleErrorDetail for Box of anything that is [SimpleErrorDetail].
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        (**self).explain_error()
+    }
+
+    #[cfg(feature = "serde")]
+    fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+        (**self).extra_fields()
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        (**self).as_any()
     }
 }
 
@@ -77,8 +189,8 @@ impl<T: SimpleErrorDetail> SimpleErrorDetail for Arc<T> {
 impl<'input> SimpleErrorDetail for SimpleErrorExplanation<'input> {
     /// SimpleErrorExplanation implements SimpleErrorDetail by giving a copy of itself as an error
     /// explanation.
-    fn explain_error(&self) -> SimpleErrorExplanation {
-        (&*self).clone()
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        self.clone()
     }
 }
 
@@ -87,9 +199,13 @@ impl<'input> SimpleErrorDetail for SimpleErrorExplanation<'input> {
 impl SimpleErrorDetail for String {
     /// String can be used as an SimpleErrorExplanation whose explanation is a copy of this String, this
     /// is useful if you don't want to create a type for your errors
-    fn explain_error(&self) -> SimpleErrorExplanation {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
         SimpleErrorExplanation::new().explanation(self.clone())
     }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 /// String can be used as an SimpleErrorExplanation whose explanation and solution are a copy of
@@ -97,7 +213,7 @@ impl SimpleErrorDetail for String {
 impl SimpleErrorDetail for (Option<String>, Option<String>) {
     /// String can be used as an SimpleErrorExplanation whose explanation and solution are a copy of
     /// this String pair, this is useful if you don't want to create a type for your errors
-    fn explain_error(&self) -> SimpleErrorExplanation {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
         let mut res = SimpleErrorExplanation::new();
         if let Some(explanation) = &self.0{
             res=res.explanation(explanation.clone());
@@ -107,4 +223,113 @@ impl SimpleErrorDetail for (Option<String>, Option<String>) {
         }
         res
     }
-}
\ No newline at end of file
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+}
+
+/// Adapter implementing SimpleErrorDetail for any type implementing both [Debug] and [Display],
+/// such as errors defined through `thiserror`, by using their displayed message as explanation, so
+/// they can be stacked as causes without writing a SimpleErrorDetail implementation for them, like
+/// `error.add_cause(DetailFromDisplay(io_error))`.
+///
+/// Types also wanting a solution, severity or any other detail should implement SimpleErrorDetail
+/// themselves instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DetailFromDisplay<T>(pub T);
+
+impl<T> DetailFromDisplay<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for DetailFromDisplay<T> {
+    fn from(value: T) -> Self {
+        DetailFromDisplay(value)
+    }
+}
+
+/// Explains the error using the message given by its [Display] implementation.
+impl<T: Debug + Display + MaybeSendSync> SimpleErrorDetail for DetailFromDisplay<T> {
+    /// Explains the error using the message given by its [Display] implementation.
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        SimpleErrorExplanation::new().explanation(self.0.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn errors_are_caused_only_by_failed_results() {
+        let failed: Result<(), String> = Err("Missing ;".to_string());
+        let error = "Couldn't compile".to_string().caused_by_result(failed);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: Couldn't compile\nCause: \n  - Error: Missing ;");
+        let succeeded: Result<u8, String> = Ok(1);
+        assert!(SimpleError::new().caused_by_result(succeeded).current_causes().is_empty());
+    }
+
+    #[test]
+    fn details_can_be_downcast_back_to_their_concrete_type() {
+        #[derive(Debug, PartialEq)]
+        struct MissingToken(char);
+
+        impl SimpleErrorDetail for MissingToken {
+            fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+                SimpleErrorExplanation::new().explanation("Missing token")
+            }
+
+            fn as_any(&self) -> Option<&dyn Any> {
+                Some(self)
+            }
+        }
+
+        let error = MissingToken(';').to_simple_error();
+        assert_eq!(error.downcast_detail::<MissingToken>(), Some(&MissingToken(';')));
+        assert!(error.detail_is::<MissingToken>());
+        assert!(!error.detail_is::<String>());
+        assert_eq!(SimpleError::from("Missing ;".to_string()).downcast_detail::<String>().map(String::as_str), Some("Missing ;"));
+        assert!(!SimpleError::new().error_detail(SimpleErrorExplanation::new()).detail_is::<SimpleErrorExplanation>());
+        assert!(!SimpleError::new().detail_is::<String>());
+    }
+
+    #[test]
+    fn displayable_errors_are_explained_through_their_display() {
+        #[derive(Debug)]
+        struct OutOfRange(u32);
+
+        impl Display for OutOfRange {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "Value {} is out of range", self.0)
+            }
+        }
+
+        let error = SimpleError::from(DetailFromDisplay(OutOfRange(300))).with_cause(DetailFromDisplay::from(OutOfRange(256)));
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: Value 300 is out of range\nCause: \n  - Error: Value 256 is out of range");
+    }
+
+    #[cfg(all(feature = "sync", feature = "std"))]
+    #[test]
+    fn errors_are_send_and_sync_when_using_the_sync_feature() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        assert_send_sync(&SimpleError::new().error_detail("Missing ;".to_string()));
+        let error = std::thread::spawn(|| SimpleError::new().error_detail(DetailFromDisplay(core::fmt::Error))).join().unwrap();
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: an error occurred when formatting an argument");
+    }
+
+    #[test]
+    fn boxed_details_explain_and_downcast_as_the_details_they_hold() {
+        let from_plugin: Box<dyn SimpleErrorDetail> = Box::new("Unknown command".to_string());
+        let error = SimpleError::new().error_detail(from_plugin);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: Unknown command");
+        let error = SimpleError::new().error_detail(Box::new("Missing value".to_string()));
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: Missing value");
+        assert_eq!(error.downcast_detail::<String>().map(String::as_str), Some("Missing value"));
+    }
+}
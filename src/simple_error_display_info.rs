@@ -1,9 +1,17 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt::{Display, Formatter};
+use core::iter;
 
-use crate::formatting::{ident_lines_except_first, join_strings, pluralize};
+use crate::display_options::{CauseOrdering, CountedNoun, DisplayMode, DisplayOptions};
+use crate::display_template::DisplayTemplate;
+use crate::formatting::{display_width, hyperlink, indent_lines_except_first, join_strings, pluralize, strip_ansi, wrap_lines};
+use crate::severity::Severity;
+use crate::suggestion::Applicability;
+use crate::timestamp::Timestamp;
 
 /// Holds information relative to an error in order to display it, and if the `serde` feature is
 /// enabled, it also implements [serde::Serialize] and [serde::Deserialize], this is mostly to allow
@@ -12,13 +20,31 @@ use crate::formatting::{ident_lines_except_first, join_strings, pluralize};
 ///
 /// The information this struct holds is:
 /// * at (Optional): Where the errors happen, this is usually an input on a Parsing error, like an
-/// AST-Building error.
+///   AST-Building error.
 /// * reason (Optional): What / Why the error happen.
 /// * solution (Optional): How to solve the error.
+/// * solution_steps (Vec of Strings): Ordered steps to follow to solve the error.
 /// * on_line_and_column (Optional): From which line and column the error happens.
 /// * up_to_line_an_column (Optional): Upto which line and column the error happens.
+/// * file (Optional): Name of the file the error happens in.
+/// * source_lines (Vec of numbered lines): Lines of the source around the error, see
+///   [crate::SimpleError::as_display_struct_with_context].
+/// * labels (Vec of labels): Messages attached to other places related to the error, along with
+///   the text and position of said places.
+/// * suggestions (Vec of labels): Replacements suggested to solve the error, along with the text
+///   and position of the replaced places.
+/// * code (Optional): Code identifying the kind of the error.
+/// * severity (Default: Error): How serious the error is.
+/// * notes and helps (Vecs of Strings): Extra notes giving context, and extra help on how to avoid
+///   the error.
+/// * doc_url (Optional): Link to documentation explaining the kind of the error in depth.
+/// * data (Optional): Raw data attached through [crate::SimpleErrorExplanation::data], only
+///   present when using the serde feature.
+/// * timestamp (Optional): Moment the error happened at, see [crate::SimpleError::timestamp].
+/// * metadata (Vec of key and value pairs): Context attached through
+///   [crate::SimpleError::with_metadata], like request IDs.
 /// * unexplained_causes (Default: 0): Number of causes from which their [SimpleErrorDisplayInfo]
-/// contents were empty according to not matching [SimpleErrorDisplayInfo::is_explained].
+///   contents were empty according to not matching [SimpleErrorDisplayInfo::is_explained].
 /// * explained_causes (Vec of [SimpleErrorDisplayInfo]) : Causes that were actually explained.
 ///
 /// When using the 'serde' feature, it also allows to both serialize and deserializing, being able
@@ -33,10 +59,60 @@ pub struct SimpleErrorDisplayInfo {
     pub(crate) reason: Option<String>,
     /// How to solve the error.
     pub(crate) solution: Option<String>,
+    /// Ordered steps to follow to solve the error.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) solution_steps: Vec<String>,
     /// From which line and column the error happens.
     pub(crate) on_line_and_column: Option<(usize, usize)>,
     /// Upto which line and column the error happens.
     pub(crate) up_to_line_an_column: Option<(usize, usize)>,
+    /// Name of the file the error happens in, see [crate::SimpleError::in_file].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) file: Option<String>,
+    /// Lines of the source around the error along with their line numbers, see
+    /// [crate::SimpleError::as_display_struct_with_context].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) source_lines: Vec<(usize, String)>,
+    /// Messages attached to other places related to the error.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) labels: Vec<LabelDisplayInfo>,
+    /// Replacements suggested to solve the error, where the message says what to replace them
+    /// with.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) suggestions: Vec<LabelDisplayInfo>,
+    /// Code identifying the kind of the error.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) code: Option<String>,
+    /// How serious the error is.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) severity: Severity,
+    /// Extra notes giving context about the error.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) notes: Vec<String>,
+    /// Extra help on how to avoid the error.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) helps: Vec<String>,
+    /// Link to documentation explaining the kind of the error in depth.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) doc_url: Option<String>,
+    /// Raw data attached through [crate::SimpleErrorExplanation::data].
+    #[cfg(feature = "serde")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) data: Option<serde_json::Value>,
+    /// Moment the error happened at, see [crate::SimpleError::timestamp].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) timestamp: Option<Timestamp>,
+    /// Key and value pairs attached through [crate::SimpleError::with_metadata].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) metadata: Vec<(String, String)>,
+    /// Backtrace recorded through [crate::SimpleError::capture_backtrace].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) backtrace: Option<String>,
+    /// Domain-specific data given by [crate::SimpleErrorDetail::extra_fields], serialized as
+    /// fields of this same error.
+    #[cfg(feature = "serde")]
+    #[serde(flatten)]
+    pub(crate) extra_fields: serde_json::Map<String, serde_json::Value>,
     /// Number of causes from which their [SimpleErrorDisplayInfo] contents were empty according to
     /// not matching [SimpleErrorDisplayInfo::is_explained].
     pub(crate) unexplained_causes: usize,
@@ -44,6 +120,45 @@ pub struct SimpleErrorDisplayInfo {
     pub(crate) explained_causes: Vec<SimpleErrorDisplayInfo>,
 }
 
+/// Line and column an error happens from, and line and column it happens up to, as given by
+/// [SimpleErrorDisplayInfo::positions].
+pub type Positions = (Option<(usize, usize)>, Option<(usize, usize)>);
+
+/// Message attached to a place related to an error, see [crate::SimpleErrorExplanation::label].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct LabelDisplayInfo {
+    /// Text of the labeled place, quoted, or its byte offsets when the text isn't available.
+    pub(crate) at: Option<String>,
+    /// Line and column where the labeled place starts.
+    pub(crate) on_line_and_column: Option<(usize, usize)>,
+    /// Message attached to the labeled place.
+    pub(crate) message: String,
+    /// How confident a suggestion is, this is always [None] on labels.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) applicability: Option<Applicability>,
+}
+
+impl LabelDisplayInfo {
+    /// Tells if this is a suggestion that might not fix the error, being displayed as a possible
+    /// fix.
+    pub(crate) fn is_uncertain(&self) -> bool {
+        matches!(self.applicability, Some(Applicability::MaybeIncorrect | Applicability::HasPlaceholders))
+    }
+
+    /// Gives a string like "'x' on line 1 and column 5: First defined here".
+    pub(crate) fn as_display_string(&self) -> String {
+        let position = self.on_line_and_column
+            .map(|(line, column)| format!("on line {line} and column {column}"));
+        let place = join_strings(" ", self.at.clone().into_iter().chain(position));
+        if place.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{place}: {}", self.message)
+        }
+    }
+}
+
 /// Implements display by calling [SimpleErrorDisplayInfo::as_display_string]
 impl Display for SimpleErrorDisplayInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
@@ -51,20 +166,84 @@ impl Display for SimpleErrorDisplayInfo {
     }
 }
 
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// SimpleErrorDisplayInfo implements [std::error::Error] as it also implements [Display] and
+/// [Debug], where its [source](std::error::Error::source) is its first explained cause, so tools
+/// walking error chains, like `anyhow` or `{:#}` chains, can traverse the error tree through the
+/// first cause of every error.
+#[cfg(feature = "std")]
+impl std::error::Error for SimpleErrorDisplayInfo {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.explained_causes.first().map(|cause| cause as &(dyn std::error::Error + 'static))
+    }
+}
+
 impl SimpleErrorDisplayInfo {
     /// Creates a new value of [SimpleErrorDisplayInfo] giving:
     /// * at: Where the errors happen, this is usually an input on a Parsing error, like an
-    ///       AST-Building error.
+    ///   AST-Building error.
     /// * reason: What / Why the error happen.
     /// * solution: How to solve the error.
     /// * on_line_and_column: From which line and column the error happens.
     /// * up_to_line_an_column: Upto which line and column the error happens.
     /// * unexplained_causes: Number of causes from which their [SimpleErrorDisplayInfo] contents
-    ///                       were empty according to not matching
-    ///                       [SimpleErrorDisplayInfo::is_explained].
+    ///   were empty according to not matching
+    ///   [SimpleErrorDisplayInfo::is_explained].
     /// * explained_causes: Displays of causes that were actually explained.
-    pub(crate) const fn new(at: Option<String>, reason: Option<String>, solution: Option<String>, on_line_and_column: Option<(usize, usize)>, up_to_line_an_column: Option<(usize, usize)>, unexplained_causes: usize, explained_causes: Vec<SimpleErrorDisplayInfo>) -> Self {
-        Self { at, reason, solution, on_line_and_column, up_to_line_an_column, unexplained_causes, explained_causes }
+    pub(crate) fn new(at: Option<String>, reason: Option<String>, solution: Option<String>, on_line_and_column: Option<(usize, usize)>, up_to_line_an_column: Option<(usize, usize)>, unexplained_causes: usize, explained_causes: Vec<SimpleErrorDisplayInfo>) -> Self {
+        Self { at, reason, solution, solution_steps: Vec::new(), on_line_and_column, up_to_line_an_column, file: None, source_lines: Vec::new(), labels: Vec::new(), suggestions: Vec::new(), code: None, severity: Severity::Error, notes: Vec::new(), helps: Vec::new(), doc_url: None, #[cfg(feature = "serde")] data: None, timestamp: None, metadata: Vec::new(), backtrace: None, #[cfg(feature = "serde")] extra_fields: serde_json::Map::new(), unexplained_causes, explained_causes }
+    }
+
+    /// Removes every ANSI escape sequence from this display and its causes, used when the error
+    /// must be shown without colors.
+    pub(crate) fn strip_ansi(&mut self) {
+        for text in [&mut self.at, &mut self.reason, &mut self.solution, &mut self.doc_url].into_iter().flatten()
+            .chain(self.solution_steps.iter_mut()).chain(self.notes.iter_mut()).chain(self.helps.iter_mut())
+            .chain(self.labels.iter_mut().chain(self.suggestions.iter_mut())
+                .flat_map(|label| label.at.iter_mut().chain([&mut label.message]))) {
+            *text = strip_ansi(text);
+        }
+        self.explained_causes.iter_mut().for_each(|cause| cause.strip_ansi());
+    }
+
+    /// Returns a copy of this error leaving out the causes less serious than *min_severity*, or
+    /// [None] if this error itself is less serious than it.
+    pub(crate) fn filtered_by_min_severity(&self, min_severity: Severity) -> Option<Self> {
+        (self.severity >= min_severity).then(|| self.with_causes_of_min_severity(min_severity))
+    }
+
+    /// Returns a copy of this error leaving out the causes less serious than *min_severity*, while
+    /// this error itself is always kept.
+    pub(crate) fn with_causes_of_min_severity(&self, min_severity: Severity) -> Self {
+        let mut res = self.clone();
+        res.explained_causes = self.explained_causes.iter()
+            .filter_map(|cause| cause.filtered_by_min_severity(min_severity))
+            .collect();
+        res
+    }
+
+    /// Describes in which file and from which line and column the error happens up to which line
+    /// and column, if known.
+    pub(crate) fn position_string(&self) -> Option<String> {
+        let lines = self.on_line_and_column.map(|(line_of_start, column_of_start)| {
+            format!("line {line_of_start} and column {column_of_start}{}",
+                    self.up_to_line_an_column.map(|(line_of_end, column_of_end)|
+                        format!(" up to line {line_of_end} and column {column_of_end}")).unwrap_or_default())
+        });
+        match (&self.file, lines) {
+            (Some(file), Some(lines)) => Some(format!("In file {file}, on {lines}")),
+            (Some(file), None) => Some(format!("In file {file}")),
+            (None, Some(lines)) => Some(format!("On {lines}")),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the solution followed by its steps as a numbered list, like '1. Add the import'.
+    pub(crate) fn solution_with_steps(&self) -> Option<String> {
+        let steps = self.solution_steps.iter().enumerate().map(|(step_no, step)| format!("{}. {step}", step_no + 1));
+        Some(join_strings("\n", self.solution.iter().cloned().chain(steps)))
+            .filter(|solution| !solution.is_empty())
     }
 
     /// Returns how many causes it holds, plus itself
@@ -72,97 +251,502 @@ impl SimpleErrorDisplayInfo {
         1 + self.explained_causes.iter().map(|display| display.complexity()).sum::<usize>()
     }
 
-    /// Tells if this error is explained, this is: When at, reason, solution or on_line_and_column
-    /// is given (See parameter at [SimpleErrorDisplayInfo]), or when explained_causes isn't empty.
+    /// Reorders the explained causes of this error and of all of its causes following *ordering*,
+    /// so renderers not taking [DisplayOptions], like [SimpleErrorDisplayInfo::summary], show them
+    /// in that order too.
+    pub fn sort_causes(&mut self, ordering: CauseOrdering) {
+        self.explained_causes.iter_mut().for_each(|cause| cause.sort_causes(ordering));
+        sort_by_ordering(&mut self.explained_causes, |cause| cause, ordering);
+    }
+
+    /// Returns the explained causes of this error in the order given by *ordering*.
+    pub(crate) fn ordered_causes(&self, ordering: CauseOrdering) -> Vec<&SimpleErrorDisplayInfo> {
+        let mut res = self.explained_causes.iter().collect::<Vec<_>>();
+        sort_by_ordering(&mut res, |cause| *cause, ordering);
+        res
+    }
+
+    /// Returns where the error happened, this is usually an input on a Parsing error.
+    pub fn at(&self) -> Option<&str> {
+        self.at.as_deref()
+    }
+
+    /// Returns what / why the error happened.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Returns how to solve the error, without its steps.
+    pub fn solution(&self) -> Option<&str> {
+        self.solution.as_deref()
+    }
+
+    /// Returns the ordered steps to follow to solve the error.
+    pub fn solution_steps(&self) -> &[String] {
+        &self.solution_steps
+    }
+
+    /// Returns from which line and column the error happens, and up to which line and column it
+    /// happens.
+    pub fn positions(&self) -> Positions {
+        (self.on_line_and_column, self.up_to_line_an_column)
+    }
+
+    /// Returns the name of the file the error happens in, see [crate::SimpleError::in_file].
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// This is only implemented when using the serde feature.
+    ///
+    /// Returns the raw data attached through [crate::SimpleErrorExplanation::data].
+    #[cfg(feature = "serde")]
+    pub fn data(&self) -> Option<&serde_json::Value> {
+        self.data.as_ref()
+    }
+
+    /// Returns the moment the error happened at, see [crate::SimpleError::timestamp].
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+    }
+
+    /// Returns the key and value pairs attached through [crate::SimpleError::with_metadata].
+    pub fn metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Returns the lines of the source around the error along with their line numbers, see
+    /// [crate::SimpleError::as_display_struct_with_context].
+    pub fn source_lines(&self) -> &[(usize, String)] {
+        &self.source_lines
+    }
+
+    /// Gives the lines of the source around the error that are up to *context_lines* lines away
+    /// from it, numbered and with the lines the error happens on marked by '>', like in
+    /// '> 3 | let y = ;'.
+    fn context_string(&self, context_lines: usize, options: &DisplayOptions) -> Option<String> {
+        let (first_line, _) = self.on_line_and_column?;
+        let last_line = self.up_to_line_an_column.map(|(last_line, _)| last_line).unwrap_or(first_line).max(first_line);
+        let lines = self.source_lines.iter()
+            .filter(|(line, _)| line.saturating_add(context_lines) >= first_line && *line <= last_line.saturating_add(context_lines))
+            .collect::<Vec<_>>();
+        let width = lines.iter().map(|(line, _)| line.to_string().len()).max()?;
+        Some(join_strings("\n", lines.into_iter().map(|(line, text)| {
+            let marker = if (first_line..=last_line).contains(line) { '>' } else { ' ' };
+            let text = options.tab_rendering.render(text);
+            if text.is_empty() { format!("{marker} {line:>width$} |") } else { format!("{marker} {line:>width$} | {text}") }
+        })))
+    }
+
+    /// Returns the code identifying the kind of the error.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Returns how serious the error is.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the extra notes giving context about the error.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Returns the extra help on how to avoid the error.
+    pub fn helps(&self) -> &[String] {
+        &self.helps
+    }
+
+    /// Returns the link to documentation explaining the kind of the error in depth.
+    pub fn doc_url(&self) -> Option<&str> {
+        self.doc_url.as_deref()
+    }
+
+    /// Returns the backtrace recorded through [crate::SimpleError::capture_backtrace].
+    pub fn backtrace(&self) -> Option<&str> {
+        self.backtrace.as_deref()
+    }
+
+    /// Returns the displays of the causes that were explained, see
+    /// [SimpleErrorDisplayInfo::is_explained].
+    pub fn explained_causes(&self) -> &[SimpleErrorDisplayInfo] {
+        &self.explained_causes
+    }
+
+    /// Returns the number of causes that weren't explained, see
+    /// [SimpleErrorDisplayInfo::is_explained].
+    pub fn unexplained_causes(&self) -> usize {
+        self.unexplained_causes
+    }
+
+    /// This is only implemented when using the serde feature.
+    ///
+    /// Returns the domain-specific data given by [crate::SimpleErrorDetail::extra_fields].
+    #[cfg(feature = "serde")]
+    pub fn extra_fields(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra_fields
+    }
+
+    /// Tells if this error is explained, this is: When at, reason, solution, on_line_and_column or
+    /// code is given (See parameter at [SimpleErrorDisplayInfo]), or when solution steps, labels,
+    /// suggestions, notes, helps, doc_url or explained_causes aren't empty.
     pub fn is_explained(&self) -> bool {
-        self.at.is_some() || self.reason.is_some() || self.solution.is_some() || self.on_line_and_column.is_some() || !self.explained_causes.is_empty()
+        self.at.is_some() || self.reason.is_some() || self.solution.is_some() || !self.solution_steps.is_empty() || self.on_line_and_column.is_some()
+            || self.code.is_some() || !self.labels.is_empty() || !self.suggestions.is_empty() || !self.notes.is_empty() || !self.helps.is_empty() || self.doc_url.is_some() || !self.explained_causes.is_empty()
     }
 
     /// Gives a string displaying this error, its format is:
     ///
-    /// * Position: From which line and column it happens up to which line and column.
+    /// * Position: In which file and from which line and column it happens up to which line and
+    ///   column.
     /// * At: String defining where it happened.
-    /// * Error: Explanation on why the error happened.
+    /// * Context: Lines of the source around the error, see [DisplayOptions::context_lines].
+    /// * Label: Every message attached to other places related to the error.
+    /// * Code: Code identifying the kind of the error.
+    /// * Error: Explanation on why the error happened, labeled as 'Warning', 'Info' or 'Hint'
+    ///   instead depending on its [Severity].
+    /// * Solution: How to solve the error, followed by its steps as a numbered list.
+    /// * Suggestion: Every replacement suggested to solve the error.
+    /// * Note / Help: Every extra note and help given for the error.
+    /// * More info: Link to documentation explaining the kind of the error in depth.
+    /// * Details: Every key and value pair attached to the error, one per line, only shown when
+    ///   [DisplayOptions::show_metadata] is set.
     /// * Has: Count of explained and unexplained errors (Omitted when there is just one explained
-    /// error, see [SimpleErrorDisplayInfo::is_explained] for more info.
+    ///   error, see [SimpleErrorDisplayInfo::is_explained] for more info.
     /// * Cause/Causes: Repeats this same structure for every explained cause.
     ///
     /// Since all of the fields are optional, if all are empty it returns "Error: Unexplained error"
     /// instead of an empty string.
     pub fn as_display_string(&self) -> String {
-        self.__as_display_string(false)
-            .unwrap_or_else(|| "Error: Unexplained error".to_string())
+        self.as_display_string_with_options(&DisplayOptions::default())
+    }
+
+    /// Gives a string displaying this error as in [SimpleErrorDisplayInfo::as_display_string], but
+    /// using the labels, indentation and cause separator given on *options*, where causes less
+    /// serious than [DisplayOptions::min_severity] are left out, and identical causes are merged
+    /// when [DisplayOptions::group_identical] is set.
+    pub fn as_display_string_with_options(&self, options: &DisplayOptions) -> String {
+        self.shown_with(options).__as_display_string(false, options)
+            .unwrap_or_else(|| format!("{}: {}", options.error_label, options.unexplained_error))
+    }
+
+    /// Returns this error as it is displayed with *options*, leaving out the causes less serious
+    /// than [DisplayOptions::min_severity] and merging identical causes when
+    /// [DisplayOptions::group_identical] is set.
+    pub(crate) fn shown_with(&self, options: &DisplayOptions) -> Cow<'_, SimpleErrorDisplayInfo> {
+        let filtered = options.min_severity.map(|min_severity| self.with_causes_of_min_severity(min_severity));
+        match (filtered, options.group_identical) {
+            (Some(filtered), true) => Cow::Owned(filtered.dedup_causes()),
+            (Some(filtered), false) => Cow::Owned(filtered),
+            (None, true) => Cow::Owned(self.dedup_causes()),
+            (None, false) => Cow::Borrowed(self),
+        }
+    }
+
+    /// Gives a string displaying this error as in [SimpleErrorDisplayInfo::as_display_string], but
+    /// without colors nor any other escape sequence, even if this error was built with colors,
+    /// this is useful for writing errors to logs and files, see [crate::fmt_util::strip_ansi].
+    pub fn to_plain_string(&self) -> String {
+        self.to_plain_string_with_options(&DisplayOptions::default())
+    }
+
+    /// Gives a string displaying this error as in [SimpleErrorDisplayInfo::to_plain_string], but
+    /// using the labels, indentation and cause separator given on *options*.
+    pub fn to_plain_string_with_options(&self, options: &DisplayOptions) -> String {
+        strip_ansi(&self.as_display_string_with_options(options))
+    }
+
+    /// Gives a string displaying this error as given by *mode*, where [DisplayMode::Regular] gives
+    /// the same string as [SimpleErrorDisplayInfo::as_display_string], while
+    /// [DisplayMode::Snapshot] gives a string that stays byte-stable across runs and platforms.
+    pub fn as_display_string_in_mode(&self, mode: DisplayMode) -> String {
+        match mode {
+            DisplayMode::Regular => self.as_display_string(),
+            DisplayMode::Snapshot => {
+                let mut display = self.clone();
+                display.strip_ansi();
+                let options = DisplayOptions { cause_ordering: CauseOrdering::Insertion, ..DisplayOptions::default() };
+                let display_string = display.as_display_string_with_options(&options).replace("\r\n", "\n");
+                join_strings("\n", display_string.lines().map(str::trim_end)).trim_end().to_string()
+            }
+        }
+    }
+
+    /// Gives a single line summarizing this error and its causes, like `Couldn't compile code (2
+    /// causes: Missing variable x, Missing function f)`, where every explained cause is summarized
+    /// the same way, this is useful for log lines and status bars, where the whole error would be
+    /// too noisy.
+    ///
+    /// Colors are removed and multi-line explanations are joined into a single line.
+    pub fn summary(&self) -> String {
+        self.summary_up_to_depth(usize::MAX)
+    }
+
+    /// Gives the same line as [SimpleErrorDisplayInfo::summary], where causes *depth* levels below
+    /// this error are only counted rather than summarized, like `Couldn't compile code (2 causes)`
+    /// for a depth of 0.
+    pub(crate) fn summary_up_to_depth(&self, depth: usize) -> String {
+        let reason = self.reason.as_deref().map(strip_ansi).unwrap_or_else(|| String::from("Unexplained error"));
+        let reason = join_strings(" ", reason.lines().map(str::trim).filter(|line| !line.is_empty()));
+        let causes_count = self.explained_causes.len() + self.unexplained_causes;
+        if causes_count == 0 {
+            return reason;
+        }
+        if depth == 0 {
+            return format!("{reason} ({})", pluralize(causes_count, "cause", ""));
+        }
+        let causes = self.explained_causes.iter().map(|cause| cause.summary_up_to_depth(depth - 1))
+            .chain(Some(pluralize(self.unexplained_causes, "unexplained cause", "")).filter(|unexplained| !unexplained.is_empty()));
+        format!("{reason} ({}: {})", pluralize(causes_count, "cause", ""), join_strings(", ", causes))
+    }
+
+    /// Gives a string displaying this error following the layout given by *template*, see
+    /// [DisplayTemplate] for more info.
+    pub fn as_display_string_with_template(&self, template: &DisplayTemplate) -> String {
+        template.render(self)
     }
 
     /// Gives a string displaying this error, its format is:
     ///
-    /// * Position: From which line and column it happens up to which line and column.
+    /// * Position: In which file and from which line and column it happens up to which line and
+    ///   column.
     /// * At: String defining where it happened.
-    /// * Error: Explanation on why the error happened.
+    /// * Context: Lines of the source around the error, see [DisplayOptions::context_lines].
+    /// * Label: Every message attached to other places related to the error.
+    /// * Code: Code identifying the kind of the error.
+    /// * Error: Explanation on why the error happened, labeled as 'Warning', 'Info' or 'Hint'
+    ///   instead depending on its [Severity].
+    /// * Solution: How to solve the error, followed by its steps as a numbered list.
+    /// * Suggestion: Every replacement suggested to solve the error.
+    /// * Note / Help: Every extra note and help given for the error.
+    /// * More info: Link to documentation explaining the kind of the error in depth.
+    /// * Details: Every key and value pair attached to the error, one per line, only shown when
+    ///   [DisplayOptions::show_metadata] is set.
     /// * Has: Count of explained and unexplained errors (Omitted when there is just one explained
-    /// error, see [SimpleErrorDisplayInfo::is_explained] for more info.
+    ///   error, see [SimpleErrorDisplayInfo::is_explained] for more info.
     /// * Cause/Causes: Repeats this same structure for every explained cause.
     ///
     /// Since all of the fields are optional, it might return [None].
-    fn __as_display_string(&self, is_displaying_as_cause_of_other: bool) -> Option<String> {
-        let where_ = &self.at;
-        let location = &self.on_line_and_column.map(|(line_of_start, column_of_start)| {
-            format!("On line {line_of_start} and column {column_of_start}{}",
-                    self.up_to_line_an_column.map(|(line_of_end, column_of_end)|
-                        format!(" up to line {line_of_end} and column {column_of_end}")).unwrap_or_default())
+    fn __as_display_string(&self, is_displaying_as_cause_of_other: bool, options: &DisplayOptions) -> Option<String> {
+        let (description, causes) = self.__as_display_parts(is_displaying_as_cause_of_other, options);
+        let causes = causes.map(|causes| indent_lines_except_first(causes.prefix + &causes.chunks.collect::<String>(), causes.spaces));
+        Some(join_strings("\n", Some(description).into_iter().chain(causes)))
+    }
+
+    /// Splits the display given by [SimpleErrorDisplayInfo::__as_display_string] into the lines
+    /// describing this error up to the 'Has' line, and its causes, which are rendered one by one as
+    /// they are iterated, so they can be written without holding the whole display.
+    pub(crate) fn __as_display_parts<'display>(&'display self, is_displaying_as_cause_of_other: bool, options: &'display DisplayOptions) -> (String, Option<CausesDisplay<'display>>) {
+        let where_ = &self.at.as_ref().map(|at| options.tab_rendering.render(at));
+        let context = &Some(options.context_lines).filter(|context_lines| *context_lines > 0)
+            .and_then(|context_lines| self.context_string(context_lines, options));
+        let location = &self.position_string().map(|position| match (&options.position_link, self.on_line_and_column) {
+            (Some(position_link), Some((line, _))) => hyperlink(&format!("{position_link}#L{line}"), &position),
+            _ => position,
         });
-        let description = &self.reason.clone().or(Some("Unexplained error".to_string()));
-        let solution = &self.solution;
+        let description = &self.reason.clone().or(Some(options.unexplained_error.clone()));
+        let solution = &self.solution_with_steps();
+        let details = &Some(&self.metadata).filter(|metadata| options.show_metadata && !metadata.is_empty())
+            .map(|metadata| join_strings("\n", metadata.iter().map(|(key, value)| format!("{key}: {value}"))));
+        let doc_url = &self.doc_url.as_ref().map(|doc_url| if options.link_doc_urls { hyperlink(doc_url, doc_url) } else { doc_url.clone() });
 
-        let explained_causes_count = Some(pluralize(self.unexplained_causes, "unexplained cause", ""))
-            .filter(|string| !string.is_empty()).map(|string| string.trim().to_string());
-        let unexplained_causes_count = Some(pluralize(self.explained_causes.len(), "explained cause", ""))
-            .filter(|string| !string.is_empty()).map(|string| string.trim().to_string());
+        let explained_causes_count = Some(self.unexplained_causes).filter(|count| *count > 0)
+            .map(|count| (options.number_formatter.count)(count, CountedNoun::UnexplainedCause).trim().to_string());
+        let unexplained_causes_count = Some(self.explained_causes.len()).filter(|count| *count > 0)
+            .map(|count| (options.number_formatter.count)(count, CountedNoun::ExplainedCause).trim().to_string());
 
         let causes_is_just_one_explained = self.explained_causes.len() == 1 && self.unexplained_causes == 0;
 
         let causes_count = &if causes_is_just_one_explained { None } else if explained_causes_count.is_some() && unexplained_causes_count.is_some() {
-            Some(format!("{} and {}", unexplained_causes_count.unwrap(), explained_causes_count.unwrap()))
+            Some(format!("{} {} {}", unexplained_causes_count.unwrap(), options.count_conjunction, explained_causes_count.unwrap()))
         } else if explained_causes_count.is_some() || unexplained_causes_count.is_some() {
-            Some(format!("{}", explained_causes_count.or(unexplained_causes_count).unwrap()))
+            Some(explained_causes_count.or(unexplained_causes_count).unwrap().to_string())
         } else {
             None
         }.map(|cause| format!("{cause}."));
 
-        let explained_causes = &match self.explained_causes.len() {
+        let causes_prefix = if causes_is_just_one_explained { &options.cause_label } else { &options.causes_label };
+        let extra_ident_on_causes = if is_displaying_as_cause_of_other { 2 } else { 0 };
+        // Causes get shifted to the right when displayed, so they are wrapped at a narrower width,
+        // and they are one level deeper, so they can go one level less deep.
+        let narrowed_options = (options.wrap_width.is_some() || options.max_depth.is_some()).then(|| {
+            let causes_prefix_len = extra_ident_on_causes + display_width(causes_prefix) + 2;
+            let shift = causes_prefix_len.min(options.indentation + extra_ident_on_causes);
+            DisplayOptions {
+                wrap_width: options.wrap_width.map(|wrap_width| wrap_width.saturating_sub(shift)),
+                max_depth: options.max_depth.map(|max_depth| max_depth.saturating_sub(1)),
+                ..options.clone()
+            }
+        });
+        let cause_options = narrowed_options.map(Cow::Owned).unwrap_or(Cow::Borrowed(options));
+        let explained_causes: Option<Box<dyn Iterator<Item=String> + 'display>> = match self.explained_causes.len() {
             0 => None,
-            1 => Some(self.explained_causes.get(0).unwrap().__as_display_string(true).unwrap()),
-            _ => {
-                let explained_causes = self.explained_causes.iter().map(|cause| cause.__as_display_string(true))
+            _ if options.max_depth == Some(0) => Some(Box::new(iter::once(options.omitted_causes_text.clone()))),
+            1 if options.max_causes_per_level != Some(0) => {
+                let cause = self.explained_causes.first().unwrap();
+                Some(Box::new(iter::once_with(move || format!("\n{}", cause.__as_display_string(true, &cause_options).unwrap()))))
+            }
+            causes_len => {
+                let shown_causes = options.max_causes_per_level.unwrap_or(usize::MAX).min(causes_len);
+                let explained_causes = self.ordered_causes(options.cause_ordering).into_iter().take(shown_causes)
+                    .map(move |cause| cause.__as_display_string(true, &cause_options))
                     .map(|opt| opt.unwrap())
                     .enumerate()
-                    .map(|(cause_no, cause)| format!("- Cause nº {} -\n{cause}", cause_no + 1));
-                Some(join_strings("\n\n", explained_causes))
+                    .map(move |(cause_no, cause)| format!("- {} {} -\n{cause}", options.cause_header, (options.number_formatter.ordinal)(cause_no + 1)));
+                let more_causes = Some(causes_len - shown_causes).filter(|more_causes| *more_causes > 0)
+                    .map(|more_causes| format!("{} {}", options.more_causes_prefix, (options.number_formatter.count)(more_causes, CountedNoun::MoreCause).trim()));
+                Some(Box::new(explained_causes.chain(more_causes).enumerate()
+                    .map(move |(chunk_no, chunk)| format!("{}{chunk}", if chunk_no == 0 { "\n" } else { &options.cause_separator }))))
             }
-        }.map(|explained_cause| format!("\n{explained_cause}"));
+        };
 
-        let causes_prefix = if causes_is_just_one_explained { "Cause" } else { "Causes" };
+        let labels = self.labels.iter().map(|label| options.tab_rendering.render(&label.as_display_string())).collect::<Vec<_>>();
+        let suggestions = self.suggestions.iter().map(|suggestion| options.tab_rendering.render(&suggestion.as_display_string())).collect::<Vec<_>>();
 
         let description_lines = [
-            ("Position", usize::MAX, location),
-            ("At", usize::MAX, where_),
-            ("Error", usize::MAX, description),
-            ("Solution", usize::MAX, solution),
-            ("Has", usize::MAX, causes_count),
-            (causes_prefix, 2, explained_causes)
+            (&options.position_label, location.as_ref()),
+            (&options.at_label, where_.as_ref()),
+            (&options.context_label, context.as_ref()),
         ]
             .into_iter()
-            .filter(|(_, _, contents)| contents.is_some())
-            .map(|(prefix, max_ident, contents)| {
+            .chain(labels.iter().map(|label| (&options.label_label, Some(label))))
+            .chain([
+                (&options.code_label, self.code.as_ref()),
+                (options.severity_label(self.severity), description.as_ref()),
+                (&options.solution_label, solution.as_ref()),
+            ])
+            .chain(self.suggestions.iter().zip(&suggestions).map(|(suggestion, suggestion_string)| {
+                let label = if suggestion.is_uncertain() { &options.possible_fix_label } else { &options.suggestion_label };
+                (label, Some(suggestion_string))
+            }))
+            .chain(self.notes.iter().map(|note| (&options.note_label, Some(note))))
+            .chain(self.helps.iter().map(|help| (&options.help_label, Some(help))))
+            .chain([
+                (&options.more_info_label, doc_url.as_ref()),
+                (&options.details_label, details.as_ref()),
+                (&options.backtrace_label, self.backtrace.as_ref().filter(|_| options.show_backtraces)),
+                (&options.has_label, causes_count.as_ref()),
+            ])
+            .filter(|(_, contents)| contents.is_some())
+            .map(|(prefix, contents)| {
                 let contents = contents.as_ref().unwrap();
-                let prefix = if is_displaying_as_cause_of_other { "- " } else { "" }.to_string() + &prefix.to_string() + ": ";
+                let prefix = if is_displaying_as_cause_of_other { "- " } else { "" }.to_string() + prefix + ": ";
                 let prefixed_contents = format!("{prefix}{contents}");
-                let extra_ident_on_causes = if is_displaying_as_cause_of_other { 2 } else { 0 };
-                let spaces = prefix.len().min(max_ident.checked_add(extra_ident_on_causes).unwrap_or(usize::MAX));
-                let spaced_contents = ident_lines_except_first(prefixed_contents, spaces);
-                spaced_contents
+                let spaces = display_width(&prefix);
+                let prefixed_contents = match options.wrap_width {
+                    Some(wrap_width) => wrap_lines(&prefixed_contents, wrap_width, spaces),
+                    _ => prefixed_contents,
+                };
+                indent_lines_except_first(prefixed_contents, spaces)
             });
         let res = join_strings("\n", description_lines);
-        Some(res)
+        let causes = explained_causes.map(|chunks| {
+            let prefix = if is_displaying_as_cause_of_other { "- " } else { "" }.to_string() + causes_prefix + ": ";
+            let spaces = display_width(&prefix).min(options.indentation + extra_ident_on_causes);
+            CausesDisplay { prefix, spaces, chunks }
+        });
+        (res, causes)
+    }
+}
+
+/// Causes of an error as given by [SimpleErrorDisplayInfo::__as_display_parts], whose display is
+/// *prefix* followed by every chunk, where every line but the first one is indented by *spaces*.
+pub(crate) struct CausesDisplay<'display> {
+    /// Label the causes start with, like 'Causes: '.
+    pub(crate) prefix: String,
+    /// Spaces every line of the causes but the first one is indented by.
+    pub(crate) spaces: usize,
+    /// Every cause along with its header and the separator before it, rendered as it is iterated.
+    pub(crate) chunks: Box<dyn Iterator<Item=String> + 'display>,
+}
+
+/// Stably sorts *items* following *ordering*, where *display_of* gives the error each item holds.
+fn sort_by_ordering<T>(items: &mut [T], display_of: impl Fn(&T) -> &SimpleErrorDisplayInfo, ordering: CauseOrdering) {
+    match ordering {
+        CauseOrdering::Insertion => {}
+        CauseOrdering::SourcePosition => items.sort_by_key(|item| {
+            let position = display_of(item).on_line_and_column;
+            (position.is_none(), position)
+        }),
+        CauseOrdering::Complexity => items.sort_by_key(|item| display_of(item).complexity()),
+        CauseOrdering::Severity => items.sort_by_key(|item| core::cmp::Reverse(display_of(item).severity)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::SimpleError;
+    use crate::{Severity, SimpleErrorExplanation};
+
+    #[test]
+    fn summaries_fit_every_cause_in_one_line() {
+        let error = SimpleError::from("Couldn't compile code".to_string())
+            .with_cause("Missing variable `x`".to_string())
+            .with_cause(SimpleError::from("Missing function `f`".to_string()).with_cause("Expected\n a name".to_string()));
+        let summary = "Couldn't compile code (2 causes: Missing variable `x`, Missing function `f` (1 cause: Expected a name))";
+        assert_eq!(error.summary(), summary);
+        assert_eq!(error.as_display_struct(false).summary(), summary);
+        assert_eq!(SimpleError::new().summary(), "Unexplained error");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn extra_fields_of_details_are_serialized_as_fields_of_the_error() {
+        use alloc::string::String;
+
+        use crate::{SimpleErrorDetail, SimpleErrorExplanation};
+
+        #[derive(Debug)]
+        struct BrokenRule;
+
+        impl SimpleErrorDetail for BrokenRule {
+            fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+                SimpleErrorExplanation::new().explanation("Rule `no-tabs` was broken")
+            }
+
+            fn extra_fields(&self) -> serde_json::Map<String, serde_json::Value> {
+                let mut fields = serde_json::Map::new();
+                fields.insert("rule".to_string(), "no-tabs".into());
+                fields
+            }
+        }
+
+        let display = SimpleError::new().error_detail(BrokenRule).as_display_struct(false);
+        assert_eq!(display.extra_fields().get("rule"), Some(&serde_json::Value::from("no-tabs")));
+        let serialized = serde_json::to_value(&display).unwrap();
+        assert_eq!(serialized["rule"], "no-tabs");
+        assert_eq!(serialized["reason"], "Rule `no-tabs` was broken");
+        assert!(SimpleError::new().as_display_struct(false).extra_fields().is_empty());
+    }
+
+    #[test]
+    fn displays_can_be_read_through_their_accessors() {
+        let display = SimpleError::from(SimpleErrorExplanation::new().explanation("Couldn't compile").solution("Fix the causes").code("E1"))
+            .start_point_of_error(1, 2).with_cause("Missing ;".to_string()).with_cause(SimpleError::new())
+            .as_display_struct(false);
+        assert_eq!(display.reason(), Some("Couldn't compile"));
+        assert_eq!(display.solution(), Some("Fix the causes"));
+        assert_eq!(display.code(), Some("E1"));
+        assert_eq!(display.severity(), Severity::Error);
+        assert_eq!(display.positions(), (Some((1, 2)), None));
+        assert_eq!(display.at(), None);
+        assert_eq!(display.explained_causes()[0].reason(), Some("Missing ;"));
+        assert_eq!(display.unexplained_causes(), 1);
+    }
+
+    #[test]
+    fn errors_rebuilt_from_displays_keep_every_detail() {
+        let input = "let x = y;";
+        let explanation = SimpleErrorExplanation::new().explanation("Unknown variable").solution("Declare it").code("E1").severity(Severity::Warning)
+            .note("Variables must be declared").help("Use let").doc_url("https://example.com/E1").solution_step("Add a let")
+            .label(&input[4..5], "Assigned here").suggestion(&input[8..9], "1");
+        let display = SimpleError::new().at(&input[8..9]).error_detail(explanation).start_point_of_error(1, 9).end_point_of_error(1, 9)
+            .with_cause("Missing ;".to_string()).with_cause(SimpleError::new())
+            .as_display_struct(false);
+        let rebuilt: SimpleError<'static> = display.clone().into();
+        assert_eq!(rebuilt.as_display_struct(false), display);
     }
 }
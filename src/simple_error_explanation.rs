@@ -1,28 +1,69 @@
 use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
 
-/// Holds a possible explanation and solution for an error, and for parsing errors it also holds a
-/// [Colorizer] for colorizing both the whole and parts of the input indicated on [SimpleError::at].
+use crate::formatting::dedent;
+#[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+use crate::markers::Marker;
+use crate::severity::Severity;
+use crate::simple_error_detail::MaybeSendSync;
+use crate::span::{Location, Span};
+use crate::suggestion::Applicability;
+
+/// Holds a possible explanation and solution for an error, and for parsing errors it also holds
+/// [crate::markers::Marker]s for colorizing both the whole and parts of the input indicated on
+/// [SimpleError::at].
 #[derive(Default, Debug, Clone)]
 pub struct SimpleErrorExplanation<'input> {
     pub(crate) explanation: Option<String>,
+    pub(crate) lazy_explanation: Option<LazyText<'input>>,
     pub(crate) solution: Option<String>,
-    #[cfg(feature = "colorization")]
-    pub(crate) whole_marker: Option<string_colorization::Colorizer>,
-    #[cfg(feature = "colorization")]
-    pub(crate) colorization_markers: Vec<(&'input str, string_colorization::Colorizer)>,
-    #[cfg(not(feature = "colorization"))]
-    _input_lifetime: core::marker::PhantomData<&'input ()>
+    pub(crate) lazy_solution: Option<LazyText<'input>>,
+    pub(crate) solution_steps: Vec<String>,
+    pub(crate) code: Option<String>,
+    pub(crate) severity: Severity,
+    pub(crate) notes: Vec<String>,
+    pub(crate) helps: Vec<String>,
+    pub(crate) doc_url: Option<String>,
+    pub(crate) labels: Vec<(Location<'input>, String)>,
+    pub(crate) suggestions: Vec<(Location<'input>, String, Option<Applicability>)>,
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+    pub(crate) whole_marker: Option<Marker>,
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+    pub(crate) colorization_markers: Vec<(&'input str, Marker)>,
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+    pub(crate) span_colorization_markers: Vec<(Span, Marker)>,
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+    pub(crate) at_colorization_markers: Vec<(Span, Marker)>,
+    pub(crate) marked_spans: Vec<&'input str>,
+    #[cfg(feature = "serde")]
+    pub(crate) data: Option<serde_json::Value>,
+}
+
+/// Text computed by a closure only once the error is displayed.
+#[derive(Clone)]
+pub(crate) struct LazyText<'input>(Arc<dyn LazyTextFn + 'input>);
+
+/// Closure computing a [LazyText], which is [Send] and [Sync] only when using the sync feature.
+pub(crate) trait LazyTextFn: Fn() -> String + MaybeSendSync {}
+
+impl<T: Fn() -> String + MaybeSendSync + ?Sized> LazyTextFn for T {}
+
+impl Debug for LazyText<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("LazyText(..)")
+    }
 }
 
 impl<'input> SimpleErrorExplanation<'input> {
 
     /// Creates a new empty [SimpleErrorExplanation]
     pub fn new() -> Self {
-        #[cfg(feature = "colorization")]
-        let res = Self { explanation: None, solution: None, colorization_markers: Vec::new(), whole_marker: None };
-        #[cfg(not(feature = "colorization"))]
-        let res = Self { explanation: None, solution: None, _input_lifetime: Default::default() };
+        #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+        let res = Self { explanation: None, lazy_explanation: None, solution: None, lazy_solution: None, solution_steps: Vec::new(), code: None, severity: Severity::Error, notes: Vec::new(), helps: Vec::new(), doc_url: None, labels: Vec::new(), suggestions: Vec::new(), colorization_markers: Vec::new(), span_colorization_markers: Vec::new(), at_colorization_markers: Vec::new(), whole_marker: None, marked_spans: Vec::new(), #[cfg(feature = "serde")] data: None };
+        #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+        let res = Self { explanation: None, lazy_explanation: None, solution: None, lazy_solution: None, solution_steps: Vec::new(), code: None, severity: Severity::Error, notes: Vec::new(), helps: Vec::new(), doc_url: None, labels: Vec::new(), suggestions: Vec::new(), marked_spans: Vec::new(), #[cfg(feature = "serde")] data: None };
         res
     }
 
@@ -32,46 +73,431 @@ impl<'input> SimpleErrorExplanation<'input> {
         self.explanation = Some(explanation.into())
             .map(|explanation| explanation.trim().to_string())
             .filter(|explanation| !explanation.is_empty());
+        self.lazy_explanation = None;
+        self
+    }
+
+    /// Adds an explanation on why this error happened as [SimpleErrorExplanation::explanation]
+    /// does, but computed by *explanation* only once the error is displayed or serialized, so
+    /// explanations kept as error details, such as those created on hot parsing paths, don't
+    /// allocate their texts unless they are actually shown.
+    ///
+    /// The closure is shared behind an [Arc] so explanations can still be cloned, which allocates
+    /// once per call regardless of what the closure captures. When using the sync feature, the
+    /// closure must be [Send] and [Sync] (See [MaybeSendSync]).
+    pub fn explanation_with<Explanation: Fn() -> String + MaybeSendSync + 'input>(mut self, explanation: Explanation) -> Self {
+        self.explanation = None;
+        self.lazy_explanation = Some(LazyText(Arc::new(explanation)));
         self
     }
 
     /// Adds a solution on how to solve this error, like 'Create variable ***my_missing_variable***
     /// before using it'.
+    ///
+    /// The indentation shared by all of its lines is removed, so code samples written with raw
+    /// string literals inside indented code aren't shown weirdly indented, use
+    /// [SimpleErrorExplanation::solution_verbatim] to keep the solution exactly as given.
     pub fn solution<Str:Into<String>>(mut self, solution: Str) -> Self {
+        self.solution = Some(dedent(&solution.into()))
+            .filter(|solution| !solution.is_empty());
+        self.lazy_solution = None;
+        self
+    }
+
+    /// Adds a solution on how to solve this error as [SimpleErrorExplanation::solution] does, but
+    /// computed by *solution* only once the error is displayed or serialized, see
+    /// [SimpleErrorExplanation::explanation_with], which also tells the cost and bounds of *solution*.
+    pub fn solution_with<Solution: Fn() -> String + MaybeSendSync + 'input>(mut self, solution: Solution) -> Self {
+        self.solution = None;
+        self.lazy_solution = Some(LazyText(Arc::new(solution)));
+        self
+    }
+
+    /// Adds a solution on how to solve this error exactly as given, without trimming nor removing
+    /// the indentation of its lines as [SimpleErrorExplanation::solution] does.
+    pub fn solution_verbatim<Str:Into<String>>(mut self, solution: Str) -> Self {
         self.solution = Some(solution.into())
-            .map(|solution| solution.trim().to_string())
             .filter(|solution| !solution.is_empty());
+        self.lazy_solution = None;
         self
     }
 
-    #[cfg(feature = "colorization")]
+    /// Computes the explanation and solution given through [SimpleErrorExplanation::explanation_with]
+    /// and [SimpleErrorExplanation::solution_with], if any.
+    pub(crate) fn evaluated(mut self) -> Self {
+        if let Some(LazyText(explanation)) = self.lazy_explanation.take() {
+            self = self.explanation(explanation());
+        }
+        if let Some(LazyText(solution)) = self.lazy_solution.take() {
+            self = self.solution(solution());
+        }
+        self
+    }
+
+    /// Adds the ordered steps to follow to solve this error, like `["Add the import", "Rename the
+    /// variable"]`, these are displayed as a numbered list after the solution and kept as a list
+    /// when serializing the error, so tools can track which steps were completed.
+    pub fn solution_steps<Step, Steps>(mut self, steps: Steps) -> Self
+        where Step: Into<String>,
+              Steps: IntoIterator<Item=Step> {
+        self.solution_steps.extend(steps.into_iter()
+            .map(|step| step.into().trim().to_string())
+            .filter(|step| !step.is_empty()));
+        self
+    }
+
+    /// Adds a step after the ones already given, see [SimpleErrorExplanation::solution_steps].
+    pub fn solution_step<Step: Into<String>>(self, step: Step) -> Self {
+        self.solution_steps([step])
+    }
+
+    /// Adds a link to documentation explaining this kind of error in depth, like
+    /// 'https://example.com/errors/E0425', displayed after the notes and helps.
+    pub fn doc_url<Url: Into<String>>(mut self, doc_url: Url) -> Self {
+        self.doc_url = Some(doc_url.into().trim().to_string())
+            .filter(|doc_url| !doc_url.is_empty());
+        self
+    }
+
+    /// Adds a code identifying the kind of this error, like 'E0425', codes let tools group and
+    /// count errors of the same kind, see [crate::ErrorAccumulator::with_budget].
+    pub fn code<Str:Into<String>>(mut self, code: Str) -> Self {
+        self.code = Some(code.into())
+            .map(|code| code.trim().to_string())
+            .filter(|code| !code.is_empty());
+        self
+    }
+
+    /// This is only implemented when using the serde feature.
+    ///
+    /// Attaches *data* to this error, like the literal that failed type-checking, which isn't
+    /// displayed but is kept untouched on its [crate::SimpleErrorDisplayInfo] and serialized as its
+    /// `data` field, for machines needing the raw offending values.
+    #[cfg(feature = "serde")]
+    pub fn data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets how serious this error is, by default, it is [Severity::Error], the severity changes the
+    /// label of the line explaining the error, like 'Warning: Variable x is never used'.
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Adds a note giving extra context about this error, like 'Variable ***my_variable*** is
+    /// declared on another scope', this can be called multiple times to add multiple notes.
+    pub fn note<Str:Into<String>>(mut self, note: Str) -> Self {
+        let note = note.into().trim().to_string();
+        if !note.is_empty() {
+            self.notes.push(note);
+        }
+        self
+    }
+
+    /// Adds extra help on how to avoid this error, like 'Variables must be declared before being
+    /// used', this can be called multiple times to add multiple helps.
+    pub fn help<Str:Into<String>>(mut self, help: Str) -> Self {
+        let help = help.into().trim().to_string();
+        if !help.is_empty() {
+            self.helps.push(help);
+        }
+        self
+    }
+
+    /// Attaches a message to another place related to this error, like 'First defined here' on a
+    /// redefined variable, *location* being either a &str referencing the input or a [Span] of it.
+    ///
+    /// Labels get displayed under the 'At' line, so a single error can point at multiple places
+    /// instead of requiring an error cause for each of them.
+    pub fn label<LocationLike: Into<Location<'input>>, Str: Into<String>>(mut self, location: LocationLike, message: Str) -> Self {
+        let message = message.into().trim().to_string();
+        if !message.is_empty() {
+            self.labels.push((location.into(), message));
+        }
+        self
+    }
+
+    /// Suggests replacing the part of the input at *location* with *replacement*, *location* being
+    /// either a &str referencing the input or a [Span] of it, an empty *replacement* suggests
+    /// removing said part, while an empty location suggests inserting *replacement* there.
+    ///
+    /// Suggestions get displayed after the solution, and can be applied automatically through
+    /// [crate::SimpleError::apply_suggestions].
+    pub fn suggestion<LocationLike: Into<Location<'input>>, Str: Into<String>>(mut self, location: LocationLike, replacement: Str) -> Self {
+        self.suggestions.push((location.into(), replacement.into(), None));
+        self
+    }
+
+    /// Suggests replacing the part of the input at *location* with *replacement* as
+    /// [SimpleErrorExplanation::suggestion] does, but saying how confident the suggestion is
+    /// through *applicability*, suggestions that aren't [Applicability::MachineApplicable] are
+    /// displayed as possible fixes and aren't applied by
+    /// [crate::SimpleError::apply_machine_applicable_suggestions].
+    pub fn suggestion_with_applicability<LocationLike: Into<Location<'input>>, Str: Into<String>>(mut self, location: LocationLike, replacement: Str, applicability: Applicability) -> Self {
+        self.suggestions.push((location.into(), replacement.into(), Some(applicability)));
+        self
+    }
+
+    /// Marks a substring belonging to the input indicated at [SimpleError::at] as important,
+    /// without requiring the 'colorization' feature, it requires the substring is a reference taken
+    /// from the same input indicated on [SimpleError::at], or else, it won't get marked.
+    ///
+    /// When the error is displayed with colors, the substring gets underlined, otherwise, it gets
+    /// surrounded by brackets, like 'if >>>missing_variable<<< > 0'.
+    pub fn mark_span(mut self, string: &'input str) -> Self {
+        self.marked_spans.push(string);
+        self
+    }
+
+    /// Marks substrings belonging to the input indicated at [SimpleError::at] as important, see
+    /// [SimpleErrorExplanation::mark_span].
+    pub fn mark_spans<Input, SpanIterator>(mut self, strings: SpanIterator) -> Self
+        where Input: Into<&'input str>,
+              SpanIterator: IntoIterator<Item=Input> {
+        self.marked_spans.extend(strings.into_iter().map(|string| string.into()));
+        self
+    }
+
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
     /// Marker for colorizing the whole input indicated at [SimpleError::at], this is used on
     /// parsing errors.
-    pub const fn whole_input_colorization(mut self, complete_marker: string_colorization::Colorizer) -> Self {
+    pub const fn whole_input_colorization(mut self, complete_marker: Marker) -> Self {
         self.whole_marker = Some(complete_marker);
         self
     }
 
-    #[cfg(feature = "colorization")]
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
     /// Markers for colorizing the substrings belonging to the input indicated at [SimpleError::at],
     /// this is used on parsing errors, but it requires the substring are references taken from the
     /// same input indicated on [SimpleError::at], or else, they won't get colorized following
     /// [string_colorization::colorize] restrictions.
     pub fn colorization_markers<Color, Input, MarkerIterator>(mut self, colorization_markers: MarkerIterator) -> Self
-        where Color: Into<string_colorization::Colorizer>,
+        where Color: Into<Marker>,
               Input: Into<&'input str>,
               MarkerIterator: IntoIterator<Item=(Input, Color)> {
         self.colorization_markers.extend(colorization_markers.into_iter().map(|(input, color)| (input.into(), color.into())));
         self
     }
 
-    #[cfg(feature = "colorization")]
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
     /// Marker for colorizing the substrings belonging to the input indicated at [SimpleError::at],
     /// this is used on parsing errors, but it requires the substring are references taken from the
     /// same input indicated on [SimpleError::at], or else, they won't get colorized following
     /// [string_colorization::colorize] restrictions.
-    pub fn colorization_marker(mut self, string: &'input str, colorization: string_colorization::Colorizer) -> Self {
+    pub fn colorization_marker(mut self, string: &'input str, colorization: Marker) -> Self {
         self.colorization_markers.push((string, colorization));
         self
     }
+
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+    /// Marker for colorizing the bytes of the input covered by *span*, where *span* uses the same
+    /// offsets as [SimpleError::at_span], this is an alternative to
+    /// [SimpleErrorExplanation::colorization_marker] for errors built from offsets.
+    ///
+    /// This marker is only applied when the input these offsets refer to is registered through
+    /// [SimpleError::with_source].
+    pub fn colorization_marker_span<SpanLike: Into<Span>>(mut self, span: SpanLike, colorization: Marker) -> Self {
+        self.span_colorization_markers.push((span.into(), colorization));
+        self
+    }
+
+    #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+    /// Without the 'colorization' or 'colorization-anstyle' features this does nothing, it only
+    /// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+    /// said features are enabled or not.
+    pub fn colorization_marker_span<SpanLike: Into<Span>, Color>(self, _span: SpanLike, _colorization: Color) -> Self {
+        self
+    }
+
+    #[cfg(any(feature = "colorization", feature = "colorization-anstyle"))]
+    /// Marker for colorizing the bytes covered by *range*, given as offsets relative to the text
+    /// indicated at [SimpleError::at], like `0..3` for its first three bytes, this is an
+    /// alternative to [SimpleErrorExplanation::colorization_marker] that doesn't require a
+    /// reference into said text, so it also works when the text is owned, like on
+    /// [SimpleError::at_owned], or was copied.
+    ///
+    /// Ranges not lying on the text or not falling on char boundaries are ignored.
+    pub fn colorization_marker_in_at<Range: Into<Span>>(mut self, range: Range, colorization: Marker) -> Self {
+        self.at_colorization_markers.push((range.into(), colorization));
+        self
+    }
+
+    #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+    /// Without the 'colorization' or 'colorization-anstyle' features this does nothing, it only
+    /// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+    /// said features are enabled or not.
+    pub fn colorization_marker_in_at<Range: Into<Span>, Color>(self, _range: Range, _colorization: Color) -> Self {
+        self
+    }
+
+    #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+    /// Without the 'colorization' or 'colorization-anstyle' features this does nothing, it only
+    /// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+    /// said features are enabled or not.
+    pub fn whole_input_colorization<Color>(self, _complete_marker: Color) -> Self {
+        self
+    }
+
+    #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+    /// Without the 'colorization' or 'colorization-anstyle' features this does nothing, it only
+    /// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+    /// said features are enabled or not.
+    pub fn colorization_markers<Color, Input, MarkerIterator>(self, _colorization_markers: MarkerIterator) -> Self
+        where Input: Into<&'input str>,
+              MarkerIterator: IntoIterator<Item=(Input, Color)> {
+        self
+    }
+
+    #[cfg(not(any(feature = "colorization", feature = "colorization-anstyle")))]
+    /// Without the 'colorization' or 'colorization-anstyle' features this does nothing, it only
+    /// exists so the same [crate::SimpleErrorDetail::explain_error] implementation compiles whether
+    /// said features are enabled or not.
+    pub fn colorization_marker<Color>(self, _string: &'input str, _colorization: Color) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::SimpleError;
+    use crate::markers::{context, error_span};
+    use crate::DisplayTemplate;
+    use crate::DisplayOptions;
+
+    #[test]
+    #[cfg_attr(not(any(feature = "colorization", feature = "colorization-anstyle")), allow(clippy::unit_arg))]
+    fn colorization_markers_compile_and_render_with_any_feature_set() {
+        let input = "let a = ;";
+        let explanation = SimpleErrorExplanation::new()
+            .whole_input_colorization(context())
+            .colorization_marker(&input[8..9], error_span())
+            .colorization_markers([(&input[4..5], error_span())])
+            .explanation("Missing value");
+        let error = SimpleError::new().error_detail(explanation).at(input);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "At: let a = ;\nError: Missing value");
+    }
+
+    #[test]
+    fn marked_spans_are_delimited_when_not_colorizing() {
+        let input = "if missing > 0 && other";
+        let explanation = SimpleErrorExplanation::new().mark_span(&input[3..10]).mark_spans([&input[18..23]]).explanation("Unknown variables");
+        let error = SimpleError::new().error_detail(explanation).at(input);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "At: if >>>missing<<< > 0 && >>>other<<<\nError: Unknown variables");
+    }
+
+    #[test]
+    fn notes_and_helps_are_displayed_in_order_and_kept_on_conversion() {
+        let explanation = SimpleErrorExplanation::new().explanation("x is defined twice").solution("Rename x")
+            .note("First defined in line 1").note("Redefined in line 3").help("Shadowing requires a new let");
+        let display = SimpleError::new().error_detail(explanation).as_display_struct(false);
+        assert_eq!(display.as_display_string(),
+                   "Error: x is defined twice\nSolution: Rename x\nNote: First defined in line 1\nNote: Redefined in line 3\nHelp: Shadowing requires a new let");
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+    }
+
+    #[test]
+    fn labels_point_at_related_locations() {
+        let source = "let x = 1;\nlet x = 2;";
+        let explanation = SimpleErrorExplanation::new().explanation("x is defined twice")
+            .label(&source[4..5], "First defined here").label(0..3, "Declared with");
+        let with_source = SimpleError::new().error_detail(explanation.clone()).at(&source[15..16]).with_source(source);
+        assert_eq!(with_source.as_display_struct(false).as_display_string(),
+                   concat!("Position: On line 2 and column 5 up to line 2 and column 5\nAt: x\n",
+                           "Label: 'x' on line 1 and column 5: First defined here\nLabel: 'let' on line 1 and column 1: Declared with\n",
+                           "Error: x is defined twice"));
+        let without_source = SimpleError::new().error_detail(explanation);
+        assert_eq!(without_source.as_display_struct(false).as_display_string(),
+                   "Label: 'x': First defined here\nLabel: bytes 0 up to 3: Declared with\nError: x is defined twice");
+    }
+
+    #[test]
+    fn solution_steps_are_numbered_and_blank_ones_skipped() {
+        let explanation = SimpleErrorExplanation::new().explanation("Unknown variable").solution("Do this:")
+            .solution_steps(["Add the import", "Rename the variable"]).solution_step(" ");
+        let display = SimpleError::new().error_detail(explanation).as_display_struct(false);
+        assert_eq!(display.as_display_string(), "Error: Unknown variable\nSolution: Do this:\n          1. Add the import\n          2. Rename the variable");
+        let steps_only = SimpleError::new().error_detail(SimpleErrorExplanation::new().solution_step("Add the import"));
+        assert_eq!(steps_only.as_display_struct(false).as_display_string(), "Error: Unexplained error\nSolution: 1. Add the import");
+        let template = DisplayTemplate::parse("{solution_steps}").unwrap();
+        assert_eq!(display.as_display_string_with_template(&template), "1. Add the import\n2. Rename the variable");
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+    }
+
+    #[test]
+    fn doc_urls_are_shown_as_more_info_lines() {
+        let explanation = SimpleErrorExplanation::new().explanation("Unknown variable").help("Declare it first").doc_url("https://example.com/E001");
+        let display = SimpleError::new().error_detail(explanation).as_display_struct(false);
+        assert_eq!(display.as_display_string(), "Error: Unknown variable\nHelp: Declare it first\nMore info: https://example.com/E001");
+        let options = DisplayOptions { link_doc_urls: true, ..DisplayOptions::default() };
+        assert!(display.as_display_string_with_options(&options)
+            .ends_with("More info: \u{1b}]8;;https://example.com/E001\u{1b}\\https://example.com/E001\u{1b}]8;;\u{1b}\\"));
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+        assert!(SimpleError::new().error_detail(SimpleErrorExplanation::new().doc_url("https://example.com")).as_display_struct(false).is_explained());
+    }
+
+    #[test]
+    fn lazy_texts_are_computed_only_when_displayed_and_eager_ones_win() {
+        let calls = AtomicUsize::new(0);
+        let explanation = SimpleErrorExplanation::new()
+            .explanation_with(|| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                format!("  Variable {} doesn't exist ", "x")
+            })
+            .solution_with(|| String::from("    Declare it:\n        let x = 1;"));
+        let error = SimpleError::new().error_detail(explanation);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "Error: Variable x doesn't exist\nSolution: Declare it:\n              let x = 1;");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let overridden = SimpleErrorExplanation::new().explanation_with(|| String::from("Lazy")).explanation("Eager");
+        assert_eq!(SimpleError::new().error_detail(overridden).as_display_struct(false).as_display_string(), "Error: Eager");
+    }
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    fn lazy_texts_can_capture_values_that_cant_be_shared_between_threads() {
+        let name = alloc::rc::Rc::new(String::from("x"));
+        let explanation = SimpleErrorExplanation::new().explanation_with(move || format!("Variable {name} doesn't exist"));
+        assert_eq!(SimpleError::new().error_detail(explanation).as_display_struct(false).as_display_string(), "Error: Variable x doesn't exist");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn raw_data_is_carried_through_serialization_but_not_displayed() {
+        #[derive(Debug)]
+        struct InvalidLiteral;
+
+        impl crate::SimpleErrorDetail for InvalidLiteral {
+            fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+                SimpleErrorExplanation::new().explanation("Invalid literal").data(serde_json::json!({ "literal": 3 }))
+            }
+        }
+
+        let display = SimpleError::new().error_detail(InvalidLiteral).as_display_struct(false);
+        assert_eq!(display.data(), Some(&serde_json::json!({ "literal": 3 })));
+        assert_eq!(display.as_display_string(), "Error: Invalid literal");
+        let json = serde_json::to_string(&display).unwrap();
+        assert!(json.contains("\"data\":{\"literal\":3}"));
+        let deserialized: crate::SimpleErrorDisplayInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, display);
+        assert_eq!(SimpleError::from(deserialized).as_display_struct(false), display);
+    }
+
+    #[test]
+    #[cfg_attr(not(any(feature = "colorization", feature = "colorization-anstyle")), allow(clippy::unit_arg))]
+    fn markers_relative_to_the_at_text_work_on_owned_texts() {
+        let explanation = SimpleErrorExplanation::new().explanation("Unused variable foo")
+            .colorization_marker_in_at(4..7, error_span()).colorization_marker_in_at(50..60, error_span());
+        let error = SimpleError::new().at_owned("let foo = 1").error_detail(explanation);
+        assert_eq!(error.as_display_struct(false).as_display_string(), "At: let foo = 1\nError: Unused variable foo");
+        let colored = error.as_display_struct(crate::ColorChoice::Always);
+        assert_eq!(crate::formatting::strip_ansi(colored.at().unwrap()), "let foo = 1");
+        #[cfg(all(feature = "colorization-anstyle", not(feature = "colorization")))]
+        assert_eq!(colored.at(), Some("let \u{1b}[1m\u{1b}[31mfoo\u{1b}[0m = 1"));
+    }
 }
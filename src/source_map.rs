@@ -0,0 +1,127 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::color_choice::ColorChoice;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+use crate::span::{line_and_column, SourceId, Span};
+
+/// Registry owning the named sources of a multi-file project, like the files of a workspace,
+/// handing out a [SourceId] for every one of them so errors can point into them through
+/// [SimpleError::in_source] and spans (See [SimpleError::at_span]) without borrowing them.
+///
+/// When displaying errors through [SourceMap::as_display_struct], every error in a registered
+/// source gets its file name, its line and column, and the text of its span from this registry,
+/// this way, a root error can aggregate causes happening across many files.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    sources: Vec<(String, String)>,
+}
+
+impl SourceMap {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the source *text* under the name *name*, like a file path, returning the id to
+    /// give to errors happening in it through [SimpleError::in_source].
+    pub fn add<Name: Into<String>, Text: Into<String>>(&mut self, name: Name, text: Text) -> SourceId {
+        self.sources.push((name.into(), text.into()));
+        SourceId(self.sources.len() as u64 - 1)
+    }
+
+    /// Returns how many sources are registered.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Tells if no source is registered.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Returns the name of the source *source_id*, it will be None if it isn't registered.
+    pub fn name(&self, source_id: SourceId) -> Option<&str> {
+        self.entry(source_id).map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the text of the source *source_id*, it will be None if it isn't registered.
+    pub fn source(&self, source_id: SourceId) -> Option<&str> {
+        self.entry(source_id).map(|(_, text)| text.as_str())
+    }
+
+    /// Returns the span *substring* covers inside the source *source_id*, it will be None if it
+    /// isn't registered or if *substring* isn't a reference taken from said source.
+    pub fn span_of(&self, source_id: SourceId, substring: &str) -> Option<Span> {
+        Span::of(self.source(source_id)?, substring)
+    }
+
+    /// Returns the part of the source *source_id* covered by *span*, see [Span::slice].
+    pub fn snippet(&self, source_id: SourceId, span: Span) -> Option<&str> {
+        span.slice(self.source(source_id)?)
+    }
+
+    /// Returns the line and column (Both starting from 1) of the char starting at byte *offset* of
+    /// the source *source_id*, where columns are counted in chars.
+    pub fn line_and_column(&self, source_id: SourceId, offset: usize) -> Option<(usize, usize)> {
+        self.source(source_id).map(|source| line_and_column(source, offset))
+    }
+
+    /// Gives a copy of *error* where it and every one of its causes happening in a registered
+    /// source (See [SimpleError::in_source]) is given the text of said source, unless it was
+    /// already given one through [SimpleError::with_source], and its name as file name, unless it
+    /// was already given one through [SimpleError::in_file].
+    ///
+    /// Causes not happening in a registered source keep using the source of the error they caused.
+    pub fn resolve<'map>(&'map self, error: &SimpleError<'map>) -> SimpleError<'map> {
+        error.map_tree(|mut error| {
+            if let Some((name, text)) = error.current_source_id().and_then(|source_id| self.entry(source_id)) {
+                if error.current_source().is_none() {
+                    error = error.with_source(text);
+                }
+                if error.current_file().is_none() {
+                    error = error.in_file(name.as_str());
+                }
+            }
+            error
+        })
+    }
+
+    /// Turns *error* into a [SimpleErrorDisplayInfo] after resolving its sources and file names
+    /// through [SourceMap::resolve], see [SimpleError::as_display_struct].
+    pub fn as_display_struct<'map, Choice: Into<ColorChoice>>(&'map self, error: &SimpleError<'map>, colorize: Choice) -> SimpleErrorDisplayInfo {
+        self.resolve(error).as_display_struct(colorize)
+    }
+
+    fn entry(&self, source_id: SourceId) -> Option<&(String, String)> {
+        usize::try_from(source_id.0).ok().and_then(|index| self.sources.get(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn spans_are_resolved_against_the_source_of_each_error() {
+        let mut map = SourceMap::new();
+        let main = map.add("main.dsl", "let x = 1;\nlet y = ;");
+        let lib = map.add("lib/util.dsl", "fn f() {}\nfn g( {}");
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.name(lib), Some("lib/util.dsl"));
+        assert_eq!(map.span_of(main, &map.source(main).unwrap()[4..5]), Some(Span::new(4, 5)));
+        assert_eq!(map.span_of(main, "x"), None);
+        assert_eq!(map.snippet(main, Span::new(4, 5)), Some("x"));
+        assert_eq!(map.line_and_column(main, 15), Some((2, 5)));
+
+        let mut error = SimpleError::new().error_detail("Workspace failed to load".to_string());
+        error.add_cause(SimpleError::new().error_detail("Missing value".to_string()).at_span(19..20).in_source(main));
+        error.add_cause(SimpleError::new().error_detail("Unclosed parenthesis".to_string()).at_span(14..15).in_source(lib));
+        let rendered = map.as_display_struct(&error, false).as_display_string();
+        assert!(rendered.contains("In file main.dsl, on line 2 and column 9"));
+        assert!(rendered.contains("In file lib/util.dsl, on line 2 and column 5"));
+    }
+}
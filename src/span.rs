@@ -0,0 +1,174 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Location of a part of an input given by its byte offsets, where *start* is inclusive and *end*
+/// is exclusive, this is an alternative to referencing the part of the input as a &str, useful when
+/// errors are built from offsets produced by lexers not retaining references to the input.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Span {
+    /// Offset of the first byte of this span.
+    pub start: usize,
+    /// Offset of the byte right after the last byte of this span.
+    pub end: usize,
+}
+
+impl Span {
+    /// Creates a new span going from *start* (Inclusive) up to *end* (Exclusive).
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns how many bytes this span covers.
+    pub const fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Tells if this span doesn't cover any byte.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the part of *input* this span covers, it will be None if this span goes out of the
+    /// bounds of *input* or if it doesn't start or end on a char boundary.
+    pub fn slice<'input>(&self, input: &'input str) -> Option<&'input str> {
+        input.get(self.start..self.end)
+    }
+
+    /// Returns the span *substring* covers inside *input*, it will be None if *substring* isn't a
+    /// reference taken from *input*.
+    pub fn of(input: &str, substring: &str) -> Option<Self> {
+        let input_start = input.as_ptr() as usize;
+        let substring_start = substring.as_ptr() as usize;
+        let start = substring_start.checked_sub(input_start)?;
+        let end = start + substring.len();
+        if end > input.len() {
+            return None;
+        }
+        Some(Self { start, end })
+    }
+}
+
+impl From<Range<usize>> for Span {
+    fn from(value: Range<usize>) -> Self {
+        Self { start: value.start, end: value.end }
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(value: Span) -> Self {
+        value.start..value.end
+    }
+}
+
+/// Identifies one of the inputs errors happen in, like a file of a project, chosen by the
+/// application, see [crate::SimpleError::in_source].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(pub u64);
+
+/// Location of a part of an input, given either as a &str referencing the input or as a [Span] of
+/// byte offsets into it, used by [crate::SimpleErrorExplanation::label].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location<'input> {
+    /// Substring referencing the input.
+    Str(&'input str),
+    /// Byte offsets into the input.
+    Span(Span),
+}
+
+impl<'input> Location<'input> {
+    /// Returns the text this location covers, for spans, the input they belong to is required.
+    pub fn text(&self, input: Option<&'input str>) -> Option<&'input str> {
+        match self {
+            Location::Str(string) => Some(string),
+            Location::Span(span) => input.and_then(|input| span.slice(input)),
+        }
+    }
+
+    /// Returns the span this location covers inside *input*, it will be None for substrings that
+    /// aren't a reference taken from *input*.
+    pub fn span(&self, input: &str) -> Option<Span> {
+        match self {
+            Location::Str(string) => Span::of(input, string),
+            Location::Span(span) => Some(*span),
+        }
+    }
+}
+
+impl<'input> From<&'input str> for Location<'input> {
+    fn from(value: &'input str) -> Self {
+        Location::Str(value)
+    }
+}
+
+impl From<Span> for Location<'_> {
+    fn from(value: Span) -> Self {
+        Location::Span(value)
+    }
+}
+
+impl From<Range<usize>> for Location<'_> {
+    fn from(value: Range<usize>) -> Self {
+        Location::Span(value.into())
+    }
+}
+
+/// Returns the line and column (Both starting from 1) of the char starting at byte *offset* of
+/// *input*, where columns are counted in chars.
+pub(crate) fn line_and_column(input: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for (char_offset, char) in input.char_indices() {
+        if char_offset >= offset { break; }
+        if char == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// Returns the offset where the last char ending before byte *end* of *input* starts.
+pub(crate) fn last_char_offset(input: &str, end: usize) -> usize {
+    input.get(..end.min(input.len()))
+        .and_then(|before_end| before_end.char_indices().next_back())
+        .map(|(offset, _)| offset)
+        .unwrap_or(0)
+}
+
+/// Returns the lines of *input* from *first_line* up to *last_line* (Both starting from 1), along
+/// with up to *context* lines before and after them, each one along with its line number.
+pub(crate) fn lines_around(input: &str, first_line: usize, last_line: usize, context: usize) -> Vec<(usize, String)> {
+    let from = first_line.saturating_sub(context).max(1);
+    let to = last_line.max(first_line).saturating_add(context);
+    input.split('\n').enumerate()
+        .map(|(line_index, line)| (line_index + 1, line))
+        .skip(from - 1)
+        .take_while(|(line, _)| *line <= to)
+        .map(|(line, text)| (line, text.strip_suffix('\r').unwrap_or(text).to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::SimpleError;
+
+    #[test]
+    fn spans_locate_substrings_by_byte_offsets() {
+        let input = "let a = b";
+        assert_eq!(Span::of(input, &input[4..9]), Some(Span::new(4, 9)));
+        assert_eq!(Span::of(input, "a = b"), None);
+        assert_eq!(Span::new(4, 9).slice(input), Some("a = b"));
+        assert_eq!(Span::new(4, 12).slice(input), None);
+        let error = SimpleError::new().error_detail("Variable b doesn't exist".to_string()).at_span(4..9);
+        assert_eq!(error.current_at_span(), Some(Span::new(4, 9)));
+        assert_eq!(error.as_display_struct(false).as_display_string(), "At: bytes 4 up to 9\nError: Variable b doesn't exist");
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::formatting::{join_strings, pluralize};
+use crate::severity::Severity;
+use crate::simple_error::SimpleError;
+
+/// Totals of an error tree given by [SimpleError::stats], so build tools can print lines like '3
+/// errors and 5 warnings emitted' without traversing the tree by hand.
+///
+/// When displayed, it counts its errors by severity, from the most serious one, like '3 errors and
+/// 5 warnings'.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ErrorStats {
+    /// How many errors the tree holds, counting the root error and all of its causes.
+    pub nodes: usize,
+    /// How many errors of the tree don't have causes, see [SimpleError::leaf_errors].
+    pub leaves: usize,
+    /// How many levels of causes there are below the root error, 0 when it has no causes.
+    pub max_depth: usize,
+    /// How many errors there are of every severity, see [SimpleError::current_severity].
+    pub by_severity: BTreeMap<Severity, usize>,
+    /// How many errors there are with every code, see [SimpleError::current_code].
+    pub by_code: BTreeMap<String, usize>,
+    /// How many errors don't have a code.
+    pub without_code: usize,
+}
+
+impl ErrorStats {
+    /// Returns how many errors of the tree have the severity *severity*.
+    pub fn count_of(&self, severity: Severity) -> usize {
+        self.by_severity.get(&severity).copied().unwrap_or_default()
+    }
+
+    /// Adds *error* and its causes to these totals, where *depth* is how deep *error* is.
+    fn add(&mut self, error: &SimpleError, depth: usize) {
+        self.nodes += 1;
+        self.max_depth = self.max_depth.max(depth);
+        *self.by_severity.entry(error.current_severity()).or_default() += 1;
+        match error.current_code() {
+            Some(code) => *self.by_code.entry(code).or_default() += 1,
+            None => self.without_code += 1,
+        }
+        if error.current_causes().is_empty() {
+            self.leaves += 1;
+        }
+        error.current_causes().iter().for_each(|cause| self.add(cause, depth + 1));
+    }
+}
+
+/// Counts the errors by severity, from the most serious one, like '3 errors and 5 warnings', or
+/// '0 errors' when there aren't any.
+impl Display for ErrorStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&severity_counts(|severity| self.count_of(severity)).unwrap_or_else(|| String::from("0 errors")))
+    }
+}
+
+impl SimpleError<'_> {
+    /// Gives the totals of this error tree, such as how many errors it holds of every severity and
+    /// with every code, see [ErrorStats].
+    pub fn stats(&self) -> ErrorStats {
+        let mut stats = ErrorStats::default();
+        stats.add(self, 0);
+        stats
+    }
+}
+
+/// Joins the counts given by *count_of* for every severity, from the most serious one, like '2
+/// errors, 1 warning and 3 hints', leaving out severities without errors, or [None] when there
+/// aren't any errors.
+pub(crate) fn severity_counts<CountOf: Fn(Severity) -> usize>(count_of: CountOf) -> Option<String> {
+    let counts = [(Severity::Error, "error"), (Severity::Warning, "warning"), (Severity::Info, "info message"), (Severity::Hint, "hint")]
+        .into_iter()
+        .map(|(severity, noun)| pluralize(count_of(severity), noun, ""))
+        .filter(|count| !count.is_empty())
+        .collect::<Vec<_>>();
+    match counts.split_last() {
+        Some((last, [])) => Some(last.clone()),
+        Some((last, rest)) => Some(format!("{} and {last}", join_strings(", ", rest.iter()))),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::SimpleErrorExplanation;
+
+    #[test]
+    fn trees_are_totaled_by_severity_and_code() {
+        let explained = |severity: Severity, code: &'static str| SimpleError::new().error_detail(SimpleErrorExplanation::new().explanation("Suspicious code").severity(severity).code(code));
+        let error = SimpleError::new()
+            .with_cause(explained(Severity::Warning, "W001").with_cause(explained(Severity::Warning, "W001")))
+            .with_cause(explained(Severity::Hint, "H001"));
+        let stats = error.stats();
+        assert_eq!((stats.nodes, stats.leaves, stats.max_depth, stats.without_code), (4, 2, 2, 1));
+        assert_eq!(stats.count_of(Severity::Warning), 2);
+        assert_eq!(stats.by_code.get("W001"), Some(&2));
+        assert_eq!(stats.to_string(), "1 error, 2 warnings and 1 hint");
+        assert_eq!(ErrorStats::default().to_string(), "0 errors");
+    }
+}
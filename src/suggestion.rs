@@ -0,0 +1,117 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::simple_error::SimpleError;
+use crate::simple_error_detail::SimpleErrorDetail;
+use crate::simple_error_explanation::SimpleErrorExplanation;
+use crate::span::Span;
+
+/// How confident a suggestion is about fixing the error, mirroring rustc's Applicability, so tools
+/// fixing errors automatically know which suggestions are safe to apply without review.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Applicability {
+    /// The suggestion is surely what the user wants, so it can be applied automatically.
+    MachineApplicable,
+    /// The suggestion might be what the user wants, but it might also be wrong, so it should be
+    /// reviewed.
+    MaybeIncorrect,
+    /// The suggestion holds placeholders like `/* value */` the user must fill in.
+    HasPlaceholders,
+}
+
+/// Error given by [SimpleError::apply_suggestions] when two suggestions replace overlapping parts
+/// of the source, meaning they can't both be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuggestionConflict {
+    /// Part of the source replaced by the suggestion starting first.
+    pub first: Span,
+    /// Part of the source replaced by the suggestion overlapping the first one.
+    pub second: Span,
+}
+
+impl SimpleErrorDetail for SuggestionConflict {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        SimpleErrorExplanation::new()
+            .explanation(format!("Suggestion replacing bytes {} up to {} overlaps with the one replacing bytes {} up to {}.",
+                                 self.second.start, self.second.end, self.first.start, self.first.end))
+            .solution("Apply the suggestions one at a time, or make them replace different parts of the source.")
+    }
+}
+
+/// Collects the suggestions of *error* and its causes accepted by *is_applied* as spans of
+/// *source* along with their replacements, suggestions that can't be located on *source* are
+/// skipped.
+fn collect_suggestions(error: &SimpleError, source: &str, is_applied: fn(Option<Applicability>) -> bool, suggestions: &mut Vec<(Span, String)>) {
+    if let Some(error_detail) = error.current_error_detail() {
+        let explanation = error_detail.explain_error();
+        suggestions.extend(explanation.suggestions.into_iter()
+            .filter(|(_, _, applicability)| is_applied(*applicability))
+            .filter_map(|(location, replacement, _)| location.span(source)
+                .filter(|span| span.slice(source).is_some())
+                .map(|span| (span, replacement))));
+    }
+    error.current_causes().iter().for_each(|cause| collect_suggestions(cause, source, is_applied, suggestions));
+}
+
+/// Applies the suggestions of *error* and its causes accepted by *is_applied* on *source*, see
+/// [SimpleError::apply_suggestions].
+pub(crate) fn apply_suggestions(error: &SimpleError, source: &str, is_applied: fn(Option<Applicability>) -> bool) -> Result<String, SuggestionConflict> {
+    let mut suggestions = Vec::new();
+    collect_suggestions(error, source, is_applied, &mut suggestions);
+    suggestions.sort();
+    suggestions.dedup();
+    for pair in suggestions.windows(2) {
+        let ((first, _), (second, _)) = (&pair[0], &pair[1]);
+        let overlaps = second.start < first.end || (first.is_empty() && second.is_empty() && first.start == second.start);
+        if overlaps {
+            return Err(SuggestionConflict { first: *first, second: *second });
+        }
+    }
+    let mut res = String::with_capacity(source.len());
+    let mut copied_up_to = 0;
+    for (span, replacement) in suggestions {
+        res.push_str(&source[copied_up_to..span.start]);
+        res.push_str(&replacement);
+        copied_up_to = span.end;
+    }
+    res.push_str(&source[copied_up_to..]);
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn suggestions_of_every_cause_are_applied_together_unless_overlapping() {
+        let source = "let x = 1;\nlet x = 2;";
+        let error = SimpleError::new()
+            .error_detail(SimpleErrorExplanation::new().explanation("x is defined twice").suggestion(&source[15..16], "y").suggestion(9..9, " // first"))
+            .with_cause(SimpleErrorExplanation::new().suggestion(&source[15..16], "y").suggestion(0..4, ""));
+        assert_eq!(error.apply_suggestions(source), Ok("x = 1 // first;\nlet y = 2;".to_string()));
+        let rendered = error.as_display_struct(false).as_display_string();
+        assert!(rendered.contains("Suggestion: 'x': Replace it with 'y'\nSuggestion: bytes 9 up to 9: Replace it with ' // first'"), "{rendered}");
+        let overlapping = SimpleError::new().error_detail(SimpleErrorExplanation::new().suggestion(0..5, "a").suggestion(3..6, "b"));
+        assert_eq!(overlapping.apply_suggestions(source), Err(SuggestionConflict { first: Span::new(0, 5), second: Span::new(3, 6) }));
+    }
+
+    #[test]
+    fn only_machine_applicable_suggestions_are_applied_automatically() {
+        let source = "let x = 1;";
+        let explanation = SimpleErrorExplanation::new().explanation("Invalid declaration")
+            .suggestion_with_applicability(4..5, "y", Applicability::MachineApplicable)
+            .suggestion_with_applicability(8..9, "2", Applicability::MaybeIncorrect)
+            .suggestion(0..3, "var");
+        let error = SimpleError::new().with_source(source).at_span(4..5).error_detail(explanation);
+        assert_eq!(error.apply_machine_applicable_suggestions(source), Ok("let y = 1;".to_string()));
+        assert_eq!(error.apply_suggestions(source), Ok("var y = 2;".to_string()));
+        let rendered = error.as_display_struct(false).as_display_string();
+        assert!(rendered.contains("Suggestion: 'x' on line 1 and column 5: Replace it with 'y'"), "{rendered}");
+        assert!(rendered.contains("Possible fix: '1' on line 1 and column 9: Replace it with '2'"), "{rendered}");
+        assert!(rendered.contains("Suggestion: 'let' on line 1 and column 1: Replace it with 'var'"), "{rendered}");
+    }
+}
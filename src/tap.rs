@@ -0,0 +1,157 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::formatting::{join_strings, strip_ansi};
+use crate::report::ReportDisplayInfo;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// Gives this error as a TAP 14 (Test Anything Protocol) document, where every leaf error of
+    /// the error tree is a failing test point, like `not ok 1 - Variable 'x' doesn't exist`,
+    /// followed by a YAML diagnostic block holding its severity, code, position, solution, notes,
+    /// helps and so on, along with the reasons of the errors it caused as `context`, starting from
+    /// the closest one.
+    ///
+    /// This lets validators built on top of this crate plug into harnesses already consuming TAP,
+    /// colors are removed and texts are written as quoted YAML strings.
+    pub fn as_tap_string(&self) -> String {
+        tap_document(core::iter::once(self))
+    }
+}
+
+impl ReportDisplayInfo {
+    /// Gives the leaf errors of every error of the report as a single TAP 14 document, numbering
+    /// their test points in order, see [SimpleErrorDisplayInfo::as_tap_string].
+    pub fn as_tap_string(&self) -> String {
+        tap_document(self.errors.iter())
+    }
+}
+
+/// Leaf error along with the errors it caused, from the outermost to the innermost.
+type TestPoint<'display> = (&'display SimpleErrorDisplayInfo, Vec<&'display SimpleErrorDisplayInfo>);
+
+fn tap_document<'display, Errors: Iterator<Item=&'display SimpleErrorDisplayInfo>>(errors: Errors) -> String {
+    let mut test_points = Vec::new();
+    errors.for_each(|error| push_test_points(error, &mut Vec::new(), &mut test_points));
+    let mut lines = vec![String::from("TAP version 14"), format!("1..{}", test_points.len())];
+    lines.extend(test_points.iter().enumerate().map(|(index, (leaf, ancestors))| test_point(index + 1, leaf, ancestors)));
+    join_strings("\n", lines.iter())
+}
+
+fn push_test_points<'display>(error: &'display SimpleErrorDisplayInfo, ancestors: &mut Vec<&'display SimpleErrorDisplayInfo>, test_points: &mut Vec<TestPoint<'display>>) {
+    if error.explained_causes.is_empty() {
+        test_points.push((error, ancestors.clone()));
+        return;
+    }
+    ancestors.push(error);
+    error.explained_causes.iter().for_each(|cause| push_test_points(cause, ancestors, test_points));
+    ancestors.pop();
+}
+
+/// Gives the `not ok` line of *leaf* followed by its YAML diagnostic block.
+fn test_point(number: usize, leaf: &SimpleErrorDisplayInfo, ancestors: &[&SimpleErrorDisplayInfo]) -> String {
+    let message = leaf.reason.as_deref().map(single_line).unwrap_or_else(|| String::from("Unexplained error"));
+    let description = message.replace('\\', "\\\\").replace('#', "\\#");
+    let mut res = format!("not ok {number} - {description}\n  ---");
+    push_yaml_field(&mut res, "message", &yaml_string(&message));
+    push_yaml_field(&mut res, "severity", &leaf.severity.name().to_lowercase());
+    let fields = [
+        ("code", leaf.code.as_deref()),
+        ("at", leaf.at.as_deref()),
+    ];
+    fields.into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .for_each(|(key, value)| push_yaml_field(&mut res, key, &yaml_string(value)));
+    if let Some((line, column)) = leaf.on_line_and_column {
+        push_yaml_field(&mut res, "line", &format!("{line}"));
+        push_yaml_field(&mut res, "column", &format!("{column}"));
+    }
+    if let Some((line, column)) = leaf.up_to_line_an_column {
+        push_yaml_field(&mut res, "end_line", &format!("{line}"));
+        push_yaml_field(&mut res, "end_column", &format!("{column}"));
+    }
+    if let Some(solution) = &leaf.solution {
+        push_yaml_field(&mut res, "solution", &yaml_string(solution));
+    }
+    let labels = leaf.labels.iter().map(|label| label.as_display_string()).collect::<Vec<_>>();
+    let suggestions = leaf.suggestions.iter().map(|suggestion| suggestion.as_display_string()).collect::<Vec<_>>();
+    let context = ancestors.iter().rev()
+        .map(|ancestor| ancestor.reason.as_deref().map(single_line).unwrap_or_else(|| String::from("Unexplained error")))
+        .collect::<Vec<_>>();
+    let lists = [
+        ("solution_steps", &leaf.solution_steps),
+        ("labels", &labels),
+        ("suggestions", &suggestions),
+        ("notes", &leaf.notes),
+        ("helps", &leaf.helps),
+    ];
+    lists.into_iter()
+        .filter(|(_, values)| !values.is_empty())
+        .for_each(|(key, values)| push_yaml_list(&mut res, key, values));
+    if let Some(doc_url) = &leaf.doc_url {
+        push_yaml_field(&mut res, "doc_url", &yaml_string(doc_url));
+    }
+    if leaf.unexplained_causes > 0 {
+        push_yaml_field(&mut res, "unexplained_causes", &format!("{}", leaf.unexplained_causes));
+    }
+    if !context.is_empty() {
+        push_yaml_list(&mut res, "context", &context);
+    }
+    res.push_str("\n  ...");
+    res
+}
+
+fn push_yaml_field(res: &mut String, key: &str, value: &str) {
+    res.push_str(&format!("\n  {key}: {value}"));
+}
+
+fn push_yaml_list(res: &mut String, key: &str, values: &[String]) {
+    res.push_str(&format!("\n  {key}:"));
+    values.iter().for_each(|value| res.push_str(&format!("\n    - {}", yaml_string(value))));
+}
+
+/// Removes the colors of *text* and joins its lines into a single one.
+fn single_line(text: &str) -> String {
+    join_strings(" ", strip_ansi(text).lines().map(str::trim).filter(|line| !line.is_empty()))
+}
+
+/// Gives *text* without colors as a double-quoted YAML string, escaping it as needed.
+fn yaml_string(text: &str) -> String {
+    let mut res = String::from("\"");
+    for char in strip_ansi(text).chars() {
+        match char {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\t' => res.push_str("\\t"),
+            '\r' => res.push_str("\\r"),
+            char if char.is_control() => res.push_str(&format!("\\u{:04x}", char as u32)),
+            char => res.push(char),
+        }
+    }
+    res.push('"');
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SimpleError, SimpleErrorExplanation};
+
+    #[test]
+    fn leaf_errors_become_failing_test_points() {
+        let input = "let x = 1;";
+        let explained = |explanation| SimpleError::from(SimpleErrorExplanation::new().explanation(explanation).code("E1").note("Quoted \"x\"\nin two lines"));
+        let error = explained("Couldn't compile # 1").with_source(input)
+            .with_cause(explained("Invalid name").at(&input[4..5]))
+            .with_cause(explained("Invalid declaration").with_cause(explained("Missing type")));
+        assert_eq!(error.as_display_struct(false).as_tap_string(), concat!(
+            "TAP version 14\n1..2\n",
+            "not ok 1 - Invalid name\n  ---\n  message: \"Invalid name\"\n  severity: error\n  code: \"E1\"\n  at: \"x\"\n",
+            "  line: 1\n  column: 5\n  end_line: 1\n  end_column: 5\n  notes:\n    - \"Quoted \\\"x\\\"\\nin two lines\"\n",
+            "  context:\n    - \"Couldn't compile # 1\"\n  ...\n",
+            "not ok 2 - Missing type\n  ---\n  message: \"Missing type\"\n  severity: error\n  code: \"E1\"\n",
+            "  notes:\n    - \"Quoted \\\"x\\\"\\nin two lines\"\n  context:\n    - \"Invalid declaration\"\n    - \"Couldn't compile # 1\"\n  ..."));
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+
+#[cfg(all(feature = "colorization-anstyle", not(feature = "colorization")))]
+use anstyle::{AnsiColor, Effects, Style};
+#[cfg(feature = "colorization")]
+use string_colorization::{foreground, style};
+
+use crate::markers::Marker;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static THEME: RefCell<Option<Theme>> = const { RefCell::new(None) };
+}
+
+/// This is only implemented when using the colorization or colorization-anstyle features, the
+/// first one being enabled by default.
+///
+/// Styles given to each semantic role of the input, so implementations of
+/// [crate::SimpleErrorDetail::explain_error] can colorize the input through the functions at
+/// [crate::markers], describing what a part of the input means rather than which color it takes,
+/// while the application displaying the errors picks the actual styles through [with_theme].
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Style for the parts of the input causing the error, red and bold by default.
+    pub error_span: Marker,
+    /// Style for the input surrounding the error, blue, italic and dimmed by default.
+    pub context: Marker,
+    /// Style for the parts of the input related to how the error might be solved, green and bold by
+    /// default.
+    pub hint: Marker,
+}
+
+#[cfg(feature = "colorization")]
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            error_span: style::Clear + foreground::Red + style::Bold,
+            context: foreground::Blue + style::Italic + style::Dimmed,
+            hint: style::Clear + foreground::Green + style::Bold,
+        }
+    }
+}
+
+#[cfg(all(feature = "colorization-anstyle", not(feature = "colorization")))]
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            error_span: Style::new().fg_color(Some(AnsiColor::Red.into())).effects(Effects::BOLD),
+            context: Style::new().fg_color(Some(AnsiColor::Blue.into())).effects(Effects::ITALIC | Effects::DIMMED),
+            hint: Style::new().fg_color(Some(AnsiColor::Green.into())).effects(Effects::BOLD),
+        }
+    }
+}
+
+impl Theme {
+    /// Creates the default theme.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the style for the parts of the input causing the error.
+    pub fn error_span<Color: Into<Marker>>(mut self, error_span: Color) -> Self {
+        self.error_span = error_span.into();
+        self
+    }
+
+    /// Sets the style for the input surrounding the error.
+    pub fn context<Color: Into<Marker>>(mut self, context: Color) -> Self {
+        self.context = context.into();
+        self
+    }
+
+    /// Sets the style for the parts of the input related to how the error might be solved.
+    pub fn hint<Color: Into<Marker>>(mut self, hint: Color) -> Self {
+        self.hint = hint.into();
+        self
+    }
+}
+
+/// This is only implemented when using the std feature along with the colorization or
+/// colorization-anstyle features, std and colorization being enabled by default.
+///
+/// Runs *scope* while making the functions at [crate::markers] resolve to the styles of *theme* on
+/// the current thread, since errors are explained when they get displayed, this affects every
+/// error rendered during *scope*. The previous theme is restored once *scope* finishes, even if it
+/// panics.
+#[cfg(feature = "std")]
+pub fn with_theme<Res, Scope: FnOnce() -> Res>(theme: Theme, scope: Scope) -> Res {
+    struct RestoreOnDrop(Option<Theme>);
+    impl Drop for RestoreOnDrop {
+        fn drop(&mut self) {
+            let previous_theme = self.0.take();
+            THEME.with(|current_theme| *current_theme.borrow_mut() = previous_theme);
+        }
+    }
+    let _restore = RestoreOnDrop(THEME.with(|current_theme| current_theme.replace(Some(theme))));
+    scope()
+}
+
+/// Returns the style given by the theme set through [with_theme] for the current thread, or by the
+/// default theme if there isn't any.
+// Markers are Copy under the anstyle backend but only Clone under string_colorization.
+#[allow(clippy::clone_on_copy)]
+pub(crate) fn current_style<Role: Fn(&Theme) -> &Marker>(role: Role) -> Marker {
+    #[cfg(feature = "std")]
+    {
+        THEME.with(|theme| theme.borrow().as_ref().map(|theme| role(theme).clone()))
+            .unwrap_or_else(|| role(&Theme::default()).clone())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        role(&Theme::default()).clone()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::markers;
+
+    #[test]
+    fn themes_give_the_marker_styles_only_during_their_scope() {
+        let default = Theme::default();
+        let theme = Theme::new().error_span(markers::hint());
+        with_theme(theme, || {
+            assert_eq!(markers::error_span(), default.hint);
+            assert_eq!(markers::hint(), default.hint);
+            assert_eq!(markers::context(), default.context);
+        });
+        assert_eq!(markers::error_span(), default.error_span);
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Moment an error happened at, given as milliseconds since the Unix epoch, see
+/// [crate::SimpleError::timestamp].
+///
+/// It is created from a `u64` holding said milliseconds, or, when using the std feature, from a
+/// [SystemTime], so no date library is needed to record when errors happen.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Gives the current moment, as given by [SystemTime::now].
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        Self::from(SystemTime::now())
+    }
+
+    /// Gives the milliseconds since the Unix epoch of this moment.
+    pub const fn as_millis(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Creates the moment happening *value* milliseconds after the Unix epoch.
+impl From<u64> for Timestamp {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Creates the moment given by *value*, where moments before the Unix epoch are given as the epoch
+/// itself.
+#[cfg(feature = "std")]
+impl From<SystemTime> for Timestamp {
+    fn from(value: SystemTime) -> Self {
+        let millis = value.duration_since(UNIX_EPOCH).map(|duration| duration.as_millis()).unwrap_or_default();
+        Self(u64::try_from(millis).unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::SimpleError;
+
+    #[test]
+    fn timestamps_are_kept_on_displays_without_being_shown() {
+        let error = SimpleError::new().error_detail("Missing value".to_string()).timestamp(1500u64);
+        assert_eq!(error.current_timestamp(), Some(Timestamp(1500)));
+        let display = error.as_display_struct(false);
+        assert_eq!(display.timestamp().map(|timestamp| timestamp.as_millis()), Some(1500));
+        assert_eq!(display.as_display_string(), "Error: Missing value");
+        assert_eq!(SimpleError::from(display.clone()).as_display_struct(false), display);
+        #[cfg(feature = "serde")]
+        assert!(serde_json::to_string(&display).unwrap().contains("\"timestamp\":1500"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn system_times_are_given_as_milliseconds_since_the_epoch() {
+        use std::time::Duration;
+
+        assert_eq!(Timestamp::from(UNIX_EPOCH + Duration::from_millis(1500)), Timestamp(1500));
+        assert_eq!(Timestamp::from(UNIX_EPOCH - Duration::from_secs(1)), Timestamp(0));
+        assert!(Timestamp::now() > Timestamp::from(UNIX_EPOCH));
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::simple_error::SimpleError;
+
+/// Iterator over an error and all of its causes in depth-first order, where every cause comes right
+/// after the error it caused and before its next sibling, given by [SimpleError::iter].
+///
+/// Every error is given along with its depth, being 0 for the error the iterator started at, 1 for
+/// its causes, 2 for the causes of these, and so on.
+#[derive(Debug, Clone)]
+pub struct DepthFirstIter<'error, 'input> {
+    pending: Vec<(usize, &'error SimpleError<'input>)>,
+}
+
+impl<'error, 'input> Iterator for DepthFirstIter<'error, 'input> {
+    type Item = (usize, &'error SimpleError<'input>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, error) = self.pending.pop()?;
+        self.pending.extend(error.current_causes().iter().rev().map(|cause| (depth + 1, cause)));
+        Some((depth, error))
+    }
+}
+
+/// Iterator over an error and all of its causes in breadth-first order, where every error of a
+/// depth comes before any error of the next depth, given by [SimpleError::iter_breadth_first].
+///
+/// Every error is given along with its depth, see [DepthFirstIter].
+#[derive(Debug, Clone)]
+pub struct BreadthFirstIter<'error, 'input> {
+    pending: VecDeque<(usize, &'error SimpleError<'input>)>,
+}
+
+impl<'error, 'input> Iterator for BreadthFirstIter<'error, 'input> {
+    type Item = (usize, &'error SimpleError<'input>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, error) = self.pending.pop_front()?;
+        self.pending.extend(error.current_causes().iter().map(|cause| (depth + 1, cause)));
+        Some((depth, error))
+    }
+}
+
+/// Iterator over the leaf errors of an error tree, where every leaf is given along with its
+/// ancestors, ordered from the leaf up to the error the iterator started at, given by
+/// [SimpleError::inverted_chains].
+///
+/// Chains are found lazily as the iterator advances, holding references to the errors of the tree
+/// rather than copies of them.
+#[derive(Debug, Clone)]
+pub struct InvertedChains<'error, 'input> {
+    pending: Vec<(usize, &'error SimpleError<'input>)>,
+    path: Vec<&'error SimpleError<'input>>,
+}
+
+impl<'error, 'input> Iterator for InvertedChains<'error, 'input> {
+    type Item = Vec<&'error SimpleError<'input>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (depth, error) = self.pending.pop()?;
+            self.path.truncate(depth);
+            self.path.push(error);
+            if error.current_causes().is_empty() {
+                return Some(self.path.iter().rev().copied().collect());
+            }
+            self.pending.extend(error.current_causes().iter().rev().map(|cause| (depth + 1, cause)));
+        }
+    }
+}
+
+impl<'input> SimpleError<'input> {
+    /// Iterates over this error and all of its causes in depth-first order along with their depth,
+    /// this error being at depth 0, so errors can be counted, filtered or exported without writing
+    /// recursive walkers, see [DepthFirstIter].
+    pub fn iter(&self) -> DepthFirstIter<'_, 'input> {
+        DepthFirstIter { pending: vec![(0, self)] }
+    }
+
+    /// Iterates over this error and all of its causes in breadth-first order along with their
+    /// depth, this error being at depth 0, see [BreadthFirstIter].
+    pub fn iter_breadth_first(&self) -> BreadthFirstIter<'_, 'input> {
+        BreadthFirstIter { pending: VecDeque::from([(0, self)]) }
+    }
+
+    /// Iterates over the chains given by [SimpleError::inverted_error_tree] without copying any
+    /// error, where every chain starts at a leaf error followed by its ancestors up to this error,
+    /// for example, if we had four errors A B C and D, where A had B and C as causes, and C had D
+    /// as cause, it would give B and A, followed by D, C and A, see [InvertedChains].
+    pub fn inverted_chains(&self) -> InvertedChains<'_, 'input> {
+        InvertedChains { pending: vec![(0, self)], path: Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn trees_are_walked_depth_and_breadth_first_with_their_depths() {
+        let error = |explanation: &str| SimpleError::new().error_detail(explanation.to_string());
+        let tree = error("Couldn't compile").with_cause(error("Invalid function").with_cause(error("Missing name"))).with_cause(error("Missing ;"));
+        let reason = |(depth, error): (usize, &SimpleError)| (depth, error.as_display_struct(false).reason.unwrap_or_default());
+        let depth_first = tree.iter().map(reason).collect::<Vec<(usize, String)>>();
+        assert_eq!(depth_first, [(0, "Couldn't compile".into()), (1, "Invalid function".into()), (2, "Missing name".into()), (1, "Missing ;".into())]);
+        let breadth_first = tree.iter_breadth_first().map(reason).collect::<Vec<(usize, String)>>();
+        assert_eq!(breadth_first, [(0, "Couldn't compile".into()), (1, "Invalid function".into()), (1, "Missing ;".into()), (2, "Missing name".into())]);
+    }
+
+    #[test]
+    fn chains_are_inverted_from_each_leaf_up_to_the_root() {
+        let error = |explanation: &str| SimpleError::new().error_detail(explanation.to_string());
+        let tree = error("Couldn't compile").with_cause(error("Missing ;")).with_cause(error("Invalid type").with_cause(error("Unknown type Strng")));
+        let reason = |error: &SimpleError| error.as_display_struct(false).reason.unwrap_or_default();
+        let chains = tree.inverted_chains().map(|chain| chain.into_iter().map(reason).collect::<Vec<String>>()).collect::<Vec<_>>();
+        assert_eq!(chains, vec![vec!["Missing ;", "Couldn't compile"], vec!["Unknown type Strng", "Invalid type", "Couldn't compile"]]);
+        let inverted = tree.inverted_error_tree();
+        assert_eq!(inverted.len(), 2);
+        assert_eq!(inverted[0].as_display_struct(false).summary(), "Missing ; (1 cause: Couldn't compile)");
+        assert_eq!(inverted[1].as_display_struct(false).summary(), "Unknown type Strng (1 cause: Invalid type (1 cause: Couldn't compile))");
+    }
+}
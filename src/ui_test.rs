@@ -0,0 +1,393 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::color_override::with_color_override;
+use crate::formatting::join_strings;
+use crate::simple_error::SimpleError;
+
+/// Name of the environment variable that, when set to anything but `0`, makes
+/// [check_diagnostics] rewrite the expected files with the current output instead of comparing
+/// them, the `--bless` argument has the same effect on test binaries not using the default harness.
+pub const BLESS_ENV_VAR: &str = "SIMPLE_DETAILED_ERROR_BLESS";
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Reads the input file at *input_path*, runs *parse* over it and compares the rendered error
+/// against the contents of the file at *expected_path*, similarly to how compilers regression-test
+/// their diagnostics through `.stderr` files.
+///
+/// The error is rendered without colors, and when *parse* succeeds the output is empty, line
+/// endings and trailing new lines are ignored when comparing them. When blessing (See
+/// [BLESS_ENV_VAR]) the expected file is rewritten with the current output instead, creating it
+/// along with its directories if it doesn't exist, otherwise, a missing expected file is reported
+/// as [UiTestFailure::Io].
+pub fn check_diagnostics<InputPath, ExpectedPath, Parse>(input_path: InputPath, expected_path: ExpectedPath, parse: Parse) -> Result<(), UiTestFailure>
+    where InputPath: AsRef<Path>,
+          ExpectedPath: AsRef<Path>,
+          Parse: for<'input> FnOnce(&'input str) -> Result<(), SimpleError<'input>> {
+    check_diagnostics_masked(input_path, expected_path, &OutputMasker::new(), parse)
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Works as [check_diagnostics], but masking the volatile parts of the rendered error through
+/// *masker* before comparing it, and before writing it when blessing, so expected files hold
+/// placeholders like `[PATH]` rather than values changing across machines.
+pub fn check_diagnostics_masked<InputPath, ExpectedPath, Parse>(input_path: InputPath, expected_path: ExpectedPath, masker: &OutputMasker, parse: Parse) -> Result<(), UiTestFailure>
+    where InputPath: AsRef<Path>,
+          ExpectedPath: AsRef<Path>,
+          Parse: for<'input> FnOnce(&'input str) -> Result<(), SimpleError<'input>> {
+    check(input_path.as_ref(), expected_path.as_ref(), masker, is_blessing(), parse)
+}
+
+fn check<Parse>(input_path: &Path, expected_path: &Path, masker: &OutputMasker, is_blessing: bool, parse: Parse) -> Result<(), UiTestFailure>
+    where Parse: for<'input> FnOnce(&'input str) -> Result<(), SimpleError<'input>> {
+    let input = std::fs::read_to_string(input_path).map_err(io_failure(input_path))?;
+    let actual = match parse(&input) {
+        Ok(()) => String::new(),
+        Err(error) => with_color_override(false, || error.as_display_struct(false).as_display_string()),
+    };
+    let actual = masker.apply(&actual);
+    if is_blessing {
+        if let Some(directory) = expected_path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+            std::fs::create_dir_all(directory).map_err(io_failure(directory))?;
+        }
+        return std::fs::write(expected_path, format!("{actual}\n")).map_err(io_failure(expected_path));
+    }
+    let expected = std::fs::read_to_string(expected_path).map_err(io_failure(expected_path))?;
+    let (expected, actual) = (normalize(&expected), normalize(&actual));
+    if expected == actual {
+        return Ok(());
+    }
+    Err(UiTestFailure::Mismatch { expected_path: expected_path.to_path_buf(), diff: line_diff(&expected, &actual) })
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Calls [check_diagnostics], panicking with a readable diff if the output doesn't match.
+pub fn assert_diagnostics<InputPath, ExpectedPath, Parse>(input_path: InputPath, expected_path: ExpectedPath, parse: Parse)
+    where InputPath: AsRef<Path>,
+          ExpectedPath: AsRef<Path>,
+          Parse: for<'input> FnOnce(&'input str) -> Result<(), SimpleError<'input>> {
+    if let Err(failure) = check_diagnostics(input_path, expected_path, parse) {
+        panic!("{failure}");
+    }
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Compares a rendered error against the expected one after masking the volatile parts of both
+/// through *masker*, panicking with a readable diff if they don't match, this is useful for
+/// integration tests of tools whose output holds timestamps, paths or durations.
+pub fn assert_rendered_eq<Actual: AsRef<str>, Expected: AsRef<str>>(actual: Actual, expected: Expected, masker: &OutputMasker) {
+    let actual = normalize(&masker.apply(actual.as_ref()));
+    let expected = normalize(&masker.apply(expected.as_ref()));
+    if expected != actual {
+        panic!("Rendered error doesn't match the expected one (- expected, + actual):\n{}", line_diff(&expected, &actual));
+    }
+}
+
+/// This is only implemented when using the std feature, enabled by default.
+///
+/// Replaces the volatile parts of rendered errors, such as timestamps, paths or durations, with
+/// placeholders, so tests comparing them stay stable across machines and executions, see
+/// [assert_rendered_eq] and [check_diagnostics_masked].
+///
+/// Masks are applied in the order they were added.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OutputMasker {
+    masks: Vec<(Mask, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mask {
+    Literal(String),
+    Timestamps,
+    Durations,
+    Paths,
+}
+
+impl OutputMasker {
+    /// Creates a masker that doesn't mask anything.
+    pub fn new() -> Self {
+        Self { masks: Vec::new() }
+    }
+
+    /// Creates a masker replacing timestamps with `[TIMESTAMP]`, absolute paths with `[PATH]` and
+    /// durations with `[DURATION]`.
+    pub fn volatile() -> Self {
+        Self::new()
+            .mask_timestamps("[TIMESTAMP]")
+            .mask_paths("[PATH]")
+            .mask_durations("[DURATION]")
+    }
+
+    /// Replaces every appearance of *text* with *placeholder*, for example, to mask the name of a
+    /// temporary directory.
+    pub fn mask_literal<Text: Into<String>, Placeholder: Into<String>>(mut self, text: Text, placeholder: Placeholder) -> Self {
+        let text = text.into();
+        if !text.is_empty() {
+            self.masks.push((Mask::Literal(text), placeholder.into()));
+        }
+        self
+    }
+
+    /// Replaces dates like `2024-05-17`, optionally followed by a time and a time zone as in
+    /// `2024-05-17T10:03:00.123Z`, and times like `10:03:00` with *placeholder*.
+    pub fn mask_timestamps<Placeholder: Into<String>>(mut self, placeholder: Placeholder) -> Self {
+        self.masks.push((Mask::Timestamps, placeholder.into()));
+        self
+    }
+
+    /// Replaces durations like `3.2s`, `15ms` or `200µs` with *placeholder*.
+    pub fn mask_durations<Placeholder: Into<String>>(mut self, placeholder: Placeholder) -> Self {
+        self.masks.push((Mask::Durations, placeholder.into()));
+        self
+    }
+
+    /// Replaces absolute paths, like `/home/user/input.txt` or `C:\\Users\\input.txt`, and paths
+    /// relative to the current directory, like `./input.txt`, with *placeholder*, where paths end at
+    /// the first whitespace, quote, parenthesis or colon, other slashes, like those of `//`
+    /// comments, are kept as they are.
+    pub fn mask_paths<Placeholder: Into<String>>(mut self, placeholder: Placeholder) -> Self {
+        self.masks.push((Mask::Paths, placeholder.into()));
+        self
+    }
+
+    /// Returns *text* with all of its volatile parts replaced by their placeholders.
+    pub fn apply(&self, text: &str) -> String {
+        self.masks.iter().fold(text.to_string(), |text, (mask, placeholder)| match mask {
+            Mask::Literal(literal) => text.replace(literal.as_str(), placeholder),
+            Mask::Timestamps => replace_matches(&text, placeholder, match_timestamp),
+            Mask::Durations => replace_matches(&text, placeholder, match_duration),
+            Mask::Paths => replace_matches(&text, placeholder, match_path),
+        })
+    }
+}
+
+/// Replaces with *placeholder* every part of *text* matched by *matcher*, which receives the text
+/// starting at a char not preceded by an alphanumeric char, and returns the length of the match.
+fn replace_matches(text: &str, placeholder: &str, matcher: fn(&str) -> Option<usize>) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut previous_char = None::<char>;
+    let mut offset = 0;
+    while let Some(char) = text[offset..].chars().next() {
+        let is_at_boundary = !previous_char.is_some_and(|previous_char| previous_char.is_alphanumeric() || previous_char == '.');
+        if let Some(len) = Some(&text[offset..]).filter(|_| is_at_boundary).and_then(matcher) {
+            res.push_str(placeholder);
+            previous_char = text[..offset + len].chars().next_back();
+            offset += len;
+            continue;
+        }
+        res.push(char);
+        previous_char = Some(char);
+        offset += char.len_utf8();
+    }
+    res
+}
+
+/// Returns the length of the ASCII digits *text* starts with.
+fn digits(text: &str) -> usize {
+    text.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+/// Returns the length of *text*'s start if it is made of groups of exactly the given amount of
+/// digits separated by *separator*, like `2024-05-17`.
+fn digit_groups(text: &str, groups: &[usize], separator: char) -> Option<usize> {
+    let mut len = 0;
+    for (group_no, group_len) in groups.iter().enumerate() {
+        if group_no > 0 {
+            if !text[len..].starts_with(separator) { return None; }
+            len += separator.len_utf8();
+        }
+        if digits(&text[len..]) != *group_len { return None; }
+        len += group_len;
+    }
+    Some(len)
+}
+
+fn match_timestamp(text: &str) -> Option<usize> {
+    let time = |text: &str| -> Option<usize> {
+        let mut len = digit_groups(text, &[2, 2], ':')?;
+        if let Some(seconds_len) = text[len..].strip_prefix(':').and_then(|rest| digit_groups(rest, &[2], ':')) {
+            len += 1 + seconds_len;
+            if let Some(fraction_len) = text[len..].strip_prefix('.').map(digits).filter(|len| *len > 0) {
+                len += 1 + fraction_len;
+            }
+        }
+        Some(len)
+    };
+    let Some(mut len) = digit_groups(text, &[4, 2, 2], '-') else {
+        return digit_groups(text, &[2, 2, 2], ':').and_then(|_| time(text));
+    };
+    if let Some(time_len) = text[len..].strip_prefix(['T', ' ']).and_then(time) {
+        len += 1 + time_len;
+        let rest = &text[len..];
+        if rest.starts_with('Z') {
+            len += 1;
+        } else if let Some(zone_len) = rest.strip_prefix(['+', '-'])
+            .and_then(|zone| digit_groups(zone, &[2, 2], ':').or_else(|| digit_groups(zone, &[4], ':'))) {
+            len += 1 + zone_len;
+        }
+    }
+    Some(len)
+}
+
+fn match_duration(text: &str) -> Option<usize> {
+    let mut len = digits(text);
+    if len == 0 { return None; }
+    if let Some(fraction_len) = text[len..].strip_prefix('.').map(digits).filter(|len| *len > 0) {
+        len += 1 + fraction_len;
+    }
+    let unit = ["ns", "µs", "us", "ms", "s", "m", "h"].into_iter().find(|unit| text[len..].starts_with(unit))?;
+    len += unit.len();
+    let is_followed_by_word = text[len..].chars().next().is_some_and(char::is_alphanumeric);
+    (!is_followed_by_word).then_some(len)
+}
+
+fn match_path(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let is_windows_path = bytes.len() > 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && (bytes[2] == b'\\' || bytes[2] == b'/');
+    let is_segment_start = |byte: u8| !byte.is_ascii_whitespace() && !matches!(byte, b'/' | b'\\' | b'"' | b'\'' | b'`' | b'(' | b')' | b':');
+    let is_unix_path = bytes.len() > 1 && bytes[0] == b'/' && is_segment_start(bytes[1]);
+    let is_relative_path = bytes.len() > 2 && bytes.starts_with(b"./") && is_segment_start(bytes[2]);
+    if !is_windows_path && !is_unix_path && !is_relative_path {
+        return None;
+    }
+    let start = if is_windows_path { 2 } else { 0 };
+    let len = start + text[start..].find(|char: char| char.is_whitespace() || matches!(char, '"' | '\'' | '`' | '(' | ')' | ':'))
+        .unwrap_or(text.len() - start);
+    Some(len)
+}
+
+/// Reason why [check_diagnostics] failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiTestFailure {
+    /// The rendered error didn't match the expected file, *diff* holds the lines of the expected
+    /// file prefixed by `-`, those of the rendered error by `+`, and those common to both by a
+    /// space.
+    Mismatch { expected_path: PathBuf, diff: String },
+    /// A file couldn't be read or written.
+    Io { path: PathBuf, error: String },
+}
+
+impl Display for UiTestFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UiTestFailure::Mismatch { expected_path, diff } => {
+                write!(f, "Rendered error doesn't match {} (- expected, + actual), set {BLESS_ENV_VAR}=1 to update it:\n{diff}", expected_path.display())
+            }
+            UiTestFailure::Io { path, error } => write!(f, "Couldn't access {}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for UiTestFailure {}
+
+fn io_failure(path: &Path) -> impl FnOnce(std::io::Error) -> UiTestFailure + '_ {
+    move |error| UiTestFailure::Io { path: path.to_path_buf(), error: error.to_string() }
+}
+
+fn is_blessing() -> bool {
+    std::env::var(BLESS_ENV_VAR).map(|value| value != "0").unwrap_or(false)
+        || std::env::args().any(|arg| arg == "--bless")
+}
+
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n").trim_end().to_string()
+}
+
+/// Gives a line by line diff of both texts based on their longest common subsequence of lines.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let mut common_lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for expected_no in (0..expected.len()).rev() {
+        for actual_no in (0..actual.len()).rev() {
+            common_lengths[expected_no][actual_no] = if expected[expected_no] == actual[actual_no] {
+                common_lengths[expected_no + 1][actual_no + 1] + 1
+            } else {
+                common_lengths[expected_no + 1][actual_no].max(common_lengths[expected_no][actual_no + 1])
+            };
+        }
+    }
+    let (mut expected_no, mut actual_no) = (0, 0);
+    let mut lines = Vec::new();
+    while expected_no < expected.len() || actual_no < actual.len() {
+        if expected_no < expected.len() && actual_no < actual.len() && expected[expected_no] == actual[actual_no] {
+            lines.push(format!("  {}", expected[expected_no]));
+            expected_no += 1;
+            actual_no += 1;
+        } else if expected_no < expected.len()
+            && (actual_no == actual.len() || common_lengths[expected_no + 1][actual_no] >= common_lengths[expected_no][actual_no + 1]) {
+            lines.push(format!("- {}", expected[expected_no]));
+            expected_no += 1;
+        } else {
+            lines.push(format!("+ {}", actual[actual_no]));
+            actual_no += 1;
+        }
+    }
+    join_strings("\n", lines.iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::result_large_err)]
+    fn fails_on_first_char(input: &str) -> Result<(), SimpleError<'_>> {
+        Err(SimpleError::new().at(&input[..1]).error_detail("Unexpected char".to_string()))
+    }
+
+    #[test]
+    fn mismatches_are_reported_as_line_diffs() {
+        let directory = std::env::temp_dir().join(format!("simple_detailed_error_ui_diff_{}", std::process::id()));
+        let (input_path, expected_path) = (directory.join("input.txt"), directory.join("input.stderr"));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(&input_path, "?").unwrap();
+        std::fs::write(&expected_path, "At: ?\nError: Unexpected token\n").unwrap();
+        let failure = check_diagnostics(&input_path, &expected_path, fails_on_first_char).unwrap_err();
+        assert!(failure.to_string().ends_with("  At: ?\n- Error: Unexpected token\n+ Error: Unexpected char"), "{failure}");
+        std::fs::write(&expected_path, "At: ?\nError: Unexpected char\n").unwrap();
+        assert_diagnostics(&input_path, &expected_path, fails_on_first_char);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn volatile_parts_are_masked_with_placeholders() {
+        let masker = OutputMasker::volatile().mask_literal("runner-42", "[HOST]");
+        assert_eq!(masker.apply("At 2024-05-17T10:03:00.123Z took 3.2s reading /home/user/input.txt:3:4 on runner-42"),
+                   "At [TIMESTAMP] took [DURATION] reading [PATH]:3:4 on [HOST]");
+        assert_eq!(masker.apply("C:\\Users\\input.txt 10:03:00 15ms 200µs line 1 and column 5 v1.2s3"),
+                   "[PATH] [TIMESTAMP] [DURATION] [DURATION] line 1 and column 5 v1.2s3");
+        assert_rendered_eq("Error: took 3s\n", "Error: took 12ms", &masker);
+    }
+
+    #[test]
+    #[should_panic]
+    fn differing_outputs_panic_even_when_masked() {
+        assert_rendered_eq("Error: Missing value", "Error: Unexpected token", &OutputMasker::volatile());
+    }
+
+    #[test]
+    fn missing_expected_files_fail_unless_blessing_which_creates_them() {
+        let directory = std::env::temp_dir().join(format!("simple_detailed_error_ui_test_{}", std::process::id()));
+        let (input_path, expected_path) = (directory.join("input.txt"), directory.join("expected").join("input.stderr"));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(&input_path, "?").unwrap();
+        let missing = check(&input_path, &expected_path, &OutputMasker::new(), false, fails_on_first_char);
+        assert!(matches!(missing, Err(UiTestFailure::Io { path, .. }) if path == expected_path));
+        assert_eq!(check(&input_path, &expected_path, &OutputMasker::new(), true, fails_on_first_char), Ok(()));
+        assert_eq!(check(&input_path, &expected_path, &OutputMasker::new(), false, fails_on_first_char), Ok(()));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn only_paths_are_masked() {
+        let masker = OutputMasker::new().mask_paths("[PATH]");
+        assert_eq!(masker.apply("At: let x = 1; // comment"), "At: let x = 1; // comment");
+        assert_eq!(masker.apply("In /home/user/a.txt and C:\\a.txt and ./a.txt: 1 / 2"), "In [PATH] and [PATH] and [PATH]: 1 / 2");
+    }
+}
@@ -0,0 +1,63 @@
+use js_sys::Error as JsError;
+use wasm_bindgen::JsValue;
+
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// This is only implemented when using the wasm feature.
+    ///
+    /// Turns this error into a structured JavaScript object holding the same fields it has when
+    /// serialized (See [SimpleErrorDisplayInfo]), so browser applications can lay errors out on
+    /// their own, like showing causes as a collapsible tree.
+    pub fn to_js_value(&self) -> Result<JsValue, serde_wasm_bindgen::Error> {
+        serde_wasm_bindgen::to_value(self)
+    }
+
+    /// This is only implemented when using the wasm feature.
+    ///
+    /// Turns this error into a JavaScript `Error` whose message is this error displayed without
+    /// colors as in [SimpleErrorDisplayInfo::as_display_string], so it reads the same as on the
+    /// command line, whose name is its severity, like `Warning`, and whose `cause` holds this error
+    /// as given by [SimpleErrorDisplayInfo::to_js_value].
+    ///
+    /// JavaScript values only exist when running on WebAssembly, so this example isn't run here:
+    ///
+    /// ```rust,no_run
+    /// use simple_detailed_error::{Severity, SimpleError, SimpleErrorExplanation};
+    ///
+    /// let explanation = SimpleErrorExplanation::new().explanation("Unused variable x").severity(Severity::Warning);
+    /// let error = SimpleError::new().error_detail(explanation).as_display_struct(false);
+    /// let js_error = error.to_js_error();
+    /// assert_eq!(String::from(js_error.name()), Severity::Warning.name());
+    /// assert_eq!(String::from(js_error.message()), error.as_display_string());
+    /// ```
+    pub fn to_js_error(&self) -> JsError {
+        let mut plain = self.clone();
+        plain.strip_ansi();
+        let error = JsError::new(&plain.as_display_string());
+        error.set_name(self.severity.name());
+        if let Ok(value) = plain.to_js_value() {
+            error.set_cause(&value);
+        }
+        error
+    }
+}
+
+/// This is only implemented when using the wasm feature.
+///
+/// Turns the error into a structured JavaScript object through
+/// [SimpleErrorDisplayInfo::to_js_value], or into the error explaining why it couldn't.
+impl From<SimpleErrorDisplayInfo> for JsValue {
+    fn from(value: SimpleErrorDisplayInfo) -> Self {
+        value.to_js_value().unwrap_or_else(JsValue::from)
+    }
+}
+
+/// This is only implemented when using the wasm feature.
+///
+/// Turns the error into a JavaScript `Error` through [SimpleErrorDisplayInfo::to_js_error].
+impl From<SimpleErrorDisplayInfo> for JsError {
+    fn from(value: SimpleErrorDisplayInfo) -> Self {
+        value.to_js_error()
+    }
+}
@@ -0,0 +1,98 @@
+use std::io::{self, Write};
+
+use crate::color_choice::ColorChoice;
+use crate::display_options::DisplayOptions;
+use crate::simple_error::SimpleError;
+use crate::simple_error_display_info::SimpleErrorDisplayInfo;
+
+impl SimpleErrorDisplayInfo {
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Writes into *sink* the same text given by
+    /// [SimpleErrorDisplayInfo::as_display_string_with_options], but rendering and writing the
+    /// causes of this error one by one instead of building the whole text first, and flushing
+    /// *sink* after every one of them, so what was written is kept even if the program crashes
+    /// halfway through a huge error tree.
+    pub fn write_report(&self, sink: &mut dyn Write, options: &DisplayOptions) -> io::Result<()> {
+        let shown = self.shown_with(options);
+        let (description, causes) = shown.__as_display_parts(false, options);
+        sink.write_all(description.as_bytes())?;
+        if let Some(causes) = causes {
+            write!(sink, "\n{}", causes.prefix)?;
+            sink.flush()?;
+            let mut is_line_start = false;
+            for chunk in causes.chunks {
+                write_indented(sink, &chunk, causes.spaces, &mut is_line_start)?;
+                sink.flush()?;
+            }
+        }
+        sink.flush()
+    }
+}
+
+impl SimpleError<'_> {
+    /// This is only implemented when using the std feature, enabled by default.
+    ///
+    /// Writes this error into *sink*, like stderr, a file or a socket, this is the same as calling
+    /// [SimpleError::as_display_struct_with_context] with [DisplayOptions::context_lines] followed
+    /// by [SimpleErrorDisplayInfo::write_report].
+    pub fn write_report<Choice: Into<ColorChoice>>(&self, sink: &mut dyn Write, colorize: Choice, options: &DisplayOptions) -> io::Result<()> {
+        self.as_display_struct_with_context(colorize, options.context_lines).write_report(sink, options)
+    }
+}
+
+/// Writes *text* into *sink* indenting every line by *spaces*, but the one being continued, where
+/// *is_line_start* tells if the text written before ended a line, being updated afterwards.
+fn write_indented(sink: &mut dyn Write, text: &str, spaces: usize, is_line_start: &mut bool) -> io::Result<()> {
+    for line in text.split_inclusive('\n') {
+        if *is_line_start {
+            write!(sink, "{:spaces$}", "")?;
+        }
+        sink.write_all(line.as_bytes())?;
+        *is_line_start = line.ends_with('\n');
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FlushCountingSink {
+        bytes: Vec<u8>,
+        flushes: usize,
+    }
+
+    impl Write for FlushCountingSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn written_reports_match_their_display_strings_and_flush_per_cause() {
+        let error = |explanation: &str| SimpleError::new().error_detail(explanation.to_string());
+        let tree = error("Couldn't compile\nmain.script")
+            .with_cause(error("Invalid function").with_cause(error("Missing name")).with_cause(error("Missing\nbody")))
+            .with_cause(error("Missing ;"))
+            .with_cause(SimpleError::new());
+        let wrapped = DisplayOptions { wrap_width: Some(12), ..DisplayOptions::default() };
+        let separated = DisplayOptions { cause_separator: "\n\n".to_string(), ..DisplayOptions::default() };
+        for options in [DisplayOptions::default(), wrapped, separated] {
+            for error in [&tree, &error("Missing ;"), &error("Invalid\nname").with_cause(error("Unexpected\nspace"))] {
+                let mut sink = FlushCountingSink { bytes: Vec::new(), flushes: 0 };
+                error.write_report(&mut sink, false, &options).unwrap();
+                assert_eq!(String::from_utf8(sink.bytes).unwrap(), error.as_display_string_with_options(false, &options));
+            }
+        }
+        let mut sink = FlushCountingSink { bytes: Vec::new(), flushes: 0 };
+        tree.write_report(&mut sink, false, &DisplayOptions::default()).unwrap();
+        assert_eq!(sink.flushes, 4);
+    }
+}
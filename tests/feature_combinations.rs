@@ -0,0 +1,105 @@
+//! Exercises rendering, serialization and conversions under whichever features are enabled, so
+//! running it for every combination of features (See the CI workflow) finds pairs of features that
+//! don't compile or behave together.
+
+// Without colorization features the marker functions give (), which is still passed to keep the
+// same explain_error implementation for every feature set.
+#![cfg_attr(not(any(feature = "colorization", feature = "colorization-anstyle")), allow(clippy::unit_arg))]
+
+use simple_detailed_error::{Report, SimpleError, SimpleErrorDetail, SimpleErrorExplanation};
+
+#[derive(Debug)]
+enum ScriptError<'input> {
+    MissingVariable { variable_name: &'input str },
+    CouldNotCompile,
+}
+
+impl<'input> SimpleErrorDetail for ScriptError<'input> {
+    fn explain_error(&self) -> SimpleErrorExplanation<'_> {
+        match self {
+            ScriptError::MissingVariable { variable_name } => SimpleErrorExplanation::new()
+                .explanation(format!("Variable {variable_name} doesn't exist."))
+                .solution("Declare it before using it.")
+                .code("E001")
+                .colorization_marker(variable_name, simple_detailed_error::markers::error_span())
+                .whole_input_colorization(simple_detailed_error::markers::context()),
+            ScriptError::CouldNotCompile => SimpleErrorExplanation::new()
+                .explanation("Couldn't compile code."),
+        }
+    }
+}
+
+const SOURCE: &str = "let a = 1;\nlet b = a + c;";
+
+fn compile_error() -> SimpleError<'static> {
+    let variable_name = &SOURCE[23..24];
+    ScriptError::CouldNotCompile.with_cause(
+        SimpleError::new()
+            .with_source(SOURCE)
+            .at_span(23..24)
+            .error_detail(ScriptError::MissingVariable { variable_name }))
+}
+
+#[test]
+fn renders() {
+    let display = compile_error().as_display_struct(false);
+    let rendered = display.as_display_string();
+    assert!(rendered.contains("Error: Couldn't compile code."), "{rendered}");
+    assert!(rendered.contains("Code: E001"), "{rendered}");
+    assert!(rendered.contains("Solution: Declare it before using it."), "{rendered}");
+    assert_eq!(compile_error().summary(), "Couldn't compile code. (1 cause: Variable c doesn't exist.)");
+    assert_eq!(display.as_gcc_string("main.script"),
+               "main.script: error: Couldn't compile code.\nmain.script:2:13: note: Variable c doesn't exist. [E001]");
+    let report = Report::new().with_error(compile_error());
+    assert_eq!(report.as_display_struct(false).as_display_string(), rendered);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn renders_with_std() {
+    use simple_detailed_error::ui_test::{assert_rendered_eq, OutputMasker};
+
+    let error = std::io::Error::new(std::io::ErrorKind::NotFound, "/home/user/main.script not found");
+    let rendered = SimpleError::from_std_error(&error).as_display_struct(false).as_display_string();
+    assert_rendered_eq(rendered, "Error: [PATH] not found", &OutputMasker::volatile());
+    let rendered = simple_detailed_error::with_color_override(false, || compile_error().to_string());
+    assert!(!rendered.contains('\u{1b}'), "{rendered}");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes() {
+    let display = compile_error().as_display_struct(false);
+    let json = serde_json::to_string(&display).unwrap();
+    assert_eq!(serde_json::from_str::<simple_detailed_error::SimpleErrorDisplayInfo>(&json).unwrap(), display);
+    let report = Report::new().with_error(compile_error()).with_tool_info("mytool", "1.4.2").as_display_struct(false);
+    let json = serde_json::to_string(&report).unwrap();
+    assert_eq!(serde_json::from_str::<simple_detailed_error::report::ReportDisplayInfo>(&json).unwrap(), report);
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn renders_html() {
+    let html = compile_error().as_display_struct(false).to_html();
+    assert!(html.starts_with("<div class=\"sde-error sde-severity-error\">"), "{html}");
+    assert!(html.contains("<ol class=\"sde-causes\"><li>"), "{html}");
+}
+
+#[cfg(feature = "codespan")]
+#[test]
+fn converts_with_codespan() {
+    let diagnostic = compile_error().with_source(SOURCE).to_codespan_diagnostic(());
+    assert_eq!(diagnostic.message, "Couldn't compile code.");
+    assert_eq!(diagnostic.labels.len(), 1);
+    let error = SimpleError::from_codespan_diagnostic(&diagnostic, SOURCE);
+    assert!(error.summary().starts_with("Couldn't compile code."), "{}", error.summary());
+}
+
+#[cfg(feature = "colorization")]
+#[test]
+fn colorizes_through_themes() {
+    let theme = simple_detailed_error::Theme::default();
+    let _ = (theme.error_span, theme.context, theme.hint);
+    let display = compile_error().as_display_struct(true);
+    assert_eq!(display.summary(), compile_error().summary());
+}